@@ -0,0 +1,32 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustieee::{
+    ieee802154::{
+        frame::Frame,
+        parser::{FrameFraming, FrameIter},
+        Ieee802154,
+    },
+    parser::{ParseMode, Parser},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Ieee802154::parse(data);
+    let _ = Ieee802154::parse_header(data);
+
+    if let Ok(Frame::Data(frame)) = Ieee802154::parse_with_mode(data, ParseMode::Lenient) {
+        for ie in frame.header.header_ies.flatten() {
+            let _ = ie.element();
+        }
+
+        for ie in frame.payload_ies.flatten() {
+            for nested in ie.nested().into_iter().flatten().flatten() {
+                let _ = nested.element();
+            }
+        }
+    }
+
+    for frame in FrameIter::new(data, FrameFraming::LengthPrefixed) {
+        let _ = frame;
+    }
+});
@@ -0,0 +1,37 @@
+#![no_main]
+
+use libfuzzer_sys::{
+    arbitrary::{Arbitrary, Unstructured},
+    fuzz_target,
+};
+use rustieee::{
+    composer::Composer,
+    ieee802154::{frame::Frame, parser::ParseContext, Ieee802154, PsduLimit},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let context = ParseContext {
+        limit: PsduLimit::Sun,
+        ..Default::default()
+    };
+
+    // parse(compose(f)) == f for every legal frame
+    if let Ok(frame) = Frame::arbitrary(&mut Unstructured::new(data)) {
+        let composed = Ieee802154::compose(&frame).unwrap();
+        let parsed = Ieee802154::parse_with_context(&composed, context).unwrap();
+
+        assert_eq!(parsed, frame);
+        assert_eq!(Ieee802154::compose(&parsed).unwrap(), composed);
+    }
+
+    // compose(parse(b)) == b only holds once reserved bits are cleared and
+    // the PAN ID compression bit matches the one derived by the composer, so
+    // the composed bytes are checked instead of the input
+    if let Ok(frame) = Ieee802154::parse_with_context(data, context) {
+        if let Ok(composed) = Ieee802154::compose(&frame) {
+            let parsed = Ieee802154::parse_with_context(&composed, context).unwrap();
+
+            assert_eq!(Ieee802154::compose(&parsed).unwrap(), composed);
+        }
+    }
+});
@@ -1,7 +1,58 @@
-#[const_trait]
-pub trait Address {
-    type Inner;
+/// Defines [`Address`], `$const` being the `const` keyword of a const trait
+/// and `$bound` the bound on the inner value of the provided methods. The
+/// const trait syntax is rejected by stable compilers even in code disabled
+/// by `cfg`, so it only appears in macro invocations.
+macro_rules! address_trait {
+    ($($const:ident)?; $($bound:tt)*) => {
+        /// Address of a protocol. With the `nightly` feature, it is a const
+        /// trait so that its methods can be called in const contexts.
+        pub $($const)? trait Address {
+            type Inner;
 
-    fn broadcast() -> Self;
-    fn value(&self) -> Self::Inner;
+            fn broadcast() -> Self;
+            fn from_value(inner: Self::Inner) -> Self;
+            fn value(&self) -> Self::Inner;
+
+            /// Returns whether the value is the broadcast one.
+            fn is_broadcast(&self) -> bool
+            where
+                Self: Copy,
+                Self::Inner: Copy + $($bound)*,
+            {
+                self.value() == Self::broadcast().value()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "nightly")]
+address_trait!(const; [const] PartialEq);
+#[cfg(not(feature = "nightly"))]
+address_trait!(; PartialEq);
+
+/// Implements [`Address`] for `$ty`, as a const impl with the `nightly`
+/// feature.
+#[cfg(feature = "nightly")]
+#[allow(unused_macros)]
+macro_rules! impl_address {
+    ($ty:ty { $($body:tt)* }) => {
+        impl const $crate::address::Address for $ty {
+            $($body)*
+        }
+    };
 }
+
+/// Implements [`Address`] for `$ty`.
+#[cfg(not(feature = "nightly"))]
+#[allow(unused_macros)]
+macro_rules! impl_address {
+    ($ty:ty { $($body:tt)* }) => {
+        impl $crate::address::Address for $ty {
+            $($body)*
+        }
+    };
+}
+
+// Only used by the protocols enabled by features
+#[allow(unused_imports)]
+pub(crate) use impl_address;
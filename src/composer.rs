@@ -2,10 +2,29 @@ use alloc::vec::Vec;
 use thiserror_no_std::Error;
 
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Invalid header")]
     InvalidHeader,
+    #[error("Frame too long: {len} bytes, at most {max} allowed")]
+    FrameTooLong { len: usize, max: usize },
+    #[error("Buffer too small: {needed} bytes needed")]
+    BufferTooSmall { needed: usize },
+    #[error("Address required by the frame is missing")]
+    MissingAddress,
+    #[error("Invalid addressing for the frame")]
+    InvalidAddressing,
+    #[error("Security enabled without auxiliary security header")]
+    MissingSecurityHeader,
+    #[error("Invalid information element {id:#x}")]
+    InvalidIe { id: u16 },
+    #[error("Invalid command identifier: {0:#x}")]
+    InvalidCommandId(u8),
+    #[error("Invalid association status: {0:#x}")]
+    InvalidAssociationStatus(u8),
+    #[error("Invalid disassociation reason: {0:#x}")]
+    InvalidDisassociationReason(u8),
 }
 
 pub trait Composer<'a> {
@@ -13,3 +32,15 @@ pub trait Composer<'a> {
 
     fn compose(value: &'a Self::Value) -> Result<Vec<u8>, Error>;
 }
+
+/// Composer writing into a caller-provided buffer, without allocating.
+pub trait ComposeInto<'a>: Composer<'a> {
+    /// Writes `value` at the start of `buf`, and returns the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` cannot hold the whole value, returns `BufferTooSmall` with
+    /// the size needed.
+    fn compose_into(value: &'a Self::Value, buf: &mut [u8]) -> Result<usize, Error>;
+}
@@ -9,5 +9,5 @@ pub enum Error {
     Composer(#[from] composer::Error),
 
     #[error("parser: {0}")]
-    Parser(#[from] parser::Error),
+    Parser(#[from] parser::ParseError),
 }
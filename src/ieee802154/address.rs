@@ -1,94 +1,920 @@
-use crate::address::Address;
+use core::fmt;
+
+use crate::address::impl_address;
+
+/// Digits of the hexadecimal forms printed by the `uDisplay` impls.
+#[cfg(feature = "ufmt")]
+const HEX_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+#[cfg(feature = "ufmt")]
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// Writes `value` as `0x` followed by 4 uppercase hexadecimal digits, ufmt
+/// having no hexadecimal formatting.
+#[cfg(feature = "ufmt")]
+fn write_hex_u16<W>(f: &mut ufmt::Formatter<'_, W>, value: u16) -> Result<(), W::Error>
+where
+    W: ufmt::uWrite + ?Sized,
+{
+    f.write_str("0x")?;
+
+    for shift in [12, 8, 4, 0] {
+        f.write_char(HEX_UPPER[usize::from((value >> shift) & 0xF)] as char)?;
+    }
+
+    Ok(())
+}
 
 /// The PAN ID is an ID used to identify a group of devices. An address must be
 /// associated with a PAN ID.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PanId(u16);
 
-impl const Address for PanId {
+impl_address!(PanId {
     type Inner = u16;
 
     fn broadcast() -> Self {
-        PanId(0xFFFF)
+        PanId::BROADCAST
+    }
+    fn from_value(inner: Self::Inner) -> Self {
+        PanId(inner)
     }
     fn value(&self) -> Self::Inner {
-        self.0
+        PanId::value(self)
     }
-}
+});
 
 impl PanId {
+    /// PAN ID accepted by every device in range.
+    pub const BROADCAST: PanId = PanId(0xFFFF);
+
     pub const fn new(address: u16) -> Self {
         PanId(address)
     }
+
+    /// The broadcast PAN identifier, `0xFFFF`.
+    pub const fn broadcast() -> Self {
+        PanId::BROADCAST
+    }
+
+    pub const fn value(&self) -> u16 {
+        self.0
+    }
+
+    pub const fn is_broadcast(&self) -> bool {
+        self.0 == PanId::BROADCAST.0
+    }
+}
+
+/// Prints the PAN ID as `0xABCD`.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for PanId {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        write_hex_u16(f, self.0)
+    }
+}
+
+impl hash32::Hash for PanId {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32::Hash::hash(&self.0, state);
+    }
+}
+
+impl From<u16> for PanId {
+    fn from(value: u16) -> Self {
+        PanId(value)
+    }
+}
+
+impl From<PanId> for u16 {
+    fn from(pan: PanId) -> Self {
+        pan.0
+    }
 }
 
+/// Prints the PAN ID as `0xABCD`.
+impl fmt::Display for PanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#06X}", self.0)
+    }
+}
+
+impl fmt::LowerHex for PanId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+/// Short or extended address.
+///
+/// Addresses are ordered short ones first, then by PAN ID, then by value.
+/// This order is stable, so sorted tables can be persisted.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AddressKind {
     Short(ShortAddress),
     Long(LongAddress),
 }
 
+impl AddressKind {
+    pub const fn pan_id(&self) -> PanId {
+        match self {
+            AddressKind::Short(address) => address.pan_id(),
+            AddressKind::Long(address) => address.pan_id(),
+        }
+    }
+
+    /// Returns whether the address is the broadcast address of its kind,
+    /// whatever its PAN ID.
+    pub const fn is_broadcast(&self) -> bool {
+        match self {
+            AddressKind::Short(address) => address.is_broadcast(),
+            AddressKind::Long(address) => address.is_broadcast(),
+        }
+    }
+
+    pub const fn is_short(&self) -> bool {
+        matches!(self, AddressKind::Short(_))
+    }
+
+    pub const fn is_long(&self) -> bool {
+        matches!(self, AddressKind::Long(_))
+    }
+
+    pub const fn as_short(&self) -> Option<&ShortAddress> {
+        match self {
+            AddressKind::Short(address) => Some(address),
+            AddressKind::Long(_) => None,
+        }
+    }
+
+    pub const fn as_long(&self) -> Option<&LongAddress> {
+        match self {
+            AddressKind::Short(_) => None,
+            AddressKind::Long(address) => Some(address),
+        }
+    }
+
+    /// Mode of the address field holding the address.
+    /// Chapter 7.2.2.9
+    pub const fn addressing_mode(&self) -> AddressingMode {
+        match self {
+            AddressKind::Short(_) => AddressingMode::Short,
+            AddressKind::Long(_) => AddressingMode::Long,
+        }
+    }
+
+    /// Size in bytes of the address field, and of the PAN ID field
+    /// preceding it if `include_pan` is set.
+    pub const fn len_on_wire(&self, include_pan: bool) -> usize {
+        self.addressing_mode().size(include_pan)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for AddressKind {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        match self {
+            AddressKind::Short(address) => ufmt::uDisplay::fmt(address, f),
+            AddressKind::Long(address) => ufmt::uDisplay::fmt(address, f),
+        }
+    }
+}
+
+impl fmt::Display for AddressKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressKind::Short(address) => fmt::Display::fmt(address, f),
+            AddressKind::Long(address) => fmt::Display::fmt(address, f),
+        }
+    }
+}
+
+/// Hashes the variant then the address, so that addresses can key the maps
+/// of `heapless`.
+impl hash32::Hash for AddressKind {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        match self {
+            AddressKind::Short(address) => {
+                hash32::Hash::hash(&0u8, state);
+                hash32::Hash::hash(address, state);
+            }
+            AddressKind::Long(address) => {
+                hash32::Hash::hash(&1u8, state);
+                hash32::Hash::hash(address, state);
+            }
+        }
+    }
+}
+
+impl From<ShortAddress> for AddressKind {
+    fn from(address: ShortAddress) -> Self {
+        AddressKind::Short(address)
+    }
+}
+
+impl From<LongAddress> for AddressKind {
+    fn from(address: LongAddress) -> Self {
+        AddressKind::Long(address)
+    }
+}
+
+/// Returns the extended address back as the error.
+impl TryFrom<AddressKind> for ShortAddress {
+    type Error = AddressKind;
+
+    fn try_from(address: AddressKind) -> Result<Self, Self::Error> {
+        match address {
+            AddressKind::Short(address) => Ok(address),
+            address => Err(address),
+        }
+    }
+}
+
+/// Returns the short address back as the error.
+impl TryFrom<AddressKind> for LongAddress {
+    type Error = AddressKind;
+
+    fn try_from(address: AddressKind) -> Result<Self, Self::Error> {
+        match address {
+            AddressKind::Long(address) => Ok(address),
+            address => Err(address),
+        }
+    }
+}
+
+/// Destination addressing fields of a MAC header.
+///
+/// IEEE 802.15.4-2015 and multipurpose frames without source address can
+/// carry a destination PAN ID without destination address, which an
+/// `Option<AddressKind>` cannot represent.
+/// Chapter 7.2.2.6, Table 7-2
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AddressField {
+    /// Neither PAN ID nor address field
+    #[default]
+    Absent,
+    /// PAN ID field without address field
+    PanOnly(PanId),
+    /// Address field, preceded by its PAN ID field unless elided
+    Present(AddressKind),
+}
+
+impl AddressField {
+    pub const fn address(&self) -> Option<&AddressKind> {
+        match self {
+            AddressField::Present(address) => Some(address),
+            AddressField::Absent | AddressField::PanOnly(_) => None,
+        }
+    }
+
+    /// PAN ID of the field, `None` if it is absent.
+    pub const fn pan_id(&self) -> Option<PanId> {
+        match self {
+            AddressField::Absent => None,
+            AddressField::PanOnly(pan) => Some(*pan),
+            AddressField::Present(address) => Some(address.pan_id()),
+        }
+    }
+
+    pub const fn is_absent(&self) -> bool {
+        matches!(self, AddressField::Absent)
+    }
+
+    /// Returns whether the field holds a broadcast address, whatever its PAN
+    /// ID.
+    pub const fn is_broadcast(&self) -> bool {
+        match self {
+            AddressField::Present(address) => address.is_broadcast(),
+            AddressField::Absent | AddressField::PanOnly(_) => false,
+        }
+    }
+
+    /// Mode of the address field, `None` unless an address is present.
+    /// Chapter 7.2.2.9
+    pub const fn addressing_mode(&self) -> AddressingMode {
+        match self {
+            AddressField::Present(address) => address.addressing_mode(),
+            AddressField::Absent | AddressField::PanOnly(_) => AddressingMode::None,
+        }
+    }
+
+    /// Size in bytes of the address field, and of the PAN ID field
+    /// preceding it if `include_pan` is set.
+    pub const fn len_on_wire(&self, include_pan: bool) -> usize {
+        match self {
+            AddressField::Absent => 0,
+            _ => self.addressing_mode().size(include_pan),
+        }
+    }
+}
+
+impl From<AddressKind> for AddressField {
+    fn from(address: AddressKind) -> Self {
+        AddressField::Present(address)
+    }
+}
+
+impl From<ShortAddress> for AddressField {
+    fn from(address: ShortAddress) -> Self {
+        AddressField::Present(address.into())
+    }
+}
+
+impl From<LongAddress> for AddressField {
+    fn from(address: LongAddress) -> Self {
+        AddressField::Present(address.into())
+    }
+}
+
+impl From<Option<AddressKind>> for AddressField {
+    fn from(address: Option<AddressKind>) -> Self {
+        address.map_or(AddressField::Absent, AddressField::Present)
+    }
+}
+
+/// Size in bytes of a PAN ID field.
+pub const PAN_ID_SIZE: usize = 2;
+/// Size in bytes of a short address field.
+pub const SHORT_ADDRESS_SIZE: usize = 2;
+/// Size in bytes of an extended address field.
+pub const LONG_ADDRESS_SIZE: usize = 8;
+
+/// Short address of a device associated without being allocated one.
+const SHORT_ADDRESS_UNASSIGNED: u16 = 0xFFFE;
+
+/// Universal/Local bit of the first octet of an EUI-64, set for locally
+/// administered addresses.
+const EUI64_LOCAL: u8 = 0x02;
+/// Individual/Group bit of the first octet of an EUI-64, set for group
+/// addresses.
+const EUI64_GROUP: u8 = 0x01;
+/// Bits of the extension identifier following a 24-bit OUI.
+const EUI64_EXTENSION_MASK: u64 = 0xFF_FFFF_FFFF;
+const EUI64_OUI_MASK: u32 = 0xFF_FFFF;
+const EUI64_OUI_OFFSET: usize = 40;
+
+const ADDRESSING_NONE: u8 = 0x0;
+const ADDRESSING_SHORT: u8 = 0x2;
+const ADDRESSING_LONG: u8 = 0x3;
+
+/// Destination or source addressing mode of the frame control field, the
+/// value 0b01 being reserved.
+/// Chapter 7.2.2.9
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressingMode {
+    /// No address field
+    #[default]
+    None,
+    Short,
+    Long,
+}
+
+impl From<&AddressKind> for AddressingMode {
+    fn from(address: &AddressKind) -> Self {
+        address.addressing_mode()
+    }
+}
+
+impl From<&AddressField> for AddressingMode {
+    fn from(field: &AddressField) -> Self {
+        field.addressing_mode()
+    }
+}
+
+impl AddressingMode {
+    /// # Error
+    ///
+    /// If `value` is the reserved mode or does not fit in 2 bits, returns
+    /// `InvalidAddressingMode`.
+    pub const fn from_bits(value: u8) -> Result<Self, crate::parser::Error> {
+        match value {
+            ADDRESSING_NONE => Ok(AddressingMode::None),
+            ADDRESSING_SHORT => Ok(AddressingMode::Short),
+            ADDRESSING_LONG => Ok(AddressingMode::Long),
+            _ => Err(crate::parser::Error::InvalidAddressingMode(value)),
+        }
+    }
+
+    pub const fn bits(&self) -> u8 {
+        match self {
+            AddressingMode::None => ADDRESSING_NONE,
+            AddressingMode::Short => ADDRESSING_SHORT,
+            AddressingMode::Long => ADDRESSING_LONG,
+        }
+    }
+
+    /// Mode of the address field holding `address`.
+    pub const fn of(address: &Option<AddressKind>) -> Self {
+        match address {
+            None => AddressingMode::None,
+            Some(address) => address.addressing_mode(),
+        }
+    }
+
+    /// Size in bytes of the address field, and of the PAN ID field
+    /// preceding it if `pan_id_present` is set.
+    pub const fn size(&self, pan_id_present: bool) -> usize {
+        let address = match self {
+            AddressingMode::None => 0,
+            AddressingMode::Short => SHORT_ADDRESS_SIZE,
+            AddressingMode::Long => LONG_ADDRESS_SIZE,
+        };
+
+        address + pan_id_present as usize * PAN_ID_SIZE
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ShortAddress(PanId, u16);
 
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LongAddress(PanId, u64);
 
-impl const Address for ShortAddress {
+/// Prints the PAN ID then the address, as `0xABCD/0x0001`.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for ShortAddress {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        write_hex_u16(f, self.0 .0)?;
+        f.write_char('/')?;
+        write_hex_u16(f, self.1)
+    }
+}
+
+/// Prints the EUI-64 as colon-separated octets, first octet first, as
+/// `00:12:4b:00:1c:a1:b2:c3`. The PAN ID is left out.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for LongAddress {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        for (i, octet) in self.1.to_be_bytes().into_iter().enumerate() {
+            if i != 0 {
+                f.write_char(':')?;
+            }
+
+            f.write_char(HEX_LOWER[usize::from(octet >> 4)] as char)?;
+            f.write_char(HEX_LOWER[usize::from(octet & 0xF)] as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the PAN ID then the address, as `0xABCD/0x0001`.
+impl fmt::Display for ShortAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:#06X}", self.0, self.1)
+    }
+}
+
+/// Prints the address value, without its PAN ID.
+impl fmt::LowerHex for ShortAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.1, f)
+    }
+}
+
+/// Prints the EUI-64 as colon-separated octets, first octet first, as
+/// `00:12:4b:00:1c:a1:b2:c3`. The PAN ID is left out.
+impl fmt::Display for LongAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, octet) in self.1.to_be_bytes().into_iter().enumerate() {
+            if i != 0 {
+                f.write_str(":")?;
+            }
+
+            write!(f, "{:02x}", octet)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the address value, without its PAN ID.
+impl fmt::LowerHex for LongAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.1, f)
+    }
+}
+
+impl hash32::Hash for ShortAddress {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32::Hash::hash(&self.0, state);
+        hash32::Hash::hash(&self.1, state);
+    }
+}
+
+impl hash32::Hash for LongAddress {
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        hash32::Hash::hash(&self.0, state);
+        hash32::Hash::hash(&self.1, state);
+    }
+}
+
+impl_address!(ShortAddress {
     type Inner = u16;
 
     fn broadcast() -> Self {
-        ShortAddress(PanId::broadcast(), 0xFFFF)
+        ShortAddress::broadcast()
+    }
+
+    /// The address takes the broadcast PAN ID.
+    fn from_value(inner: Self::Inner) -> Self {
+        ShortAddress(PanId::BROADCAST, inner)
     }
 
     fn value(&self) -> Self::Inner {
-        self.1
+        ShortAddress::value(self)
     }
-}
+});
 
 impl ShortAddress {
     pub const fn new(pan: PanId, address: u16) -> Self {
         ShortAddress(pan, address)
     }
 
+    /// The broadcast short address, `0xFFFF` on the broadcast PAN.
+    pub const fn broadcast() -> Self {
+        ShortAddress(PanId::BROADCAST, 0xFFFF)
+    }
+
+    pub const fn value(&self) -> u16 {
+        self.1
+    }
+
     pub const fn pan_id(&self) -> PanId {
         self.0
     }
+
+    /// Returns whether the address is the broadcast short address, whatever
+    /// its PAN ID.
+    pub const fn is_broadcast(&self) -> bool {
+        self.1 == ShortAddress::broadcast().1
+    }
+
+    /// Returns whether the address is the one of a device associated without
+    /// being allocated a short address, which then uses its extended one.
+    pub const fn is_unassigned(&self) -> bool {
+        self.1 == SHORT_ADDRESS_UNASSIGNED
+    }
+
+    /// Returns whether the address can designate a single device, neither
+    /// broadcast nor unassigned.
+    pub const fn is_valid_unicast(&self) -> bool {
+        self.1 < SHORT_ADDRESS_UNASSIGNED
+    }
 }
 
-impl const Address for LongAddress {
+impl_address!(LongAddress {
     type Inner = u64;
 
     fn broadcast() -> Self {
-        LongAddress(PanId::broadcast(), 0xFFFFFFFF)
+        LongAddress::broadcast()
+    }
+
+    /// The address takes the broadcast PAN ID.
+    fn from_value(inner: Self::Inner) -> Self {
+        LongAddress(PanId::BROADCAST, inner)
     }
 
     fn value(&self) -> Self::Inner {
-        self.1
+        LongAddress::value(self)
     }
-}
+});
 
 impl LongAddress {
     pub const fn new(pan: PanId, address: u64) -> Self {
         LongAddress(pan, address)
     }
 
+    /// The broadcast long address, all ones on the broadcast PAN.
+    pub const fn broadcast() -> Self {
+        LongAddress(PanId::BROADCAST, 0xFFFF_FFFF_FFFF_FFFF)
+    }
+
+    pub const fn value(&self) -> u64 {
+        self.1
+    }
+
+    /// Builds the address from its bytes in on-air order, least significant
+    /// octet of the EUI-64 first.
+    /// Chapter 7.2.1
+    pub const fn from_bytes(pan: PanId, bytes: [u8; LONG_ADDRESS_SIZE]) -> Self {
+        LongAddress(pan, u64::from_le_bytes(bytes))
+    }
+
+    /// Builds the EUI-64 made of a 24-bit OUI and a 40-bit extension
+    /// identifier, the bits of each above their size being ignored.
+    pub const fn from_oui(pan: PanId, oui: u32, extension: u64) -> Self {
+        LongAddress(
+            pan,
+            ((oui & EUI64_OUI_MASK) as u64) << EUI64_OUI_OFFSET
+                | (extension & EUI64_EXTENSION_MASK),
+        )
+    }
+
+    /// Bytes of the address in on-air order, the inverse of
+    /// [`LongAddress::from_bytes`].
+    pub const fn to_bytes(&self) -> [u8; LONG_ADDRESS_SIZE] {
+        self.1.to_le_bytes()
+    }
+
+    /// First octet of the EUI-64 as printed, sent last on air.
+    const fn first_octet(&self) -> u8 {
+        self.1.to_be_bytes()[0]
+    }
+
+    /// Returns whether the U/L bit is set, the address not being assigned
+    /// from an OUI.
+    pub const fn is_locally_administered(&self) -> bool {
+        self.first_octet() & EUI64_LOCAL != 0
+    }
+
+    /// Returns whether the I/G bit is set, the address designating a group.
+    pub const fn is_group(&self) -> bool {
+        self.first_octet() & EUI64_GROUP != 0
+    }
+
     pub const fn pan_id(&self) -> PanId {
         self.0
     }
+
+    /// Returns whether the address is the all-ones extended address,
+    /// whatever its PAN ID.
+    pub const fn is_broadcast(&self) -> bool {
+        self.1 == LongAddress::broadcast().1
+    }
+}
+
+/// Reads and writes the PAN ID and address fields at an offset of a frame,
+/// in little-endian order. On success, the offset is moved past the field,
+/// it is left untouched otherwise.
+/// Chapter 7.2.1
+pub mod wire {
+    use byte::{BytesExt, LE};
+
+    use super::{
+        LongAddress, PanId, ShortAddress, LONG_ADDRESS_SIZE, PAN_ID_SIZE, SHORT_ADDRESS_SIZE,
+    };
+
+    /// # Error
+    ///
+    /// If `bytes` ends before the field, returns `Truncated`.
+    pub fn read_pan(bytes: &[u8], offset: &mut usize) -> Result<PanId, crate::parser::Error> {
+        bytes
+            .read_with(offset, LE)
+            .map(PanId::new)
+            .map_err(|_| crate::parser::Error::Truncated)
+    }
+
+    /// Reads a short address, `pan` being the PAN ID it belongs to.
+    ///
+    /// # Error
+    ///
+    /// If `bytes` ends before the field, returns `Truncated`.
+    pub fn read_short(
+        bytes: &[u8],
+        offset: &mut usize,
+        pan: PanId,
+    ) -> Result<ShortAddress, crate::parser::Error> {
+        bytes
+            .read_with(offset, LE)
+            .map(|address| ShortAddress::new(pan, address))
+            .map_err(|_| crate::parser::Error::Truncated)
+    }
+
+    /// Reads an extended address, `pan` being the PAN ID it belongs to.
+    ///
+    /// # Error
+    ///
+    /// If `bytes` ends before the field, returns `Truncated`.
+    pub fn read_long(
+        bytes: &[u8],
+        offset: &mut usize,
+        pan: PanId,
+    ) -> Result<LongAddress, crate::parser::Error> {
+        bytes
+            .read_with(offset, LE)
+            .map(|address| LongAddress::new(pan, address))
+            .map_err(|_| crate::parser::Error::Truncated)
+    }
+
+    /// # Error
+    ///
+    /// If `bytes` cannot hold the field, returns `BufferTooSmall`.
+    pub fn write_pan(
+        bytes: &mut [u8],
+        offset: &mut usize,
+        pan: PanId,
+    ) -> Result<(), crate::composer::Error> {
+        bytes.write_with(offset, pan.value(), LE).map_err(|_| {
+            crate::composer::Error::BufferTooSmall {
+                needed: *offset + PAN_ID_SIZE,
+            }
+        })
+    }
+
+    /// Writes the value of a short address, without its PAN ID.
+    ///
+    /// # Error
+    ///
+    /// If `bytes` cannot hold the field, returns `BufferTooSmall`.
+    pub fn write_short(
+        bytes: &mut [u8],
+        offset: &mut usize,
+        address: &ShortAddress,
+    ) -> Result<(), crate::composer::Error> {
+        bytes.write_with(offset, address.value(), LE).map_err(|_| {
+            crate::composer::Error::BufferTooSmall {
+                needed: *offset + SHORT_ADDRESS_SIZE,
+            }
+        })
+    }
+
+    /// Writes the value of an extended address, without its PAN ID.
+    ///
+    /// # Error
+    ///
+    /// If `bytes` cannot hold the field, returns `BufferTooSmall`.
+    pub fn write_long(
+        bytes: &mut [u8],
+        offset: &mut usize,
+        address: &LongAddress,
+    ) -> Result<(), crate::composer::Error> {
+        bytes.write_with(offset, address.value(), LE).map_err(|_| {
+            crate::composer::Error::BufferTooSmall {
+                needed: *offset + LONG_ADDRESS_SIZE,
+            }
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+    use core::fmt::Debug;
+    use heapless::FnvIndexMap;
+
     use crate::{
         address::Address,
-        ieee802154::address::{LongAddress, PanId, ShortAddress},
+        ieee802154::address::{
+            wire, AddressField, AddressKind, AddressingMode, LongAddress, PanId, ShortAddress,
+            LONG_ADDRESS_SIZE, PAN_ID_SIZE, SHORT_ADDRESS_SIZE,
+        },
     };
 
+    #[test]
+    fn pan_id_conversions() {
+        let pan = PanId::from(0xABCD);
+
+        assert_eq!(pan, PanId::new(0xABCD));
+        assert_eq!(u16::from(pan), 0xABCD);
+        assert!(!pan.is_broadcast());
+        assert!(PanId::BROADCAST.is_broadcast());
+        assert_eq!(PanId::broadcast(), PanId::BROADCAST);
+    }
+
+    #[test]
+    fn pan_id_format() {
+        assert_eq!(format!("{}", PanId::new(0xABCD)), "0xABCD");
+        assert_eq!(format!("{}", PanId::new(0x0012)), "0x0012");
+        assert_eq!(format!("{:x}", PanId::new(0xABCD)), "abcd");
+        assert_eq!(format!("{:#06x}", PanId::new(0x0012)), "0x0012");
+    }
+
+    #[test]
+    fn wire_round_trip() {
+        let pan = PanId::new(0x1234);
+        let short = ShortAddress::new(pan, 0xABCD);
+        let long = LongAddress::new(pan, 0x0102_0304_0506_0708);
+
+        let mut bytes = [0u8; 12];
+        let mut offset = 0;
+        wire::write_pan(&mut bytes, &mut offset, pan).unwrap();
+        wire::write_short(&mut bytes, &mut offset, &short).unwrap();
+        wire::write_long(&mut bytes, &mut offset, &long).unwrap();
+
+        assert_eq!(offset, 12);
+        assert_eq!(
+            bytes,
+            [0x34, 0x12, 0xCD, 0xAB, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]
+        );
+
+        let mut offset = 0;
+        assert_eq!(wire::read_pan(&bytes, &mut offset), Ok(pan));
+        assert_eq!(wire::read_short(&bytes, &mut offset, pan), Ok(short));
+        assert_eq!(wire::read_long(&bytes, &mut offset, pan), Ok(long));
+        assert_eq!(offset, 12);
+    }
+
+    #[test]
+    fn wire_out_of_bounds() {
+        let pan = PanId::new(0x1234);
+        let bytes = [0u8; 9];
+
+        let mut offset = 2;
+        assert_eq!(
+            wire::read_long(&bytes, &mut offset, pan),
+            Err(crate::parser::Error::Truncated)
+        );
+        assert_eq!(offset, 2);
+        assert_eq!(
+            wire::read_pan(&bytes[..1], &mut 0),
+            Err(crate::parser::Error::Truncated)
+        );
+
+        let mut out = [0u8; 3];
+        let mut offset = 2;
+        assert_eq!(
+            wire::write_short(&mut out, &mut offset, &ShortAddress::new(pan, 0x0001)),
+            Err(crate::composer::Error::BufferTooSmall { needed: 4 })
+        );
+        assert_eq!(offset, 2);
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn display_addresses() {
+        use heapless::String;
+        use ufmt::uwrite;
+
+        let pan = PanId::new(0xABCD);
+        let short = ShortAddress::new(pan, 0x0001);
+        let long = LongAddress::new(pan, 0x0012_4B00_1CA1_B2C3);
+
+        let mut out: String<40> = String::new();
+        uwrite!(&mut out, "{}", pan).unwrap();
+        assert_eq!(out, "0xABCD");
+
+        out.clear();
+        uwrite!(&mut out, "{}", short).unwrap();
+        assert_eq!(out, "0xABCD/0x0001");
+
+        out.clear();
+        uwrite!(&mut out, "{}", long).unwrap();
+        assert_eq!(out, "00:12:4b:00:1c:a1:b2:c3");
+
+        out.clear();
+        uwrite!(
+            &mut out,
+            "{} {}",
+            AddressKind::Short(short),
+            AddressKind::Long(long)
+        )
+        .unwrap();
+        assert_eq!(out, "0xABCD/0x0001 00:12:4b:00:1c:a1:b2:c3");
+
+        // Same output as core::fmt
+        for address in [AddressKind::Short(short), AddressKind::Long(long)] {
+            out.clear();
+            uwrite!(&mut out, "{}", address).unwrap();
+            assert_eq!(out, format!("{}", address).as_str());
+        }
+
+        out.clear();
+        uwrite!(&mut out, "{}", pan).unwrap();
+        assert_eq!(out, format!("{}", pan).as_str());
+    }
+
+    #[test]
+    fn format_addresses() {
+        let pan = PanId::new(0xABCD);
+        let short = ShortAddress::new(pan, 0x0001);
+        let long = LongAddress::new(pan, 0x0012_4B00_1CA1_B2C3);
+
+        assert_eq!(format!("{}", short), "0xABCD/0x0001");
+        assert_eq!(format!("{}", long), "00:12:4b:00:1c:a1:b2:c3");
+        assert_eq!(format!("{}", AddressKind::Short(short)), "0xABCD/0x0001");
+        assert_eq!(
+            format!("{}", AddressKind::Long(long)),
+            "00:12:4b:00:1c:a1:b2:c3"
+        );
+
+        assert_eq!(format!("{:#06x}", short), "0x0001");
+        assert_eq!(format!("{:016x}", long), "00124b001ca1b2c3");
+    }
+
     #[test]
     fn broadcast_for_short_address() {
         assert_eq!(ShortAddress::broadcast().value(), 0xFFFF);
@@ -97,7 +923,307 @@ mod tests {
 
     #[test]
     fn broadcast_for_long_address() {
-        assert_eq!(LongAddress::broadcast().value(), 0xFFFFFFFF);
+        assert_eq!(LongAddress::broadcast().value(), 0xFFFF_FFFF_FFFF_FFFF);
         assert_eq!(LongAddress::broadcast().pan_id(), PanId::broadcast());
     }
+
+    #[test]
+    fn short_address_special_values() {
+        let pan = PanId::new(0x1234);
+
+        let broadcast = ShortAddress::new(pan, 0xFFFF);
+        assert!(broadcast.is_broadcast());
+        assert!(!broadcast.is_unassigned());
+        assert!(!broadcast.is_valid_unicast());
+
+        let unassigned = ShortAddress::new(pan, 0xFFFE);
+        assert!(!unassigned.is_broadcast());
+        assert!(unassigned.is_unassigned());
+        assert!(!unassigned.is_valid_unicast());
+
+        for value in [0x0000, 0x1234, 0xFFFD] {
+            let address = ShortAddress::new(pan, value);
+            assert!(!address.is_broadcast());
+            assert!(!address.is_unassigned());
+            assert!(address.is_valid_unicast());
+        }
+    }
+
+    #[test]
+    fn long_address_eui64() {
+        let pan = PanId::new(0x1234);
+        // Nordic OUI followed by the FICR device ID of an nRF52840
+        let bytes = [0x9A, 0x78, 0x56, 0x34, 0x12, 0x36, 0xCE, 0xF4];
+
+        let address = LongAddress::from_bytes(pan, bytes);
+        assert_eq!(address.value(), 0xF4CE_3612_3456_789A);
+        assert_eq!(address.pan_id(), pan);
+        assert_eq!(address.to_bytes(), bytes);
+        assert_eq!(
+            LongAddress::from_oui(pan, 0xF4CE36, 0x12_3456_789A),
+            address
+        );
+        assert!(!address.is_locally_administered());
+        assert!(!address.is_group());
+
+        assert_eq!(
+            LongAddress::from_oui(pan, 0xFFF4CE36, 0xFF12_3456_789A),
+            address
+        );
+
+        let local = LongAddress::new(pan, 0x0200_0000_0000_0001);
+        assert!(local.is_locally_administered());
+        assert!(!local.is_group());
+
+        let group = LongAddress::new(pan, 0x0100_0000_0000_0001);
+        assert!(!group.is_locally_administered());
+        assert!(group.is_group());
+    }
+
+    #[test]
+    fn is_broadcast() {
+        let pan = PanId::new(0x1234);
+
+        assert!(ShortAddress::broadcast().is_broadcast());
+        assert!(ShortAddress::new(pan, 0xFFFF).is_broadcast());
+        assert!(!ShortAddress::new(pan, 0xFFFE).is_broadcast());
+
+        assert!(LongAddress::broadcast().is_broadcast());
+        assert!(LongAddress::new(pan, 0xFFFF_FFFF_FFFF_FFFF).is_broadcast());
+        assert!(!LongAddress::new(pan, 0xFFFF_FFFF).is_broadcast());
+
+        assert!(AddressKind::Short(ShortAddress::new(pan, 0xFFFF)).is_broadcast());
+        assert!(!AddressKind::Long(LongAddress::new(pan, 0x0102_0304_0506_0708)).is_broadcast());
+    }
+
+    /// Uses the address types only through the `Address` trait, as generic
+    /// code would.
+    fn check_address<A>(unicast: A::Inner)
+    where
+        A: Address + Copy + PartialEq + Debug,
+        A::Inner: Copy + PartialEq + Debug,
+    {
+        let address = A::from_value(unicast);
+        assert_eq!(address.value(), unicast);
+        assert!(!address.is_broadcast());
+
+        let broadcast = A::from_value(A::broadcast().value());
+        assert_eq!(broadcast, A::broadcast());
+        assert!(broadcast.is_broadcast());
+    }
+
+    #[test]
+    fn address_trait() {
+        check_address::<PanId>(0x1234);
+        check_address::<ShortAddress>(0xABCD);
+        check_address::<LongAddress>(0x0102_0304_0506_0708);
+
+        // The PAN ID of an address built from its value alone is the
+        // broadcast one
+        assert_eq!(ShortAddress::from_value(0xABCD).pan_id(), PanId::BROADCAST);
+        assert_eq!(LongAddress::from_value(0x0102).pan_id(), PanId::BROADCAST);
+        assert_eq!(
+            Address::is_broadcast(&ShortAddress::new(PanId::new(0x1234), 0xFFFF)),
+            ShortAddress::new(PanId::new(0x1234), 0xFFFF).is_broadcast()
+        );
+    }
+
+    #[test]
+    fn const_contexts() {
+        const PAN: PanId = PanId::broadcast();
+        const SHORT: ShortAddress = ShortAddress::broadcast();
+        const LONG: LongAddress = LongAddress::broadcast();
+        const VALUES: (u16, u16, u64) = (PAN.value(), SHORT.value(), LONG.value());
+
+        assert_eq!(PAN, <PanId as Address>::broadcast());
+        assert_eq!(SHORT, <ShortAddress as Address>::broadcast());
+        assert_eq!(LONG, <LongAddress as Address>::broadcast());
+        assert_eq!(VALUES, (0xFFFF, 0xFFFF, 0xFFFF_FFFF_FFFF_FFFF));
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn const_trait() {
+        const PAN: PanId = <PanId as Address>::from_value(0x1234);
+        const SHORT: ShortAddress = <ShortAddress as Address>::from_value(0xFFFF);
+        const BROADCAST: [bool; 2] = [Address::is_broadcast(&PAN), Address::is_broadcast(&SHORT)];
+
+        assert_eq!(PAN, PanId::new(0x1234));
+        assert_eq!(BROADCAST, [false, true]);
+    }
+
+    #[test]
+    fn addressing_mode_bits() {
+        for mode in [
+            AddressingMode::None,
+            AddressingMode::Short,
+            AddressingMode::Long,
+        ] {
+            assert_eq!(AddressingMode::from_bits(mode.bits()), Ok(mode));
+        }
+
+        assert_eq!(
+            AddressingMode::from_bits(0x1),
+            Err(crate::parser::Error::InvalidAddressingMode(0x1))
+        );
+        assert_eq!(
+            AddressingMode::from_bits(0x4),
+            Err(crate::parser::Error::InvalidAddressingMode(0x4))
+        );
+    }
+
+    #[test]
+    fn address_kind_accessors() {
+        let short = ShortAddress::new(PanId::new(0x1234), 0xABCD);
+        let long = LongAddress::new(PanId::new(0x5678), 0x0102_0304_0506_0708);
+
+        let address = AddressKind::Short(short);
+        assert_eq!(address.pan_id(), PanId::new(0x1234));
+        assert!(address.is_short());
+        assert!(!address.is_long());
+        assert_eq!(address.as_short(), Some(&short));
+        assert_eq!(address.as_long(), None);
+        assert_eq!(address.addressing_mode(), AddressingMode::Short);
+        assert_eq!(address.len_on_wire(false), 2);
+        assert_eq!(address.len_on_wire(true), 4);
+
+        let address = AddressKind::Long(long);
+        assert_eq!(address.pan_id(), PanId::new(0x5678));
+        assert!(!address.is_short());
+        assert!(address.is_long());
+        assert_eq!(address.as_short(), None);
+        assert_eq!(address.as_long(), Some(&long));
+        assert_eq!(address.addressing_mode(), AddressingMode::Long);
+        assert_eq!(address.len_on_wire(false), 8);
+        assert_eq!(address.len_on_wire(true), 10);
+    }
+
+    #[test]
+    fn address_kind_conversions() {
+        let short = ShortAddress::new(PanId::new(0x1234), 0xABCD);
+        let long = LongAddress::new(PanId::new(0x1234), 0x0102_0304_0506_0708);
+
+        assert_eq!(AddressKind::from(short), AddressKind::Short(short));
+        assert_eq!(AddressKind::from(long), AddressKind::Long(long));
+
+        assert_eq!(ShortAddress::try_from(AddressKind::from(short)), Ok(short));
+        assert_eq!(
+            ShortAddress::try_from(AddressKind::from(long)),
+            Err(AddressKind::Long(long))
+        );
+        assert_eq!(LongAddress::try_from(AddressKind::from(long)), Ok(long));
+        assert_eq!(
+            LongAddress::try_from(AddressKind::from(short)),
+            Err(AddressKind::Short(short))
+        );
+
+        assert_eq!(
+            AddressingMode::from(&AddressKind::from(short)),
+            AddressingMode::Short
+        );
+        assert_eq!(
+            AddressingMode::from(&AddressKind::from(long)),
+            AddressingMode::Long
+        );
+    }
+
+    #[test]
+    fn address_field_accessors() {
+        let pan = PanId::new(0x1234);
+        let short = ShortAddress::new(pan, 0xFFFF);
+        let long = LongAddress::new(pan, 0x0102_0304_0506_0708);
+
+        let absent = AddressField::from(None);
+        assert!(absent.is_absent());
+        assert_eq!(absent.pan_id(), None);
+        assert_eq!(absent.address(), None);
+        assert_eq!(absent.len_on_wire(true), 0);
+
+        let pan_only = AddressField::PanOnly(pan);
+        assert_eq!(pan_only.pan_id(), Some(pan));
+        assert_eq!(pan_only.address(), None);
+        assert_eq!(pan_only.addressing_mode(), AddressingMode::None);
+        assert_eq!(pan_only.len_on_wire(true), PAN_ID_SIZE);
+        assert!(!pan_only.is_broadcast());
+
+        let present = AddressField::from(short);
+        assert_eq!(present, AddressField::from(Some(AddressKind::Short(short))));
+        assert_eq!(present.pan_id(), Some(pan));
+        assert_eq!(present.address(), Some(&AddressKind::Short(short)));
+        assert_eq!(AddressingMode::from(&present), AddressingMode::Short);
+        assert_eq!(present.len_on_wire(false), SHORT_ADDRESS_SIZE);
+        assert!(present.is_broadcast());
+
+        assert_eq!(
+            AddressField::from(long).len_on_wire(true),
+            PAN_ID_SIZE + LONG_ADDRESS_SIZE
+        );
+    }
+
+    #[test]
+    fn address_kind_order() {
+        let mut addresses = [
+            AddressKind::Long(LongAddress::new(PanId::new(0x0001), 0x01)),
+            AddressKind::Short(ShortAddress::new(PanId::new(0x0002), 0x0001)),
+            AddressKind::Long(LongAddress::new(PanId::new(0x0000), 0xFF)),
+            AddressKind::Short(ShortAddress::new(PanId::new(0x0001), 0xFFFF)),
+            AddressKind::Short(ShortAddress::new(PanId::new(0x0001), 0x0002)),
+        ];
+        addresses.sort();
+
+        assert_eq!(
+            addresses,
+            [
+                AddressKind::Short(ShortAddress::new(PanId::new(0x0001), 0x0002)),
+                AddressKind::Short(ShortAddress::new(PanId::new(0x0001), 0xFFFF)),
+                AddressKind::Short(ShortAddress::new(PanId::new(0x0002), 0x0001)),
+                AddressKind::Long(LongAddress::new(PanId::new(0x0000), 0xFF)),
+                AddressKind::Long(LongAddress::new(PanId::new(0x0001), 0x01)),
+            ]
+        );
+    }
+
+    #[test]
+    fn neighbor_table() {
+        let pan = PanId::new(0x1234);
+        let short = AddressKind::Short(ShortAddress::new(pan, 0x0001));
+        let long = AddressKind::Long(LongAddress::new(pan, 0x0001));
+
+        // Frames received per neighbor
+        let mut counters: FnvIndexMap<AddressKind, u32, 4> = FnvIndexMap::new();
+
+        for address in [short, long, short, short] {
+            match counters.get_mut(&address) {
+                Some(counter) => *counter += 1,
+                None => {
+                    counters.insert(address, 1).unwrap();
+                }
+            }
+        }
+
+        assert_eq!(counters.len(), 2);
+        assert_eq!(counters.get(&short), Some(&3));
+        assert_eq!(counters.get(&long), Some(&1));
+    }
+
+    #[test]
+    fn addressing_mode_of_address() {
+        let short = ShortAddress::new(PanId::new(0x1234), 0xABCD);
+        let long = LongAddress::new(PanId::new(0x1234), 0x0102_0304_0506_0708);
+
+        assert_eq!(AddressingMode::of(&None), AddressingMode::None);
+        assert_eq!(
+            AddressingMode::of(&Some(AddressKind::Short(short))),
+            AddressingMode::Short
+        );
+        assert_eq!(
+            AddressingMode::of(&Some(AddressKind::Long(long))),
+            AddressingMode::Long
+        );
+
+        assert_eq!(AddressingMode::None.size(false), 0);
+        assert_eq!(AddressingMode::None.size(true), 2);
+        assert_eq!(AddressingMode::Short.size(true), 4);
+        assert_eq!(AddressingMode::Long.size(false), 8);
+    }
 }
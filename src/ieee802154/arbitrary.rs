@@ -0,0 +1,878 @@
+//! [`Arbitrary`] implementations, generating standard-legal values only.
+//!
+//! Frames are generated the way the parser returns them: derived fields of
+//! the frame control field match the rest of the frame, elided PAN IDs are
+//! the ones the parser assumes, and IE lists end with the termination IEs
+//! matching what follows them. Composing then parsing a generated frame
+//! therefore gives it back unchanged.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{
+    address::{AddressField, AddressKind, AddressingMode, LongAddress, PanId, ShortAddress},
+    composer::pan_id_compression,
+    control_field::{FrameKind, FrameVersion, MultipurposeControlField, StandardControlField},
+    frame::{
+        ack::AckFrame,
+        beacon::{
+            gts::{Gts, GtsDescriptor, MAX_GTS_DESCRIPTOR},
+            pending_address::{PendingAddress, MAX_PENDING_ADDRESS},
+            BeaconFrame, BeaconHeader, BeaconPayload, SuperFrame, NON_BEACON_ORDER,
+        },
+        command::{CommandId, MacCommandFrame},
+        data::DataFrame,
+        enh_ack::EnhAckFrame,
+        enh_beacon::EnhBeaconFrame,
+        multipurpose::{self, MultipurposeFrame},
+        Frame, MacHeader,
+    },
+    ie::{
+        header::{element_id, HeaderIe, HeaderIeIterator},
+        nested::NestedIe,
+        payload::{PayloadIe, PayloadIeGroup, PayloadIeIterator},
+        time_correction::{TimeCorrection, MAX_TIME_CORRECTION, MIN_TIME_CORRECTION},
+        HeaderTermination, IE_DESCRIPTOR_SIZE,
+    },
+    security_header::{AuxiliarySecurityHeader, KeyIdentifierMode, Mic, SecurityLevel},
+};
+
+const VERSIONS: [FrameVersion; 3] = [
+    FrameVersion::Ieee802154_2003,
+    FrameVersion::Ieee802154_2006,
+    FrameVersion::Ieee802154,
+];
+const LEGACY_VERSIONS: [FrameVersion; 2] =
+    [FrameVersion::Ieee802154_2003, FrameVersion::Ieee802154_2006];
+
+/// Header IE lists made of a Header Termination 1 or 2 IE only, since
+/// random input seldom contains one
+const HEADER_TERMINATIONS: [&[u8]; 2] = [&[0x00, 0x3F], &[0x80, 0x3F]];
+/// Payload IE list made of a Payload Termination IE only
+const PAYLOAD_TERMINATION: [u8; 2] = [0x00, 0xF8];
+
+const fn is_legacy(version: &FrameVersion) -> bool {
+    matches!(
+        version,
+        FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+    )
+}
+
+/// Beacons and acknowledgments cannot request an acknowledgment.
+const fn is_unacknowledged(kind: FrameKind) -> bool {
+    matches!(kind, FrameKind::Beacon | FrameKind::Acknowledgment)
+}
+
+/// Length of the longest prefix of `data` made of header IEs whose content
+/// matches their element ID, termination included.
+fn header_ies_len(data: &[u8]) -> usize {
+    let mut ies = HeaderIeIterator::new(data);
+    let mut len = 0;
+
+    while let Some(Ok(ie)) = ies.next() {
+        if ie.element().is_err() {
+            return len;
+        }
+
+        len = ies.offset();
+    }
+
+    match ies.termination() {
+        HeaderTermination::None => len,
+        _ => ies.offset(),
+    }
+}
+
+/// Length of the longest prefix of `data` made of payload IEs of a defined
+/// group whose nested IEs, if any, match their sub-ID, termination included.
+fn payload_ies_len(data: &[u8]) -> usize {
+    let mut ies = PayloadIeIterator::new(data);
+    let mut len = 0;
+
+    while let Some(Ok(ie)) = ies.next() {
+        let nested = ie.nested().is_none_or(|mut nested| {
+            nested.all(|nested: Result<NestedIe, _>| nested.is_ok_and(|ie| ie.element().is_ok()))
+        });
+
+        if !nested || matches!(ie.group, PayloadIeGroup::Reserved(_)) {
+            return len;
+        }
+
+        len = ies.offset();
+    }
+
+    match ies.terminated() {
+        true => ies.offset(),
+        false => len,
+    }
+}
+
+impl<'a> Arbitrary<'a> for HeaderIeIterator<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let data: &[u8] = u.arbitrary()?;
+
+        Ok(HeaderIeIterator::new(&data[..header_ies_len(data)]))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PayloadIeIterator<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let data: &[u8] = u.arbitrary()?;
+
+        Ok(PayloadIeIterator::new(&data[..payload_ies_len(data)]))
+    }
+}
+
+impl<'a> Arbitrary<'a> for HeaderIe<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match HeaderIeIterator::arbitrary(u)?.next() {
+            Some(Ok(ie)) => Ok(ie),
+            _ => Err(arbitrary::Error::IncorrectFormat),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for PayloadIe<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match PayloadIeIterator::arbitrary(u)?.next() {
+            Some(Ok(ie)) => Ok(ie),
+            _ => Err(arbitrary::Error::IncorrectFormat),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for NestedIe<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mlme = PayloadIe {
+            group: PayloadIeGroup::Mlme,
+            content: u.arbitrary()?,
+        };
+
+        match mlme.nested().and_then(|mut nested| nested.next()) {
+            Some(Ok(ie)) if ie.element().is_ok() => Ok(ie),
+            _ => Err(arbitrary::Error::IncorrectFormat),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for TimeCorrection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(TimeCorrection {
+            correction_us: u.int_in_range(MIN_TIME_CORRECTION..=MAX_TIME_CORRECTION)?,
+            nack: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StandardControlField {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let frame_kind = *u.choose(&[
+            FrameKind::Beacon,
+            FrameKind::Data,
+            FrameKind::Acknowledgment,
+            FrameKind::MacCommand,
+        ])?;
+        let version = *u.choose(&VERSIONS)?;
+        let legacy = is_legacy(&version);
+        let dst_addressing_mode: AddressingMode = u.arbitrary()?;
+        let src_addressing_mode: AddressingMode = u.arbitrary()?;
+        let both = dst_addressing_mode != AddressingMode::None
+            && src_addressing_mode != AddressingMode::None;
+
+        Ok(StandardControlField {
+            frame_kind,
+            // The 2003 auxiliary security header has another layout
+            security_enabled: version != FrameVersion::Ieee802154_2003 && u.arbitrary()?,
+            frame_pending: frame_kind != FrameKind::MacCommand && u.arbitrary()?,
+            ack_required: !is_unacknowledged(frame_kind) && u.arbitrary()?,
+            // Chapter 7.2.2.6
+            pan_id_compression: (!legacy || both) && u.arbitrary()?,
+            seq_no_present: legacy || u.arbitrary()?,
+            ie_present: !legacy && u.arbitrary()?,
+            version,
+            dst_addressing_mode: dst_addressing_mode.bits(),
+            src_addressing_mode: src_addressing_mode.bits(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MultipurposeControlField {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut control = MultipurposeControlField {
+            long_frame_control: u.arbitrary()?,
+            pan_id_present: u.arbitrary()?,
+            security_enabled: u.arbitrary()?,
+            seq_no_present: u.arbitrary()?,
+            frame_pending: u.arbitrary()?,
+            ack_required: u.arbitrary()?,
+            ie_present: u.arbitrary()?,
+            version: FrameVersion::Ieee802154,
+            dst_addressing_mode: u.arbitrary()?,
+            src_addressing_mode: u.arbitrary()?,
+        };
+        control.long_frame_control |= control.is_long();
+
+        Ok(control)
+    }
+}
+
+impl<'a> Arbitrary<'a> for SecurityLevel {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SecurityLevel {
+            // Security level 4 is reserved since IEEE 802.15.4-2011
+            mic: u.choose(&[Mic::Mic32, Mic::Mic64, Mic::Mic128])?.clone(),
+            encrypted: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AuxiliarySecurityHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let security_level: Option<SecurityLevel> = u.arbitrary()?;
+
+        // A key is only identified for a secured frame
+        let key_identifier_mode = match security_level {
+            Some(_) => u.arbitrary()?,
+            None => KeyIdentifierMode::Implicit,
+        };
+
+        Ok(AuxiliarySecurityHeader {
+            security_level,
+            key_identifier_mode,
+            frame_counter: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SuperFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let beacon_order = u.int_in_range(0..=NON_BEACON_ORDER)?;
+
+        Ok(SuperFrame {
+            beacon_order,
+            super_frame_order: u.int_in_range(0..=beacon_order)?,
+            final_cap_slot: u.int_in_range(0..=0xF)?,
+            low_energy: u.arbitrary()?,
+            pan_coordinator: u.arbitrary()?,
+            association_permit: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for GtsDescriptor {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(GtsDescriptor {
+            address: u.arbitrary()?,
+            starting_slot: u.int_in_range(0..=0xF)?,
+            length: u.int_in_range(0..=0xF)?,
+            direction: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Gts {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut descriptors = heapless::Vec::new();
+
+        for _ in 0..u.int_in_range(0..=MAX_GTS_DESCRIPTOR)? {
+            let _ = descriptors.push(u.arbitrary()?);
+        }
+
+        Ok(Gts {
+            permit: u.arbitrary()?,
+            descriptors,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PendingAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut pending = PendingAddress::default();
+
+        for _ in 0..u.int_in_range(0..=MAX_PENDING_ADDRESS)? {
+            let _ = pending.short_addresses.push(u.arbitrary()?);
+        }
+
+        for _ in 0..u.int_in_range(0..=MAX_PENDING_ADDRESS)? {
+            let _ = pending.long_addresses.push(u.arbitrary()?);
+        }
+
+        Ok(pending)
+    }
+}
+
+/// Returns `address` moved to `pan`.
+fn with_pan(address: AddressKind, pan: PanId) -> AddressKind {
+    match address {
+        AddressKind::Short(address) => AddressKind::Short(ShortAddress::new(pan, address.value())),
+        AddressKind::Long(address) => AddressKind::Long(LongAddress::new(pan, address.value())),
+    }
+}
+
+/// MIC of a frame secured by `aux`, of the size of its security level.
+fn frame_mic<'a>(
+    u: &mut Unstructured<'a>,
+    aux: Option<&AuxiliarySecurityHeader>,
+) -> Result<&'a [u8]> {
+    let len = aux
+        .and_then(|aux| aux.security_level.as_ref())
+        .map_or(0, |level| level.mic.size());
+
+    // Zeroes once the input runs out, as for the other fields
+    Ok(u.bytes(len).unwrap_or(&[0; 16][..len]))
+}
+
+/// MAC header of a `kind` frame without header IEs, its frame control field
+/// matching the other fields. IEEE 802.15.4-2015 frames without address may
+/// carry a destination PAN ID alone.
+fn mac_header<'a>(
+    u: &mut Unstructured<'a>,
+    kind: FrameKind,
+    version: FrameVersion,
+    dst_addr: Option<AddressKind>,
+    src_addr: Option<AddressKind>,
+) -> Result<MacHeader<'a>> {
+    let legacy = is_legacy(&version);
+
+    // Two extended addresses share the destination PAN ID since
+    // IEEE 802.15.4-2015, the source one being elided
+    let src_addr = match (dst_addr, src_addr) {
+        (Some(AddressKind::Long(dst)), Some(src @ AddressKind::Long(_))) if !legacy => {
+            Some(with_pan(src, dst.pan_id()))
+        }
+        _ => src_addr,
+    };
+
+    let dst_addr = match (dst_addr, src_addr) {
+        (None, None) if !legacy && u.arbitrary()? => AddressField::PanOnly(u.arbitrary()?),
+        (dst_addr, _) => dst_addr.into(),
+    };
+
+    // Sequence number suppression appeared in IEEE 802.15.4-2015
+    let seq_no = match legacy {
+        true => Some(u.arbitrary()?),
+        false => u.arbitrary()?,
+    };
+
+    let aux = match version != FrameVersion::Ieee802154_2003 && u.arbitrary()? {
+        true => {
+            let mut aux: AuxiliarySecurityHeader = u.arbitrary()?;
+
+            // Frame counter suppression appeared in IEEE 802.15.4-2015
+            if legacy && aux.frame_counter.is_none() {
+                aux.frame_counter = Some(u.arbitrary()?);
+            }
+
+            Some(aux)
+        }
+        false => None,
+    };
+
+    let control = StandardControlField {
+        frame_kind: kind,
+        security_enabled: aux.is_some(),
+        frame_pending: kind != FrameKind::MacCommand && u.arbitrary()?,
+        ack_required: !is_unacknowledged(kind) && !dst_addr.is_broadcast() && u.arbitrary()?,
+        pan_id_compression: pan_id_compression(&version, &dst_addr, src_addr.as_ref()),
+        seq_no_present: seq_no.is_some(),
+        ie_present: false,
+        version,
+        dst_addressing_mode: dst_addr.addressing_mode().bits(),
+        src_addressing_mode: AddressingMode::of(&src_addr).bits(),
+    };
+
+    Ok(MacHeader {
+        control: control.into(),
+        seq_no,
+        dst_addr,
+        src_addr,
+        aux,
+        header_ies: HeaderIeIterator::new(&[]),
+    })
+}
+
+/// Sets the header IEs of a header built by [`mac_header`], along with the
+/// IE present bit of its frame control field.
+fn set_header_ies<'a>(
+    header: &mut MacHeader<'a>,
+    header_ies: HeaderIeIterator<'a>,
+    ie_present: bool,
+) {
+    if let Some(control) = header.control.as_standard_mut() {
+        control.ie_present = ie_present;
+    }
+
+    header.header_ies = header_ies;
+}
+
+/// IE lists of a frame, each one ending with the termination matching what
+/// follows it.
+struct IeLists<'a> {
+    header_ies: HeaderIeIterator<'a>,
+    payload_ies: PayloadIeIterator<'a>,
+    /// Whether an unformatted payload may follow the lists
+    payload: bool,
+}
+
+impl<'a> IeLists<'a> {
+    fn empty() -> Self {
+        IeLists {
+            header_ies: HeaderIeIterator::new(&[]),
+            payload_ies: PayloadIeIterator::new(&[]),
+            payload: true,
+        }
+    }
+
+    /// `time_correction` tells whether a Time Correction IE is written
+    /// before the header IEs.
+    fn arbitrary(u: &mut Unstructured<'a>, time_correction: bool) -> Result<Self> {
+        let header_ies: HeaderIeIterator = match u.ratio(1, 4)? {
+            true => HeaderIeIterator::new(u.choose(&HEADER_TERMINATIONS)?),
+            false => u.arbitrary()?,
+        };
+
+        let mut walk = header_ies.clone();
+        walk.by_ref().for_each(drop);
+
+        let (payload_ies, payload) = match walk.termination() {
+            HeaderTermination::None => (
+                PayloadIeIterator::new(&[]),
+                header_ies.as_bytes().is_empty() && !time_correction,
+            ),
+            HeaderTermination::PayloadIes => {
+                let payload_ies: PayloadIeIterator = match u.ratio(1, 4)? {
+                    true => PayloadIeIterator::new(&PAYLOAD_TERMINATION),
+                    false => u.arbitrary()?,
+                };
+
+                let mut walk = payload_ies.clone();
+                walk.by_ref().for_each(drop);
+
+                (payload_ies, walk.terminated())
+            }
+            HeaderTermination::Payload => (PayloadIeIterator::new(&[]), true),
+        };
+
+        Ok(IeLists {
+            header_ies,
+            payload_ies,
+            payload,
+        })
+    }
+
+    /// Unformatted payload following the lists.
+    fn payload(&self, u: &mut Unstructured<'a>) -> Result<&'a [u8]> {
+        match self.payload {
+            true => u.arbitrary(),
+            false => Ok(&[]),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for BeaconFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let version = *u.choose(&LEGACY_VERSIONS)?;
+        let dst_addr = u.arbitrary()?;
+        let src_addr: AddressKind = u.arbitrary()?;
+        let header = mac_header(u, FrameKind::Beacon, version, dst_addr, Some(src_addr))?;
+
+        // GTS and pending addresses belong to the PAN of the coordinator
+        let pan = src_addr.pan_id();
+
+        let super_frame: SuperFrame = u.arbitrary()?;
+        let mut gts: Gts = u.arbitrary()?;
+
+        for desc in gts.descriptors.iter_mut() {
+            desc.address = ShortAddress::new(pan, desc.address.value());
+        }
+
+        if super_frame.validate(&gts).is_err() {
+            gts.permit = false;
+            gts.descriptors.clear();
+        }
+
+        let mut pending_address: PendingAddress = u.arbitrary()?;
+
+        for address in pending_address.short_addresses.iter_mut() {
+            *address = ShortAddress::new(pan, address.value());
+        }
+
+        for address in pending_address.long_addresses.iter_mut() {
+            *address = LongAddress::new(pan, address.value());
+        }
+
+        let mic = frame_mic(u, header.aux.as_ref())?;
+
+        Ok(BeaconFrame {
+            header: BeaconHeader {
+                control: header.control,
+                seq_no: header.seq_no,
+                src_addr: header.src_addr,
+                dst_addr: header.dst_addr,
+                aux: header.aux,
+                header_ies: header.header_ies,
+            },
+            payload: BeaconPayload {
+                super_frame,
+                gts,
+                pending_address,
+                data: u.arbitrary()?,
+            },
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for EnhBeaconFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (dst_addr, src_addr) = (u.arbitrary()?, u.arbitrary()?);
+        let mut header = mac_header(
+            u,
+            FrameKind::Beacon,
+            FrameVersion::Ieee802154,
+            dst_addr,
+            src_addr,
+        )?;
+
+        let ies = IeLists::arbitrary(u, false)?;
+        let ie_present = !ies.header_ies.as_bytes().is_empty();
+        set_header_ies(&mut header, ies.header_ies.clone(), ie_present);
+
+        let mic = frame_mic(u, header.aux.as_ref())?;
+
+        Ok(EnhBeaconFrame {
+            header,
+            payload_ies: ies.payload_ies.clone(),
+            payload: ies.payload(u)?,
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DataFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let version = *u.choose(&VERSIONS)?;
+        let legacy = is_legacy(&version);
+        let (mut dst_addr, src_addr): (Option<AddressKind>, Option<AddressKind>) =
+            (u.arbitrary()?, u.arbitrary()?);
+
+        // At least one address before IEEE 802.15.4-2015
+        if legacy && dst_addr.is_none() && src_addr.is_none() {
+            dst_addr = Some(u.arbitrary()?);
+        }
+
+        // Data frames are not sent to the unassigned short address
+        if let Some(AddressKind::Short(dst)) = dst_addr {
+            if dst.is_unassigned() {
+                dst_addr = Some(AddressKind::Short(ShortAddress::new(dst.pan_id(), 0xFFFD)));
+            }
+        }
+
+        let mut header = mac_header(u, FrameKind::Data, version, dst_addr, src_addr)?;
+
+        let ies = match legacy {
+            true => IeLists::empty(),
+            false => IeLists::arbitrary(u, false)?,
+        };
+        let ie_present = !ies.header_ies.as_bytes().is_empty();
+        set_header_ies(&mut header, ies.header_ies.clone(), ie_present);
+
+        let mic = frame_mic(u, header.aux.as_ref())?;
+
+        Ok(DataFrame {
+            header,
+            payload_ies: ies.payload_ies.clone(),
+            payload: ies.payload(u)?,
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AckFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(AckFrame {
+            version: *u.choose(&LEGACY_VERSIONS)?,
+            frame_pending: u.arbitrary()?,
+            seq_no: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for EnhAckFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (dst_addr, src_addr) = (u.arbitrary()?, u.arbitrary()?);
+        let mut header = mac_header(
+            u,
+            FrameKind::Acknowledgment,
+            FrameVersion::Ieee802154,
+            dst_addr,
+            src_addr,
+        )?;
+
+        let mut time_correction: Option<TimeCorrection> = u.arbitrary()?;
+        let mut ies = IeLists::arbitrary(u, time_correction.is_some())?;
+
+        // A Time Correction IE leading the header IEs is the one of the frame
+        if let (None, Some(Ok(ie))) = (time_correction, ies.header_ies.clone().next()) {
+            if ie.element_id == element_id::TIME_CORRECTION {
+                time_correction = TimeCorrection::try_from(ie).ok();
+                ies.header_ies = HeaderIeIterator::new(
+                    &ies.header_ies.as_bytes()[IE_DESCRIPTOR_SIZE + ie.length()..],
+                );
+            }
+        }
+
+        let ie_present = time_correction.is_some() || !ies.header_ies.as_bytes().is_empty();
+        set_header_ies(&mut header, ies.header_ies.clone(), ie_present);
+
+        let mic = frame_mic(u, header.aux.as_ref())?;
+
+        Ok(EnhAckFrame {
+            header,
+            time_correction,
+            payload_ies: ies.payload_ies.clone(),
+            payload: ies.payload(u)?,
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MacCommandFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let version = *u.choose(&VERSIONS)?;
+        let legacy = is_legacy(&version);
+        let ids: heapless::Vec<CommandId, 256> = (0..=u8::MAX)
+            .filter_map(|id| CommandId::from_byte(id).ok())
+            .collect();
+        let command_id = *u.choose(&ids)?;
+        let (mut dst_addr, mut src_addr): (Option<AddressKind>, Option<AddressKind>) =
+            (u.arbitrary()?, u.arbitrary()?);
+
+        match command_id {
+            // Chapter 7.5.2, sent by a device to the coordinator it wishes to
+            // join, and chapter 7.5.4, sent by either end of the association,
+            // with its extended address
+            CommandId::AssociationRequest | CommandId::DisassociationNotification => {
+                src_addr = Some(AddressKind::Long(u.arbitrary()?));
+
+                if dst_addr.is_none_or(|dst| dst.is_broadcast()) {
+                    dst_addr = Some(AddressKind::Short(ShortAddress::new(
+                        u.arbitrary()?,
+                        0x0000,
+                    )));
+                }
+            }
+            // Chapter 7.5.3, sent by the coordinator to the requesting device,
+            // and chapter 7.5.6, sent by a device to its PAN coordinator
+            CommandId::AssociationResponse | CommandId::PanIdConflictNotification => {
+                dst_addr = Some(AddressKind::Long(u.arbitrary()?));
+                src_addr = Some(AddressKind::Long(u.arbitrary()?));
+            }
+            // Chapter 7.5.5, sent by the device polling its coordinator
+            CommandId::DataRequest if src_addr.is_none() => src_addr = Some(u.arbitrary()?),
+            // Chapter 7.5.7, sent by a device having lost its coordinator,
+            // without destination address or to the broadcast address
+            CommandId::OrphanNotification => {
+                if dst_addr.is_some() {
+                    dst_addr = Some(AddressKind::Short(ShortAddress::broadcast()));
+                }
+                src_addr = Some(AddressKind::Long(u.arbitrary()?));
+            }
+            // Chapter 7.5.10, broadcast after a PAN ID change or sent to an
+            // orphaned device, in the broadcast PAN
+            CommandId::CoordinatorRealignment => {
+                dst_addr = Some(match u.arbitrary()? {
+                    true => AddressKind::Long(LongAddress::new(PanId::broadcast(), u.arbitrary()?)),
+                    false => AddressKind::Short(ShortAddress::broadcast()),
+                });
+                src_addr = Some(AddressKind::Long(u.arbitrary()?));
+            }
+            // Chapter 7.5.8, broadcast to every coordinator in range
+            CommandId::BeaconRequest => {
+                dst_addr = Some(AddressKind::Short(ShortAddress::broadcast()));
+                src_addr = None;
+            }
+            _ => (),
+        }
+
+        let mut header = mac_header(u, FrameKind::MacCommand, version, dst_addr, src_addr)?;
+
+        // The command identifier always follows the IE lists
+        let ies = match legacy {
+            true => IeLists::empty(),
+            false => match IeLists::arbitrary(u, false)? {
+                ies if ies.payload => ies,
+                _ => IeLists::empty(),
+            },
+        };
+        let ie_present = !ies.header_ies.as_bytes().is_empty();
+        set_header_ies(&mut header, ies.header_ies, ie_present);
+
+        let mic = frame_mic(u, header.aux.as_ref())?;
+
+        Ok(MacCommandFrame {
+            header,
+            payload_ies: ies.payload_ies,
+            command_id,
+            content: match command_id.content_size() {
+                Some(size) => u.bytes(size)?,
+                None => u.arbitrary()?,
+            },
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MultipurposeFrame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut control: MultipurposeControlField = u.arbitrary()?;
+        let seq_no = match control.seq_no_present {
+            true => Some(u.arbitrary()?),
+            false => None,
+        };
+        let (mut dst_addr, mut src_addr): (Option<AddressKind>, Option<AddressKind>) =
+            (u.arbitrary()?, u.arbitrary()?);
+
+        control.ack_required &= !dst_addr.is_some_and(|dst| dst.is_broadcast());
+        control.dst_addressing_mode = AddressingMode::of(&dst_addr);
+        control.src_addressing_mode = AddressingMode::of(&src_addr);
+
+        let (dst_pan, src_pan) = multipurpose::pan_id_presence(
+            control.pan_id_present,
+            dst_addr.is_some(),
+            src_addr.is_some(),
+        );
+
+        // Elided PAN IDs are read as the broadcast one
+        if !dst_pan {
+            dst_addr = dst_addr.map(|address| with_pan(address, PanId::BROADCAST));
+        }
+
+        // A PAN ID without any address is the destination one
+        let dst_addr = match (dst_addr, dst_pan) {
+            (None, true) => AddressField::PanOnly(u.arbitrary()?),
+            (dst_addr, _) => dst_addr.into(),
+        };
+
+        if !src_pan {
+            src_addr = src_addr.map(|address| with_pan(address, PanId::BROADCAST));
+        }
+
+        let aux = match control.security_enabled {
+            true => Some(u.arbitrary()?),
+            false => None,
+        };
+
+        let ies = IeLists::arbitrary(u, false)?;
+        control.ie_present = !ies.header_ies.as_bytes().is_empty();
+        control.long_frame_control |= control.is_long();
+        let mic = frame_mic(u, aux.as_ref())?;
+
+        Ok(MultipurposeFrame {
+            control,
+            seq_no,
+            dst_addr,
+            src_addr,
+            aux,
+            header_ies: ies.header_ies.clone(),
+            payload_ies: ies.payload_ies.clone(),
+            payload: ies.payload(u)?,
+            mic,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Frame<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=6)? {
+            0 => u.arbitrary().map(Frame::Beacon),
+            1 => u.arbitrary().map(Frame::EnhBeacon),
+            2 => u.arbitrary().map(Frame::Data),
+            3 => u.arbitrary().map(Frame::Acknowledgment),
+            4 => u.arbitrary().map(Frame::EnhAcknowledgment),
+            5 => u.arbitrary().map(Frame::MacCommand),
+            _ => u.arbitrary().map(Frame::MultiPurpose),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            composer::ComposeContext, frame::Frame, parser::ParseContext, Ieee802154, PsduLimit,
+        },
+    };
+
+    /// Fills `buf` with the xorshift sequence starting at `seed`.
+    fn fill(seed: u64, buf: &mut [u8]) {
+        let mut state = seed;
+
+        for byte in buf.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *byte = state as u8;
+        }
+    }
+
+    /// Arbitrary frames carry their private payload in the clear.
+    #[test]
+    fn parse_compose_round_trip() {
+        let context = ParseContext {
+            limit: PsduLimit::Sun,
+            decrypted: true,
+            ..Default::default()
+        };
+        let compose_context = ComposeContext {
+            limit: PsduLimit::Sun,
+        };
+        let mut buf = [0u8; 512];
+
+        for seed in 1..=20_000 {
+            fill(seed, &mut buf);
+
+            let frame = Frame::arbitrary(&mut Unstructured::new(&buf)).unwrap();
+            let composed = Ieee802154::compose_with_context(&frame, compose_context)
+                .unwrap_or_else(|err| panic!("{err:?} composing {frame:?}"));
+            let parsed = Ieee802154::parse_with_context(&composed, context)
+                .unwrap_or_else(|err| panic!("{err:?} parsing {composed:02X?}"));
+
+            assert_eq!(parsed, frame);
+            assert_eq!(
+                Ieee802154::compose_with_context(&parsed, compose_context).unwrap(),
+                composed
+            );
+        }
+    }
+
+    /// Reserved bits are dropped and the PAN ID compression bit is derived by
+    /// the composer, so only the composed bytes are expected to survive a
+    /// second round trip unchanged.
+    #[test]
+    fn compose_parse_fixed_point() {
+        let context = ParseContext {
+            limit: PsduLimit::Sun,
+            ..Default::default()
+        };
+        let mut buf = [0u8; 64];
+
+        for seed in 1..=200_000 {
+            fill(seed, &mut buf);
+
+            let data = &buf[..3 + seed as usize % 61];
+            let Ok(frame) = Ieee802154::parse_with_context(data, context) else {
+                continue;
+            };
+            let Ok(composed) = Ieee802154::compose(&frame) else {
+                continue;
+            };
+            let parsed = Ieee802154::parse_with_context(&composed, context)
+                .unwrap_or_else(|err| panic!("{err:?} parsing {composed:02X?}"));
+
+            assert_eq!(Ieee802154::compose(&parsed).unwrap(), composed);
+        }
+    }
+}
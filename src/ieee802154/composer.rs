@@ -1,11 +1,1876 @@
-use crate::composer::Composer;
+use alloc::{vec, vec::Vec};
 
-use super::{frame::Frame, Ieee802154};
+use crate::composer::{ComposeInto, Composer};
+
+use super::{
+    address::{wire, AddressField, AddressKind, AddressingMode},
+    control_field::{
+        FrameKind, FrameVersion, MultipurposeControlField, StandardControlField,
+        STANDARD_CONTROL_FIELD_SIZE,
+    },
+    fcs::FcsPolicy,
+    frame::{
+        ack::IMM_ACK_SIZE,
+        beacon::BeaconFrame,
+        beacon::SUPER_FRAME_SIZE,
+        command::{CommandId, MacCommandFrame},
+        data::DataFrame,
+        enh_ack::EnhAckFrame,
+        enh_beacon::EnhBeaconFrame,
+        multipurpose::{self, MultipurposeFrame},
+        Frame, MacHeader,
+    },
+    ie::{
+        header::{element_id, HeaderIe, HeaderIeIterator},
+        payload::{PayloadIe, PayloadIeGroup, PayloadIeIterator},
+        time_correction::{TimeCorrection, TIME_CORRECTION_SIZE},
+        HeaderTermination, Terminations, IE_DESCRIPTOR_SIZE,
+    },
+    parser::pan_id_presence,
+    security_header::AuxiliarySecurityHeader,
+    Ieee802154, PsduLimit,
+};
+
+const SEQ_NO_SIZE: usize = 1;
+
+/// Options controlling how frames are composed.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ComposeContext {
+    /// Longer frames are refused
+    pub limit: PsduLimit,
+}
 
 impl<'a> Composer<'a> for Ieee802154 {
     type Value = Frame<'a>;
 
-    fn compose(_value: &Self::Value) -> Result<alloc::vec::Vec<u8>, crate::composer::Error> {
-        todo!()
+    fn compose(value: &Self::Value) -> Result<Vec<u8>, crate::composer::Error> {
+        // Sized upfront, composing allocates exactly once
+        let mut frame = vec![0u8; Self::composed_len(value)?];
+        Self::compose_into(value, &mut frame)?;
+
+        Ok(frame)
+    }
+}
+
+impl<'a> ComposeInto<'a> for Ieee802154 {
+    fn compose_into(value: &Self::Value, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        Self::compose_into_with_context(value, buf, ComposeContext::default())
+    }
+}
+
+impl Ieee802154 {
+    /// Same as [`ComposeInto::compose_into`], with every composing option
+    /// set by `context`.
+    pub fn compose_into_with_context(
+        frame: &Frame,
+        buf: &mut [u8],
+        context: ComposeContext,
+    ) -> Result<usize, crate::composer::Error> {
+        let limit = context.limit;
+
+        match frame {
+            Frame::Beacon(beacon) => compose_beacon(beacon, buf, limit),
+            Frame::EnhBeacon(beacon) => compose_enh_beacon(beacon, buf, limit),
+            Frame::Data(data) => compose_data(data, buf, limit),
+            Frame::EnhAcknowledgment(ack) => compose_enh_ack(ack, buf, limit),
+            Frame::MacCommand(command) => compose_command(command, buf, limit),
+            Frame::MultiPurpose(frame) => compose_multipurpose(frame, buf, limit),
+            Frame::Acknowledgment(ack) => {
+                let frame = buf
+                    .get_mut(..IMM_ACK_SIZE)
+                    .and_then(|frame| frame.try_into().ok())
+                    .ok_or(crate::composer::Error::BufferTooSmall {
+                        needed: IMM_ACK_SIZE,
+                    })?;
+                ack.compose_into(frame)?;
+
+                Ok(IMM_ACK_SIZE)
+            }
+            _ => Err(crate::composer::Error::InvalidHeader),
+        }
+    }
+
+    /// Same as [`Composer::compose`], with every composing option set by
+    /// `context`.
+    pub fn compose_with_context(
+        frame: &Frame,
+        context: ComposeContext,
+    ) -> Result<Vec<u8>, crate::composer::Error> {
+        let mut buf = vec![0u8; Self::composed_len_with_context(frame, context)?];
+        Self::compose_into_with_context(frame, &mut buf, context)?;
+
+        Ok(buf)
+    }
+
+    /// Size in bytes of `frame` once composed.
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Composer::compose`] would.
+    pub fn composed_len(frame: &Frame) -> Result<usize, crate::composer::Error> {
+        Self::composed_len_with_context(frame, ComposeContext::default())
+    }
+
+    /// Same as [`Ieee802154::composed_len`], with every composing option set
+    /// by `context`.
+    pub fn composed_len_with_context(
+        frame: &Frame,
+        context: ComposeContext,
+    ) -> Result<usize, crate::composer::Error> {
+        // Composing into an empty buffer reports the size of the frame,
+        // every check being done before writing.
+        match Self::compose_into_with_context(frame, &mut [], context) {
+            Err(crate::composer::Error::BufferTooSmall { needed }) => Ok(needed),
+            result => result,
+        }
+    }
+
+    /// Same as [`Ieee802154::composed_len`], the FCS of `fcs` included.
+    pub fn composed_len_with_fcs(
+        frame: &Frame,
+        fcs: FcsPolicy,
+    ) -> Result<usize, crate::composer::Error> {
+        Ok(Self::composed_len(frame)? + fcs.size())
+    }
+
+    /// Same as [`ComposeInto::compose_into`], the FCS of `fcs` being
+    /// appended to the frame.
+    pub fn compose_into_with_fcs(
+        frame: &Frame,
+        buf: &mut [u8],
+        fcs: FcsPolicy,
+    ) -> Result<usize, crate::composer::Error> {
+        let needed = Self::composed_len_with_fcs(frame, fcs)?;
+
+        if buf.len() < needed {
+            return Err(crate::composer::Error::BufferTooSmall { needed });
+        }
+
+        let len = Self::compose_into(frame, buf)?;
+
+        fcs.append(buf, len)
+    }
+
+    /// Same as [`Composer::compose`], the FCS of `fcs` being appended to the
+    /// frame.
+    pub fn compose_with_fcs(
+        frame: &Frame,
+        fcs: FcsPolicy,
+    ) -> Result<Vec<u8>, crate::composer::Error> {
+        let mut buf = vec![0u8; Self::composed_len_with_fcs(frame, fcs)?];
+        Self::compose_into_with_fcs(frame, &mut buf, fcs)?;
+
+        Ok(buf)
+    }
+}
+
+impl<'a> MacHeader<'a> {
+    /// Size in bytes of the MAC header, header IEs included.
+    ///
+    /// # Errors
+    ///
+    /// If the frame control field is not a standard one, the optional
+    /// fields do not match it or the addressing is invalid, returns
+    /// `InvalidHeader`.
+    pub fn serialized_len(&self) -> Result<usize, crate::composer::Error> {
+        Header::new(self)?.len()
+    }
+}
+
+/// Cursor over the output buffer, the composing counterpart of the parser
+/// reader.
+struct Writer<'b> {
+    buf: &'b mut [u8],
+    offset: usize,
+}
+
+impl<'b> Writer<'b> {
+    /// Takes the first `len` bytes of `buf`, `len` being the size of the
+    /// frame to write.
+    ///
+    /// # Error
+    ///
+    /// If `len` is over `limit`, returns `FrameTooLong`. If `buf` is
+    /// shorter than `len`, returns `BufferTooSmall`.
+    fn new(
+        buf: &'b mut [u8],
+        len: usize,
+        limit: PsduLimit,
+    ) -> Result<Self, crate::composer::Error> {
+        limit.check_compose(len)?;
+
+        match buf.get_mut(..len) {
+            Some(buf) => Ok(Writer { buf, offset: 0 }),
+            None => Err(crate::composer::Error::BufferTooSmall { needed: len }),
+        }
+    }
+
+    /// Copies `bytes` at the current offset.
+    fn write(&mut self, bytes: &[u8]) -> Result<(), crate::composer::Error> {
+        self.write_with(|buf| {
+            buf.get_mut(..bytes.len())
+                .ok_or(crate::composer::Error::InvalidHeader)?
+                .copy_from_slice(bytes);
+
+            Ok(bytes.len())
+        })
+    }
+
+    /// Runs `f` over the rest of the buffer, and advances by the number of
+    /// bytes it wrote.
+    fn write_with(
+        &mut self,
+        f: impl FnOnce(&mut [u8]) -> Result<usize, crate::composer::Error>,
+    ) -> Result<(), crate::composer::Error> {
+        self.offset += f(&mut self.buf[self.offset..])?;
+
+        Ok(())
+    }
+
+    /// Writes `mic`, or `len` zeroes if it is empty, leaving room for the
+    /// MIC computed once the frame is secured.
+    fn write_mic(&mut self, mic: &[u8], len: usize) -> Result<(), crate::composer::Error> {
+        match mic.is_empty() {
+            true => self.write_with(|buf| {
+                buf.get_mut(..len)
+                    .ok_or(crate::composer::Error::InvalidHeader)?
+                    .fill(0);
+
+                Ok(len)
+            }),
+            false => self.write(mic),
+        }
+    }
+}
+
+/// Size in bytes of the MIC following the payload of a frame secured by
+/// `aux`.
+/// Chapter 9.3.1
+///
+/// # Error
+///
+/// If `mic` is neither empty nor of the size of the MIC, returns
+/// `InvalidHeader`.
+fn mic_len(
+    aux: Option<&AuxiliarySecurityHeader>,
+    mic: &[u8],
+) -> Result<usize, crate::composer::Error> {
+    let len = aux
+        .and_then(|aux| aux.security_level.as_ref())
+        .map_or(0, |level| level.mic.size());
+
+    match mic.len() {
+        0 => Ok(len),
+        mic_len if mic_len == len => Ok(len),
+        _ => Err(crate::composer::Error::InvalidHeader),
+    }
+}
+
+/// Borrowed view of the MAC header fields of the frames using the standard
+/// frame control field.
+/// Chapter 7.2.1
+struct Header<'h> {
+    control: &'h StandardControlField,
+    seq_no: Option<u8>,
+    dst_addr: &'h AddressField,
+    src_addr: Option<&'h AddressKind>,
+    aux: Option<&'h AuxiliarySecurityHeader>,
+    /// Written before the header IEs
+    time_correction: Option<TimeCorrection>,
+    header_ies: &'h [u8],
+    /// Written after the header IEs
+    termination: HeaderTermination,
+}
+
+/// Returns whether a raw header IE list already ends with a Header
+/// Termination IE.
+fn header_terminated(header_ies: &[u8]) -> bool {
+    let mut ies = HeaderIeIterator::new(header_ies);
+    ies.by_ref().for_each(drop);
+
+    ies.termination() != HeaderTermination::None
+}
+
+/// Returns whether a raw payload IE list already ends with a Payload
+/// Termination IE.
+fn payload_terminated(payload_ies: &[u8]) -> bool {
+    let mut ies = PayloadIeIterator::new(payload_ies);
+    ies.by_ref().for_each(drop);
+
+    ies.terminated()
+}
+
+/// Returns whether a Payload Termination IE is written after `payload_ies`,
+/// when they are followed by a payload and do not end with one already.
+/// Chapter 7.4.1
+fn payload_termination(payload_ies: &[u8], payload: bool) -> bool {
+    Terminations::new(true, !payload_ies.is_empty(), payload).payload
+        && !payload_terminated(payload_ies)
+}
+
+/// Size in bytes of `payload_ies` once composed before a payload or not,
+/// the Payload Termination IE they lack included.
+pub(crate) fn payload_ies_len(payload_ies: &[u8], payload: bool) -> usize {
+    payload_ies.len() + payload_termination(payload_ies, payload) as usize * IE_DESCRIPTOR_SIZE
+}
+
+/// Size in bytes of a termination IE, `None` being written as nothing.
+fn termination_len(termination: HeaderTermination) -> usize {
+    match termination {
+        HeaderTermination::None => 0,
+        _ => IE_DESCRIPTOR_SIZE,
+    }
+}
+
+/// Writes the Header Termination IE announcing what follows the header IEs.
+/// Chapter 7.4.2.18
+fn write_header_termination(
+    writer: &mut Writer,
+    termination: HeaderTermination,
+) -> Result<(), crate::composer::Error> {
+    let element_id = match termination {
+        HeaderTermination::None => return Ok(()),
+        HeaderTermination::PayloadIes => element_id::HEADER_TERMINATION_1,
+        HeaderTermination::Payload => element_id::HEADER_TERMINATION_2,
+    };
+
+    writer.write_with(|buf| {
+        HeaderIe {
+            element_id,
+            content: &[],
+        }
+        .write_into(buf)
+    })
+}
+
+/// Writes the Payload Termination IE separating the payload IEs from the
+/// unformatted payload.
+/// Chapter 7.4.3.3
+fn write_payload_termination(writer: &mut Writer) -> Result<(), crate::composer::Error> {
+    writer.write_with(|buf| {
+        PayloadIe {
+            group: PayloadIeGroup::Termination,
+            content: &[],
+        }
+        .write_into(buf)
+    })
+}
+
+/// PAN ID compression of a frame with the given addresses. The source PAN
+/// ID is elided when both addresses share a PAN ID, except for two extended
+/// addresses in IEEE 802.15.4-2015 frames where this would elide both PAN
+/// IDs. A destination PAN ID without any address sets it, as in Table 7-2.
+/// Chapter 7.2.2.6
+pub(crate) fn pan_id_compression(
+    version: &FrameVersion,
+    dst: &AddressField,
+    src: Option<&AddressKind>,
+) -> bool {
+    let legacy = matches!(
+        version,
+        FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+    );
+
+    match (dst, src) {
+        (AddressField::PanOnly(_), None) => true,
+        (AddressField::Present(AddressKind::Long(_)), Some(AddressKind::Long(_))) if !legacy => {
+            false
+        }
+        (AddressField::Present(dst), Some(src)) => dst.pan_id() == src.pan_id(),
+        _ => false,
+    }
+}
+
+/// Returns whether `dst` is the broadcast short address in the broadcast PAN.
+fn is_broadcast_everywhere(dst: &AddressField) -> bool {
+    matches!(
+        dst,
+        AddressField::Present(AddressKind::Short(dst))
+            if dst.is_broadcast() && dst.pan_id().is_broadcast()
+    )
+}
+
+/// Writes the address of `field`, preceded by its PAN ID if `pan_present`
+/// is set.
+fn write_address(
+    writer: &mut Writer,
+    field: &AddressField,
+    pan_present: bool,
+) -> Result<(), crate::composer::Error> {
+    let Some(pan) = field.pan_id() else {
+        return Ok(());
+    };
+
+    writer.write_with(|buf| {
+        let mut offset = 0;
+
+        if pan_present {
+            wire::write_pan(buf, &mut offset, pan)?;
+        }
+
+        match field.address() {
+            Some(AddressKind::Short(address)) => wire::write_short(buf, &mut offset, address)?,
+            Some(AddressKind::Long(address)) => wire::write_long(buf, &mut offset, address)?,
+            None => (),
+        }
+
+        Ok(offset)
+    })
+}
+
+impl<'h> Header<'h> {
+    /// # Error
+    ///
+    /// If the frame control field is not a standard one, returns
+    /// `InvalidHeader`.
+    fn new(header: &'h MacHeader) -> Result<Self, crate::composer::Error> {
+        let control = header
+            .control
+            .as_standard()
+            .ok_or(crate::composer::Error::InvalidHeader)?;
+
+        Ok(Header {
+            control,
+            seq_no: header.seq_no,
+            dst_addr: &header.dst_addr,
+            src_addr: header.src_addr.as_ref(),
+            aux: header.aux.as_ref(),
+            time_correction: None,
+            header_ies: header.header_ies.as_bytes(),
+            termination: HeaderTermination::None,
+        })
+    }
+
+    /// PAN ID compression derived from the addresses, the one of the frame
+    /// control field being ignored.
+    fn pan_id_compression(&self) -> bool {
+        pan_id_compression(&self.control.version, self.dst_addr, self.src_addr)
+    }
+
+    /// Returns whether the destination and source PAN ID fields are present.
+    ///
+    /// # Error
+    ///
+    /// Returns `InvalidAddressing` if no row of the PAN ID compression table
+    /// matches the addresses, or if a destination PAN ID without address
+    /// would not be written.
+    fn pan_id_presence(&self) -> Result<(bool, bool), crate::composer::Error> {
+        let (dst_pan, src_pan) = pan_id_presence(
+            &self.control.version,
+            self.dst_addr.addressing_mode(),
+            self.src_addr
+                .map_or(AddressingMode::None, AddressingMode::from),
+            self.pan_id_compression(),
+        )
+        .map_err(|_| crate::composer::Error::InvalidAddressing)?;
+
+        match (self.dst_addr, dst_pan) {
+            (AddressField::PanOnly(_), false) => Err(crate::composer::Error::InvalidAddressing),
+            _ => Ok((dst_pan, src_pan)),
+        }
+    }
+
+    /// Checks the frame control field, and that the optional fields match
+    /// it.
+    fn check(&self) -> Result<(), crate::composer::Error> {
+        self.written_control().validate()?;
+
+        if self.control.security_enabled && self.aux.is_none() {
+            return Err(crate::composer::Error::MissingSecurityHeader);
+        }
+
+        // The security suites of IEEE 802.15.4-2003 all protect the frame,
+        // none matches security level 0
+        let unsupported_level = self.control.version == FrameVersion::Ieee802154_2003
+            && self.aux.is_some_and(|aux| aux.security_level.is_none());
+
+        // Chapter 9.4.2.3, the frame counter is only suppressed from
+        // IEEE 802.15.4-2015 on
+        let suppressed_counter = matches!(
+            self.control.version,
+            FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+        ) && self.aux.is_some_and(|aux| aux.frame_counter_suppressed());
+
+        // Chapter 7.2.2.4, broadcast frames are not acknowledged
+        let broadcast_ack = self.control.ack_required && self.dst_addr.is_broadcast();
+
+        let consistent = self.control.seq_no_present == self.seq_no.is_some()
+            && self.control.security_enabled == self.aux.is_some()
+            && !unsupported_level
+            && !suppressed_counter
+            && !broadcast_ack;
+
+        match consistent {
+            true => Ok(()),
+            false => Err(crate::composer::Error::InvalidHeader),
+        }
+    }
+
+    /// Size in bytes of the MAC header.
+    fn len(&self) -> Result<usize, crate::composer::Error> {
+        self.check()?;
+
+        let (dst_pan, src_pan) = self.pan_id_presence()?;
+
+        Ok(STANDARD_CONTROL_FIELD_SIZE
+            + self.seq_no.map_or(0, |_| SEQ_NO_SIZE)
+            + self.dst_addr.len_on_wire(dst_pan)
+            + self
+                .src_addr
+                .map_or(0, |address| address.len_on_wire(src_pan))
+            + self.aux.map_or(0, |aux| aux.serialized_len())
+            + self.ies_len())
+    }
+
+    /// Size in bytes of the header IEs, termination included.
+    fn ies_len(&self) -> usize {
+        self.time_correction
+            .map_or(0, |_| IE_DESCRIPTOR_SIZE + TIME_CORRECTION_SIZE)
+            + self.header_ies.len()
+            + termination_len(self.termination)
+    }
+
+    /// Inserts the Header Termination IE matching what follows the header
+    /// IEs, unless the list already ends with one.
+    fn terminate(&mut self, payload_ies: bool, payload: bool) {
+        if header_terminated(self.header_ies) {
+            return;
+        }
+
+        self.termination = Terminations::new(
+            self.time_correction.is_some() || !self.header_ies.is_empty(),
+            payload_ies,
+            payload,
+        )
+        .header;
+    }
+
+    /// Frame control field as written, the PAN ID compression and
+    /// addressing modes being derived from the addresses, and the IE
+    /// presence from the header IEs and their termination.
+    /// Chapter 7.2.2
+    fn written_control(&self) -> StandardControlField {
+        StandardControlField {
+            pan_id_compression: self.pan_id_compression(),
+            ie_present: self.ies_len() != 0,
+            dst_addressing_mode: self.dst_addr.addressing_mode().bits(),
+            src_addressing_mode: self
+                .src_addr
+                .map_or(AddressingMode::None, AddressingMode::from)
+                .bits(),
+            ..self.control.clone()
+        }
+    }
+
+    fn write(&self, writer: &mut Writer) -> Result<(), crate::composer::Error> {
+        writer.write(&self.written_control().bits()?)?;
+
+        if let Some(seq_no) = self.seq_no {
+            writer.write(&[seq_no])?;
+        }
+
+        let (dst_pan, src_pan) = self.pan_id_presence()?;
+
+        write_address(writer, self.dst_addr, dst_pan)?;
+        write_address(writer, &self.src_addr.copied().into(), src_pan)?;
+
+        if let Some(aux) = self.aux {
+            writer.write_with(|buf| aux.write_into(buf))?;
+        }
+
+        if let Some(correction) = &self.time_correction {
+            writer.write_with(|buf| correction.write_into(buf))?;
+        }
+
+        writer.write(self.header_ies)?;
+        write_header_termination(writer, self.termination)
+    }
+}
+
+/// Composes a beacon frame, the source address being mandatory.
+/// Chapter 7.3.1
+fn compose_beacon(
+    beacon: &BeaconFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let control = beacon
+        .header
+        .control
+        .as_standard()
+        .ok_or(crate::composer::Error::InvalidHeader)?;
+
+    if control.frame_kind != FrameKind::Beacon {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    if beacon.header.src_addr.is_none() {
+        return Err(crate::composer::Error::MissingAddress);
+    }
+
+    let header = Header {
+        control,
+        seq_no: beacon.header.seq_no,
+        dst_addr: &beacon.header.dst_addr,
+        src_addr: beacon.header.src_addr.as_ref(),
+        aux: beacon.header.aux.as_ref(),
+        time_correction: None,
+        header_ies: beacon.header.header_ies.as_bytes(),
+        termination: HeaderTermination::None,
+    };
+    let payload = &beacon.payload;
+    let mic_len = mic_len(header.aux, beacon.mic)?;
+
+    let len = header.len()?
+        + SUPER_FRAME_SIZE
+        + payload.gts.serialized_len()
+        + payload.pending_address.serialized_len()
+        + payload.data.len()
+        + mic_len;
+
+    let mut writer = Writer::new(buf, len, limit)?;
+
+    header.write(&mut writer)?;
+    writer.write(&payload.super_frame.bytes())?;
+    writer.write_with(|buf| payload.gts.write_into(buf))?;
+    writer.write_with(|buf| payload.pending_address.write_into(buf))?;
+    writer.write(payload.data)?;
+    writer.write_mic(beacon.mic, mic_len)?;
+
+    Ok(len)
+}
+
+/// Composes a frame made of a MAC header, payload IEs, a payload split in
+/// several parts and a MIC. The IE lists are written back verbatim, the
+/// termination IEs they lack being inserted according to what follows them.
+/// Chapter 7.4.1
+fn compose_with_ies(
+    mut header: Header,
+    payload_ies: &[u8],
+    payload: &[&[u8]],
+    mic: &[u8],
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let payload_len: usize = payload.iter().map(|part| part.len()).sum();
+    let mic_len = mic_len(header.aux, mic)?;
+
+    header.terminate(!payload_ies.is_empty(), payload_len > 0);
+
+    let payload_termination = payload_termination(payload_ies, payload_len > 0);
+
+    let len = header.len()?
+        + payload_ies.len()
+        + payload_termination as usize * IE_DESCRIPTOR_SIZE
+        + payload_len
+        + mic_len;
+    let mut writer = Writer::new(buf, len, limit)?;
+
+    header.write(&mut writer)?;
+    writer.write(payload_ies)?;
+
+    if payload_termination {
+        write_payload_termination(&mut writer)?;
+    }
+
+    for part in payload {
+        writer.write(part)?;
+    }
+
+    writer.write_mic(mic, mic_len)?;
+
+    Ok(len)
+}
+
+/// Composes an enhanced beacon frame.
+/// Chapter 7.3.1
+fn compose_enh_beacon(
+    beacon: &EnhBeaconFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let header = Header::new(&beacon.header)?;
+
+    if header.control.frame_kind != FrameKind::Beacon
+        || header.control.version != FrameVersion::Ieee802154
+    {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    compose_with_ies(
+        header,
+        beacon.payload_ies.as_bytes(),
+        &[beacon.payload],
+        beacon.mic,
+        buf,
+        limit,
+    )
+}
+
+/// Composes an enhanced acknowledgment, the Time Correction IE being
+/// written first among the header IEs.
+/// Chapter 7.3.3
+fn compose_enh_ack(
+    ack: &EnhAckFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let mut header = Header::new(&ack.header)?;
+    let payload_ies = ack.payload_ies.as_bytes();
+
+    if header.control.frame_kind != FrameKind::Acknowledgment
+        || header.control.version != FrameVersion::Ieee802154
+    {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    header.time_correction = ack.time_correction;
+
+    compose_with_ies(header, payload_ies, &[ack.payload], ack.mic, buf, limit)
+}
+
+/// Composes a data frame. Before IEEE 802.15.4-2015, at least one address
+/// is required.
+/// Chapter 7.3.2
+fn compose_data(
+    data: &DataFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let header = Header::new(&data.header)?;
+
+    let addressed = header.dst_addr.address().is_some() || header.src_addr.is_some();
+    let legacy = matches!(
+        header.control.version,
+        FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+    );
+
+    if header.control.frame_kind != FrameKind::Data {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    if legacy && !addressed {
+        return Err(crate::composer::Error::MissingAddress);
+    }
+
+    // A device without short address is reached through its extended one
+    if matches!(header.dst_addr, AddressField::Present(AddressKind::Short(dst)) if dst.is_unassigned())
+    {
+        return Err(crate::composer::Error::InvalidAddressing);
+    }
+
+    compose_with_ies(
+        header,
+        data.payload_ies.as_bytes(),
+        &[data.payload],
+        data.mic,
+        buf,
+        limit,
+    )
+}
+
+/// Composes a MAC command frame, checking the addressing of the commands
+/// that require a specific one.
+/// Chapter 7.3.4, 7.5
+fn compose_command(
+    command: &MacCommandFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let header = Header::new(&command.header)?;
+
+    if header.control.frame_kind != FrameKind::MacCommand {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    match command.command_id {
+        // Chapter 7.5.2, sent by a device to the coordinator it wishes to
+        // join, and chapter 7.5.4, sent by either end of the association,
+        // with its extended address
+        CommandId::AssociationRequest | CommandId::DisassociationNotification
+            if header.src_addr.is_none() || header.dst_addr.address().is_none() =>
+        {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+        CommandId::AssociationRequest | CommandId::DisassociationNotification
+            if !matches!(header.src_addr, Some(AddressKind::Long(_)))
+                || header.dst_addr.is_broadcast() =>
+        {
+            return Err(crate::composer::Error::InvalidAddressing);
+        }
+        // Chapter 7.5.3, sent by the coordinator to the requesting device,
+        // and chapter 7.5.6, sent by a device to its PAN coordinator
+        CommandId::AssociationResponse | CommandId::PanIdConflictNotification
+            if header.src_addr.is_none() || header.dst_addr.address().is_none() =>
+        {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+        CommandId::AssociationResponse | CommandId::PanIdConflictNotification
+            if !matches!(header.src_addr, Some(AddressKind::Long(_)))
+                || !matches!(header.dst_addr, AddressField::Present(AddressKind::Long(_))) =>
+        {
+            return Err(crate::composer::Error::InvalidAddressing);
+        }
+        // Chapter 7.5.5, sent by the device polling its coordinator
+        CommandId::DataRequest if header.src_addr.is_none() => {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+        // Chapter 7.5.7, sent by a device having lost its coordinator, without
+        // destination address or to the broadcast address
+        CommandId::OrphanNotification if header.src_addr.is_none() => {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+        CommandId::OrphanNotification
+            if !matches!(header.src_addr, Some(AddressKind::Long(_)))
+                || !(header.dst_addr.is_absent() || is_broadcast_everywhere(header.dst_addr)) =>
+        {
+            return Err(crate::composer::Error::InvalidAddressing);
+        }
+        // Chapter 7.5.10, broadcast after a PAN ID change or sent to an
+        // orphaned device, in the broadcast PAN
+        CommandId::CoordinatorRealignment if header.src_addr.is_none() => {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+        CommandId::CoordinatorRealignment
+            if !matches!(header.src_addr, Some(AddressKind::Long(_)))
+                || !(is_broadcast_everywhere(header.dst_addr)
+                    || matches!(
+                        header.dst_addr,
+                        AddressField::Present(AddressKind::Long(dst)) if dst.pan_id().is_broadcast()
+                    )) =>
+        {
+            return Err(crate::composer::Error::InvalidAddressing);
+        }
+        // Chapter 7.5.8, broadcast to every coordinator in range
+        CommandId::BeaconRequest
+            if header.src_addr.is_some() || !is_broadcast_everywhere(header.dst_addr) =>
+        {
+            return Err(crate::composer::Error::InvalidAddressing);
+        }
+        _ => (),
+    }
+
+    compose_with_ies(
+        header,
+        command.payload_ies.as_bytes(),
+        &[&[command.command_id.bits()?], command.content],
+        command.mic,
+        buf,
+        limit,
+    )
+}
+
+/// Composes a multipurpose frame. The short frame control field is used
+/// unless the long one is forced or needed for the fields of the frame.
+/// Chapter 7.3.5
+fn compose_multipurpose(
+    frame: &MultipurposeFrame,
+    buf: &mut [u8],
+    limit: PsduLimit,
+) -> Result<usize, crate::composer::Error> {
+    let control = &frame.control;
+    let header_ies = frame.header_ies.as_bytes();
+    let payload_ies = frame.payload_ies.as_bytes();
+
+    let consistent = control.seq_no_present == frame.seq_no.is_some()
+        && control.security_enabled == frame.aux.is_some()
+        && !(control.ack_required && frame.dst_addr.is_broadcast());
+
+    if control.security_enabled && frame.aux.is_none() {
+        return Err(crate::composer::Error::MissingSecurityHeader);
+    }
+
+    if !consistent {
+        return Err(crate::composer::Error::InvalidHeader);
+    }
+
+    let dst_addr = &frame.dst_addr;
+    let src_addr = frame.src_addr.as_ref();
+    let (dst_pan, src_pan) = multipurpose::pan_id_presence(
+        control.pan_id_present,
+        dst_addr.address().is_some(),
+        src_addr.is_some(),
+    );
+
+    match (dst_addr, dst_pan) {
+        // A PAN ID field is written from the address or PAN ID of the field
+        (AddressField::Absent, true) => return Err(crate::composer::Error::MissingAddress),
+        (AddressField::PanOnly(_), false) => return Err(crate::composer::Error::InvalidAddressing),
+        _ => (),
+    }
+
+    let terminations = Terminations::new(
+        !header_ies.is_empty(),
+        !payload_ies.is_empty(),
+        !frame.payload.is_empty(),
+    );
+    let header_termination = match header_terminated(header_ies) {
+        true => HeaderTermination::None,
+        false => terminations.header,
+    };
+    let payload_termination = payload_termination(payload_ies, !frame.payload.is_empty());
+    let mic_len = mic_len(frame.aux.as_ref(), frame.mic)?;
+
+    // Addressing modes derived from the addresses, and IE presence from
+    // the IE lists
+    let control = MultipurposeControlField {
+        dst_addressing_mode: dst_addr.addressing_mode(),
+        src_addressing_mode: AddressingMode::of(&frame.src_addr),
+        ie_present: !header_ies.is_empty() || !payload_ies.is_empty(),
+        ..control.clone()
+    };
+    let bits = control.bits()?;
+
+    let len = control.size()
+        + frame.seq_no.map_or(0, |_| SEQ_NO_SIZE)
+        + dst_addr.len_on_wire(dst_pan)
+        + src_addr.map_or(0, |address| address.len_on_wire(src_pan))
+        + frame.aux.as_ref().map_or(0, |aux| aux.serialized_len())
+        + header_ies.len()
+        + termination_len(header_termination)
+        + payload_ies.len()
+        + payload_termination as usize * IE_DESCRIPTOR_SIZE
+        + frame.payload.len()
+        + mic_len;
+
+    let mut writer = Writer::new(buf, len, limit)?;
+
+    writer.write(&bits.to_le_bytes()[..control.size()])?;
+
+    if let Some(seq_no) = frame.seq_no {
+        writer.write(&[seq_no])?;
+    }
+
+    write_address(&mut writer, dst_addr, dst_pan)?;
+    write_address(&mut writer, &src_addr.copied().into(), src_pan)?;
+
+    if let Some(aux) = &frame.aux {
+        writer.write_with(|buf| aux.write_into(buf))?;
+    }
+
+    writer.write(header_ies)?;
+    write_header_termination(&mut writer, header_termination)?;
+    writer.write(payload_ies)?;
+
+    if payload_termination {
+        write_payload_termination(&mut writer)?;
+    }
+
+    writer.write(frame.payload)?;
+    writer.write_mic(frame.mic, mic_len)?;
+
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::ComposeContext;
+    use crate::{
+        composer::{ComposeInto, Composer},
+        ieee802154::{
+            address::{
+                AddressField, AddressKind, AddressingMode, LongAddress, PanId, ShortAddress,
+            },
+            control_field::{FrameKind, FrameVersion, StandardControlField},
+            fcs::FcsPolicy,
+            frame::{data::DataFrame, ControlField, Frame, MacHeader},
+            ie::{
+                header::{self, HeaderElement, HeaderIeIterator},
+                payload::{self, PayloadIe, PayloadIeGroup, PayloadIeIterator},
+                rit::Rit,
+            },
+            security_header::{AuxiliarySecurityHeader, KeyIdentifierMode, Mic, SecurityLevel},
+            Ieee802154, PsduLimit,
+        },
+        parser::{ParseMode, Parser},
+    };
+
+    fn round_trip(input: &[u8]) {
+        let frame = Ieee802154::parse(input).unwrap();
+        let composed = Ieee802154::compose(&frame).unwrap();
+
+        assert_eq!(composed, input);
+        // Never reallocated nor over-allocated
+        assert_eq!(composed.capacity(), composed.len());
+
+        let composed = Ieee802154::compose_with_fcs(&frame, FcsPolicy::Crc16).unwrap();
+        assert_eq!(composed.capacity(), composed.len());
+    }
+
+    #[test]
+    fn compose_beacon_round_trip() {
+        // Without GTS nor pending address
+        round_trip(&[
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+        ]);
+        // With pending short addresses
+        round_trip(&[
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x02, 0x01, 0x00, 0x02,
+            0x00,
+        ]);
+    }
+
+    #[test]
+    fn compose_coordinator_beacon_round_trip() {
+        // Beacon-enabled PAN coordinator with one GTS and a pending long
+        // address
+        round_trip(&[
+            0x00, 0xC0, 0x17, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x66,
+            0xCC, 0x81, 0x01, 0x01, 0x00, 0x2D, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0xDE, 0xAD,
+        ]);
+    }
+
+    #[test]
+    fn compose_enh_beacon_round_trip() {
+        // 6TiSCH minimal enhanced beacon
+        let input: [u8; 45] = [
+            0x40, 0xEA, 0x01, 0xCD, 0xAB, 0xFF, 0xFF, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x00, 0x3F, 0x1A, 0x88, 0x06, 0x1A, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A,
+            0x1B, 0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x01, 0x1C, 0x00,
+            0x01, 0xC8, 0x00,
+        ];
+        round_trip(&input);
+
+        // With a header IE and a beacon payload, but no payload IEs
+        round_trip(&[
+            0x00, 0xA2, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x0F, 0xAB, 0x80, 0x3F, 0x42, 0x43,
+        ]);
+
+        // 6TiSCH minimal enhanced beacon, followed by a Payload Termination
+        // IE and a beacon payload
+        let mut input: Vec<u8> = input.into();
+        input.extend_from_slice(&[0x00, 0xF8, 0xAB, 0xCD]);
+        round_trip(&input);
+
+        let Frame::EnhBeacon(beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected an enhanced beacon frame");
+        };
+        assert_eq!(beacon.payload, &[0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn compose_data_round_trip() {
+        // Broadcast with PAN ID compression
+        round_trip(&[
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ]);
+        // Long addresses in different PANs, acknowledgment requested
+        round_trip(&[
+            0x21, 0xCC, 0x05, 0x34, 0x12, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x78,
+            0x56, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x42,
+        ]);
+        // Header IEs, payload IEs and payload
+        round_trip(&[
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x00, 0x3F, 0x01, 0x80, 0xAA,
+            0x00, 0xF8, 0xBB, 0xCC,
+        ]);
+    }
+
+    #[test]
+    fn compose_derives_pan_id_compression() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // The compression bit of the control field is ignored
+        let mut frame = data.clone();
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .pan_id_compression = false;
+        assert_eq!(Ieee802154::compose(&Frame::Data(frame)).unwrap(), input);
+
+        // Different PAN IDs, both written
+        let mut frame = data.clone();
+        frame.header.src_addr = Some(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x5678),
+            0xABCD,
+        )));
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(frame)).unwrap(),
+            [0x01, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0x78, 0x56, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE]
+        );
+
+        // Table 7-2: extended addresses sharing a PAN ID keep the
+        // destination one, a single address keeps its own
+        let mut frame = data.clone();
+        frame.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        frame.header.dst_addr = AddressField::Present(AddressKind::Long(LongAddress::new(
+            PanId::new(0x1234),
+            0x0102030405060708,
+        )));
+        frame.header.src_addr = Some(AddressKind::Long(LongAddress::new(
+            PanId::new(0x1234),
+            0x1112131415161718,
+        )));
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(frame)).unwrap(),
+            [
+                0x01, 0xEC, 0x01, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x18,
+                0x17, 0x16, 0x15, 0x14, 0x13, 0x12, 0x11, 0xDE, 0xAD, 0xBE
+            ]
+        );
+
+        let mut frame = data;
+        frame.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        frame.header.src_addr = None;
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(frame)).unwrap(),
+            [0x01, 0x28, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xDE, 0xAD, 0xBE]
+        );
+    }
+
+    #[test]
+    fn compose_data_invalid_header() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // No address before IEEE 802.15.4-2015
+        let mut frame = data.clone();
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .pan_id_compression = false;
+        frame.header.dst_addr = AddressField::Absent;
+        frame.header.src_addr = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+
+        // Unassigned short address as destination
+        let mut frame = data.clone();
+        frame.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0xFFFE,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame.clone())),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        frame.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0xFFFD,
+        )));
+        assert!(Ieee802154::compose(&Frame::Data(frame)).is_ok());
+
+        // PAN ID without address, only written by IEEE 802.15.4-2015 frames
+        // without source address
+        let mut frame = data.clone();
+        frame.header.dst_addr = AddressField::PanOnly(PanId::new(0x1234));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame.clone())),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        frame.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame.clone())),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        frame.header.src_addr = None;
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(frame)).unwrap(),
+            [0x41, 0x20, 0x01, 0x34, 0x12, 0xDE, 0xAD, 0xBE]
+        );
+
+        // Security enabled without auxiliary security header
+        let mut frame = data.clone();
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .security_enabled = true;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::MissingSecurityHeader)
+        ));
+
+        // Frame control field of another layout
+        let mut frame = data.clone();
+        frame.header.control = ControlField::Extended(0x07);
+        assert!(matches!(
+            frame.header.serialized_len(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        // Sequence number suppressed but present
+        let mut frame = data;
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .seq_no_present = false;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn compose_psdu_limits() {
+        let header = [0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB];
+        let Frame::Data(data) = Ieee802154::parse(&header).unwrap() else {
+            panic!("expected a data frame");
+        };
+        let payload = [0u8; 2048];
+        let of_len = |len: usize| {
+            Frame::Data(DataFrame {
+                payload: &payload[..len - header.len()],
+                ..data.clone()
+            })
+        };
+
+        assert_eq!(Ieee802154::compose(&of_len(127)).unwrap().len(), 127);
+        assert!(matches!(
+            Ieee802154::compose(&of_len(128)),
+            Err(crate::composer::Error::FrameTooLong { len: 128, max: 127 })
+        ));
+        assert!(matches!(
+            Ieee802154::compose_into(&of_len(128), &mut [0u8; 2048]),
+            Err(crate::composer::Error::FrameTooLong { len: 128, max: 127 })
+        ));
+
+        let context = ComposeContext {
+            limit: PsduLimit::Sun,
+        };
+        assert_eq!(
+            Ieee802154::compose_with_context(&of_len(128), context)
+                .unwrap()
+                .len(),
+            128
+        );
+        assert_eq!(
+            Ieee802154::compose_with_context(&of_len(2047), context)
+                .unwrap()
+                .len(),
+            2047
+        );
+        assert!(matches!(
+            Ieee802154::compose_with_context(&of_len(2048), context),
+            Err(crate::composer::Error::FrameTooLong {
+                len: 2048,
+                max: 2047
+            })
+        ));
+    }
+
+    #[test]
+    fn compose_inserts_ie_terminations() {
+        let input: [u8; 9] = [0x41, 0x88, 0x2A, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB];
+        let Frame::Data(mut data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        data.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        data.header.control.as_standard_mut().unwrap().ie_present = true;
+
+        let header_ies = [HeaderElement::Rit(Rit {
+            time_to_first_listen: 0x01,
+            number_of_repeat_listen: 0x02,
+            repeat_listen_interval: 0x0304,
+        })];
+        let payload_ies = [PayloadIe {
+            group: PayloadIeGroup::Esdu,
+            content: &[0xAB, 0xCD],
+        }];
+
+        let mut header_buf = [0u8; 16];
+        let header_len = header::write_ies(&header_ies, &mut header_buf).unwrap();
+        let mut payload_buf = [0u8; 16];
+        let payload_len = payload::write_ies(&payload_ies, &mut payload_buf).unwrap();
+
+        // Header IEs, payload IEs and payload: HT1 and Payload Termination
+        let mut frame = data.clone();
+        frame.header.header_ies = HeaderIeIterator::new(&header_buf[..header_len]);
+        frame.payload_ies = PayloadIeIterator::new(&payload_buf[..payload_len]);
+        frame.payload = &[0xDE, 0xAD];
+
+        let composed = Ieee802154::compose(&Frame::Data(frame)).unwrap();
+        assert_eq!(
+            &composed[9..],
+            &[
+                0x84, 0x0D, 0x01, 0x02, 0x04, 0x03, 0x00, 0x3F, 0x02, 0x80, 0xAB, 0xCD, 0x00, 0xF8,
+                0xDE, 0xAD
+            ]
+        );
+
+        let Frame::Data(parsed) = Ieee802154::parse(&composed).unwrap() else {
+            panic!("expected a data frame");
+        };
+        let parsed_header_ies: Vec<HeaderElement> = parsed
+            .header
+            .header_ies
+            .clone()
+            .map(|ie| ie.unwrap().element().unwrap())
+            .collect();
+        let parsed_payload_ies: Vec<PayloadIe> =
+            parsed.payload_ies.clone().map(Result::unwrap).collect();
+        assert_eq!(parsed_header_ies, header_ies);
+        assert_eq!(parsed_payload_ies, payload_ies);
+        assert_eq!(parsed.payload, &[0xDE, 0xAD]);
+        assert_eq!(Ieee802154::compose(&Frame::Data(parsed)).unwrap(), composed);
+
+        // Header IEs and payload: HT2
+        let mut frame = data.clone();
+        frame.header.header_ies = HeaderIeIterator::new(&header_buf[..header_len]);
+        frame.payload = &[0xDE, 0xAD];
+
+        let composed = Ieee802154::compose(&Frame::Data(frame)).unwrap();
+        assert_eq!(&composed[9 + header_len..], &[0x80, 0x3F, 0xDE, 0xAD]);
+
+        // Payload IEs running to the end of the frame: HT1 only
+        let mut frame = data;
+        frame.payload_ies = PayloadIeIterator::new(&payload_buf[..payload_len]);
+
+        let composed = Ieee802154::compose(&Frame::Data(frame)).unwrap();
+        assert_eq!(&composed[9..], &[0x00, 0x3F, 0x02, 0x80, 0xAB, 0xCD]);
+        round_trip(&composed);
+    }
+
+    #[test]
+    fn compose_secured_data_round_trip() {
+        // Security level 5 with a key index, followed by the encrypted
+        // payload and its 32-bit MIC
+        round_trip(&[
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ]);
+        // Security level 7 with an 8-byte key source, frame counter
+        // suppressed in IEEE 802.15.4-2015, followed by the encrypted payload
+        // and its 128-bit MIC
+        round_trip(&[
+            0x69, 0xA8, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x3F, 0x08, 0x07, 0x06, 0x05,
+            0x04, 0x03, 0x02, 0x01, 0x42, 0xDE, 0xAD, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06,
+            0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+        ]);
+    }
+
+    #[test]
+    fn compose_mic() {
+        let input: [u8; 21] = [
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ];
+        let Frame::Data(mut data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // Room left for the MIC
+        data.mic = &[];
+        let frame = Frame::Data(data.clone());
+        let mut buf = [0xFF; 32];
+
+        assert_eq!(Ieee802154::composed_len(&frame).unwrap(), input.len());
+        assert_eq!(
+            Ieee802154::compose_into(&frame, &mut buf).unwrap(),
+            input.len()
+        );
+        assert_eq!(buf[..17], input[..17]);
+        assert_eq!(buf[17..21], [0x00; 4]);
+
+        // MIC of another security level
+        data.mic = &[0xAA, 0xBB];
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(data.clone())),
+            Err(crate::composer::Error::InvalidHeader)
+        );
+
+        // MIC of an unsecured frame
+        data.header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .security_enabled = false;
+        data.header.aux = None;
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(data)),
+            Err(crate::composer::Error::InvalidHeader)
+        );
+    }
+
+    #[test]
+    fn compose_annex_c_secured_data() {
+        // Data frame of Annex C.2.1: MHR with security level 4 and frame
+        // counter 5, followed by the encrypted payload
+        let input: [u8; 30] = [
+            0x69, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x04, 0x05, 0x00, 0x00, 0x00, 0xD4, 0x3E,
+            0x02, 0x2B,
+        ];
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        let pan = PanId::new(0x4321);
+        assert!(data.header.control.security_enabled());
+        assert_eq!(data.header.seq_no, Some(0x84));
+        assert_eq!(
+            data.header.dst_addr,
+            LongAddress::new(pan, 0xACDE_4800_0000_0002).into()
+        );
+        assert_eq!(
+            data.header.src_addr,
+            Some(LongAddress::new(pan, 0xACDE_4800_0000_0001).into())
+        );
+        assert_eq!(
+            data.header.aux,
+            Some(AuxiliarySecurityHeader {
+                security_level: Some(SecurityLevel {
+                    mic: Mic::None,
+                    encrypted: true,
+                }),
+                key_identifier_mode: KeyIdentifierMode::Implicit,
+                frame_counter: Some(5),
+            })
+        );
+        assert_eq!(data.header.serialized_len().unwrap(), 26);
+        assert_eq!(data.payload, &[0xD4, 0x3E, 0x02, 0x2B]);
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(data.clone())).unwrap(),
+            input
+        );
+
+        // The auxiliary security header follows the security enabled bit
+        let mut frame = data.clone();
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .security_enabled = false;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        let mut frame = data;
+        frame.header.aux = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::MissingSecurityHeader)
+        ));
+    }
+
+    #[test]
+    fn compose_invalid_control_field() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+        ];
+        let Frame::Beacon(mut beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a beacon frame");
+        };
+
+        // Acknowledgment requested for a beacon
+        beacon
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .ack_required = true;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Beacon(beacon)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        let input = [
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ];
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // Security level 0 in an IEEE 802.15.4-2003 frame
+        let mut frame = data.clone();
+        frame.header.aux.as_mut().unwrap().security_level = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        // Frame counter suppressed in an IEEE 802.15.4-2003 frame
+        let mut frame = data.clone();
+        frame.header.aux.as_mut().unwrap().frame_counter = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame.clone())),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        // Emitted as the Frame Counter Suppression bit from
+        // IEEE 802.15.4-2015 on
+        frame.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        let composed = Ieee802154::compose(&Frame::Data(frame)).unwrap();
+        assert_eq!(composed[1], 0xA8);
+        assert_eq!(composed[9], 0x2D);
+        assert_eq!(composed.len(), input.len() - 4);
+
+        // IEs in an IEEE 802.15.4-2003 frame
+        let header_ies = [HeaderElement::Rit(Rit {
+            time_to_first_listen: 0x01,
+            number_of_repeat_listen: 0x02,
+            repeat_listen_interval: 0x0304,
+        })];
+        let mut header_buf = [0u8; 16];
+        let header_len = header::write_ies(&header_ies, &mut header_buf).unwrap();
+
+        let mut frame = data;
+        frame.header.header_ies = HeaderIeIterator::new(&header_buf[..header_len]);
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn compose_derives_ie_present() {
+        let input: [u8; 9] = [0x41, 0xA8, 0x2A, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB];
+        let Frame::Data(mut data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // Set without IEs, cleared when composing
+        data.header.control.as_standard_mut().unwrap().ie_present = true;
+        assert_eq!(
+            Ieee802154::compose(&Frame::Data(data.clone())).unwrap(),
+            input
+        );
+
+        let header_ies = [HeaderElement::Rit(Rit {
+            time_to_first_listen: 0x01,
+            number_of_repeat_listen: 0x02,
+            repeat_listen_interval: 0x0304,
+        })];
+        let mut header_buf = [0u8; 16];
+        let header_len = header::write_ies(&header_ies, &mut header_buf).unwrap();
+
+        // Cleared with IEs, set when composing
+        data.header.control.as_standard_mut().unwrap().ie_present = false;
+        data.header.header_ies = HeaderIeIterator::new(&header_buf[..header_len]);
+        let composed = Ieee802154::compose(&Frame::Data(data)).unwrap();
+
+        let Frame::Data(parsed) = Ieee802154::parse(&composed).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert!(parsed.header.control.ie_present());
+        assert_eq!(
+            parsed.header.header_ies.as_bytes(),
+            &header_buf[..header_len]
+        );
+    }
+
+    #[test]
+    fn compose_ack_and_pending_semantics() {
+        let input: [u8; 11] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD,
+        ];
+        let Frame::Data(mut data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+
+        // Acknowledgment requested for a broadcast frame
+        data.header.control.as_standard_mut().unwrap().ack_required = true;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Data(data.clone())),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        data.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0x5678,
+        )));
+        assert!(Ieee802154::compose(&Frame::Data(data)).is_ok());
+
+        let input: [u8; 10] = [0x63, 0x88, 0x01, 0x34, 0x12, 0x78, 0x56, 0xCD, 0xAB, 0x04];
+        let Frame::MacCommand(mut command) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a MAC command frame");
+        };
+        assert!(Ieee802154::compose(&Frame::MacCommand(command.clone())).is_ok());
+
+        // Pending data announced by a MAC command
+        command
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .frame_pending = true;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(command)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn compose_reserved_frame_version() {
+        let input: [u8; 10] = [0x41, 0xB8, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x42];
+        let frame = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap();
+
+        assert!(matches!(
+            Ieee802154::compose(&frame),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        let Frame::Data(mut data) = frame else {
+            panic!("expected a data frame");
+        };
+        data.header.control.as_standard_mut().unwrap().version = FrameVersion::Ieee802154;
+        assert!(Ieee802154::compose(&Frame::Data(data)).is_ok());
+    }
+
+    #[test]
+    fn compose_beacon_without_source_address() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+        ];
+        let Frame::Beacon(mut beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a beacon frame");
+        };
+        beacon.header.src_addr = None;
+
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Beacon(beacon)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn compose_beacon_security_mismatch() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+        ];
+        let Frame::Beacon(beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a beacon frame");
+        };
+        assert_eq!(beacon.header.aux, None);
+
+        let mut frame = beacon.clone();
+        frame
+            .header
+            .control
+            .as_standard_mut()
+            .unwrap()
+            .security_enabled = true;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Beacon(frame)),
+            Err(crate::composer::Error::MissingSecurityHeader)
+        ));
+
+        let mut frame = beacon.clone();
+        frame.header.aux = Some(AuxiliarySecurityHeader {
+            security_level: SecurityLevel::from_bits(0x05).unwrap(),
+            key_identifier_mode: KeyIdentifierMode::Implicit,
+            frame_counter: Some(1),
+        });
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Beacon(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        let mut frame = beacon;
+        frame.header.seq_no = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::Beacon(frame)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn compose_into_buffer() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+
+        let mut buf = [0u8; 127];
+        let len = Ieee802154::compose_into(&frame, &mut buf).unwrap();
+        assert_eq!(&buf[..len], input);
+
+        assert!(matches!(
+            Ieee802154::compose_into(&frame, &mut buf[..11]),
+            Err(crate::composer::Error::BufferTooSmall { needed: 12 })
+        ));
+
+        let ack = Ieee802154::parse(&[0x02, 0x00, 0x2A]).unwrap();
+        assert!(matches!(
+            crate::compose_into::<Ieee802154>(&ack, &mut buf[..2]),
+            Err(crate::error::Error::Composer(
+                crate::composer::Error::BufferTooSmall { needed: 3 }
+            ))
+        ));
+        assert_eq!(
+            crate::compose_into::<Ieee802154>(&ack, &mut buf).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn compose_into_heapless() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+
+        let composed = crate::compose_heapless::<Ieee802154, 12>(&frame).unwrap();
+        assert_eq!(composed, input);
+
+        assert!(matches!(
+            crate::compose_heapless::<Ieee802154, 11>(&frame),
+            Err(crate::composer::Error::BufferTooSmall { needed: 12 })
+        ));
+    }
+
+    #[test]
+    fn composed_len_matches_compose() {
+        let addresses = [
+            None,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD,
+            ))),
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x4321),
+                0x0001,
+            ))),
+            Some(AddressKind::Long(LongAddress::new(
+                PanId::new(0x1234),
+                0x0102030405060708,
+            ))),
+        ];
+        let versions = [
+            FrameVersion::Ieee802154_2003,
+            FrameVersion::Ieee802154_2006,
+            FrameVersion::Ieee802154,
+        ];
+        let payloads: [&[u8]; 3] = [&[], &[0x42], &[0xDE, 0xAD, 0xBE, 0xEF]];
+        let mut composed = 0;
+
+        for version in &versions {
+            for (dst_addr, src_addr) in addresses
+                .iter()
+                .flat_map(|dst| addresses.iter().map(move |src| (dst, src)))
+            {
+                for bits in 0..4 {
+                    for payload in payloads {
+                        let frame = Frame::Data(DataFrame {
+                            header: MacHeader {
+                                control: StandardControlField {
+                                    frame_kind: FrameKind::Data,
+                                    security_enabled: false,
+                                    frame_pending: false,
+                                    ack_required: bits & 0x2 != 0,
+                                    pan_id_compression: bits & 0x1 != 0,
+                                    seq_no_present: *version != FrameVersion::Ieee802154
+                                        || bits & 0x2 == 0,
+                                    ie_present: false,
+                                    version: *version,
+                                    dst_addressing_mode: AddressingMode::None.bits(),
+                                    src_addressing_mode: AddressingMode::None.bits(),
+                                }
+                                .into(),
+                                seq_no: (*version != FrameVersion::Ieee802154 || bits & 0x2 == 0)
+                                    .then_some(0x2A),
+                                dst_addr: (*dst_addr).into(),
+                                src_addr: *src_addr,
+                                aux: None,
+                                header_ies: HeaderIeIterator::new(&[]),
+                            },
+                            payload_ies: PayloadIeIterator::new(&[]),
+                            payload,
+                            mic: &[],
+                        });
+
+                        match Ieee802154::compose(&frame) {
+                            Ok(bytes) => {
+                                assert_eq!(Ieee802154::composed_len(&frame).unwrap(), bytes.len());
+
+                                let Frame::Data(data) = &frame else {
+                                    unreachable!();
+                                };
+                                assert_eq!(
+                                    data.header.serialized_len().unwrap() + payload.len(),
+                                    bytes.len()
+                                );
+                                composed += 1;
+                            }
+                            Err(_) => assert!(Ieee802154::composed_len(&frame).is_err()),
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(composed > 0);
+
+        let input: [u8; 31] = [
+            0x00, 0xC0, 0x17, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x66,
+            0xCC, 0x81, 0x01, 0x01, 0x00, 0x2D, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0xDE, 0xAD,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        assert_eq!(Ieee802154::composed_len(&frame).unwrap(), input.len());
+
+        let Frame::Beacon(beacon) = frame else {
+            panic!("expected a beacon frame");
+        };
+        assert_eq!(beacon.payload.gts.serialized_len(), 5);
+    }
+
+    #[test]
+    fn compose_with_fcs() {
+        // Data request, followed by its CRC-16
+        let input: [u8; 12] = [
+            0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x04, 0xD5, 0xA9,
+        ];
+        let frame = Ieee802154::parse(&input[..10]).unwrap();
+
+        assert_eq!(
+            Ieee802154::composed_len_with_fcs(&frame, FcsPolicy::Crc16).unwrap(),
+            12
+        );
+        assert_eq!(
+            Ieee802154::compose_with_fcs(&frame, FcsPolicy::Crc16).unwrap(),
+            input
+        );
+        assert_eq!(
+            Ieee802154::compose_with_fcs(&frame, FcsPolicy::Absent).unwrap(),
+            input[..10]
+        );
+
+        let composed = Ieee802154::compose_with_fcs(&frame, FcsPolicy::Crc32).unwrap();
+        assert_eq!(composed[10..], [0x35, 0x8D, 0xBA, 0xAD]);
+        assert!(Ieee802154::validate(&composed, FcsPolicy::Crc32).is_ok());
+
+        let mut buf = [0u8; 12];
+        assert!(matches!(
+            Ieee802154::compose_into_with_fcs(&frame, &mut buf[..11], FcsPolicy::Crc16),
+            Err(crate::composer::Error::BufferTooSmall { needed: 12 })
+        ));
+        assert_eq!(
+            Ieee802154::compose_into_with_fcs(&frame, &mut buf, FcsPolicy::Crc16).unwrap(),
+            12
+        );
+        assert_eq!(buf, input);
     }
 }
@@ -1,20 +1,492 @@
+use crate::{ieee802154::address::AddressingMode, parser::ParseMode};
+
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StandardControlField {
-    frame_kind: FrameKind,
-    security_enabled: bool,
-    frame_pending: bool,
-    ack_required: bool,
-    pan_id_compression: bool,
-    seq_no_present: bool,
-    ie_present: bool,
-    version: FrameVersion,
+    pub(crate) frame_kind: FrameKind,
+    pub(crate) security_enabled: bool,
+    pub(crate) frame_pending: bool,
+    pub(crate) ack_required: bool,
+    pub(crate) pan_id_compression: bool,
+    pub(crate) seq_no_present: bool,
+    pub(crate) ie_present: bool,
+    pub(crate) version: FrameVersion,
+    /// Raw destination addressing mode
+    pub(crate) dst_addressing_mode: u8,
+    /// Raw source addressing mode
+    pub(crate) src_addressing_mode: u8,
+}
+
+pub(crate) mod offset {
+    pub(crate) const FRAME_KIND: usize = 0;
+    pub(crate) const SECURITY_ENABLED: usize = 3;
+    pub(crate) const FRAME_PENDING: usize = 4;
+    pub(crate) const ACK_REQUIRED: usize = 5;
+    pub(crate) const PAN_ID_COMPRESSION: usize = 6;
+    pub(crate) const SEQ_NO_SUPPRESSION: usize = 8;
+    pub(crate) const IE_PRESENT: usize = 9;
+    pub(crate) const DST_ADDRESSING_MODE: usize = 10;
+    pub(crate) const VERSION: usize = 12;
+    pub(crate) const SRC_ADDRESSING_MODE: usize = 14;
+}
+
+pub(crate) mod mask {
+    use super::offset;
+
+    pub(crate) const FRAME_KIND: u16 = 0x7 << offset::FRAME_KIND;
+    pub(crate) const SECURITY_ENABLED: u16 = 0x1 << offset::SECURITY_ENABLED;
+    pub(crate) const FRAME_PENDING: u16 = 0x1 << offset::FRAME_PENDING;
+    pub(crate) const ACK_REQUIRED: u16 = 0x1 << offset::ACK_REQUIRED;
+    pub(crate) const PAN_ID_COMPRESSION: u16 = 0x1 << offset::PAN_ID_COMPRESSION;
+    pub(crate) const SEQ_NO_SUPPRESSION: u16 = 0x1 << offset::SEQ_NO_SUPPRESSION;
+    pub(crate) const IE_PRESENT: u16 = 0x1 << offset::IE_PRESENT;
+    pub(crate) const DST_ADDRESSING_MODE: u16 = 0x3 << offset::DST_ADDRESSING_MODE;
+    pub(crate) const VERSION: u16 = 0x3 << offset::VERSION;
+    pub(crate) const SRC_ADDRESSING_MODE: u16 = 0x3 << offset::SRC_ADDRESSING_MODE;
+}
+
+/// Size in bytes of a standard frame control field
+pub const STANDARD_CONTROL_FIELD_SIZE: usize = 2;
+
+impl StandardControlField {
+    /// Decodes the frame control field shared by beacon, data,
+    /// acknowledgment and MAC command frames.
+    /// Chapter 7.2.2
+    ///
+    /// # Error
+    ///
+    /// If the frame type is not one using the standard layout, returns
+    /// `InvalidFrameKind`. If the frame version is invalid for it, returns
+    /// `InvalidFrameVersion`.
+    pub fn from_bytes(
+        data: &[u8; STANDARD_CONTROL_FIELD_SIZE],
+    ) -> Result<Self, crate::parser::Error> {
+        Self::from_bytes_with_mode(data, ParseMode::Strict)
+    }
+
+    /// Same as [`StandardControlField::from_bytes`]. In lenient mode, the
+    /// reserved frame type and frame version are accepted, and the frame
+    /// type is assumed to use the standard layout.
+    pub fn from_bytes_with_mode(
+        data: &[u8; STANDARD_CONTROL_FIELD_SIZE],
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        let data: u16 = u16::from_le_bytes(*data);
+
+        let frame_kind = FrameKind::from_byte_with_mode(
+            ((data & mask::FRAME_KIND) >> offset::FRAME_KIND) as u8,
+            mode,
+        )?;
+
+        if !matches!(
+            frame_kind,
+            FrameKind::Beacon
+                | FrameKind::Data
+                | FrameKind::Acknowledgment
+                | FrameKind::MacCommand
+                | FrameKind::Reserved(_)
+        ) {
+            return Err(crate::parser::Error::InvalidFrameKind(frame_kind.bits()));
+        }
+
+        let version = FrameVersion::from_byte_with_mode(
+            frame_kind,
+            ((data & mask::VERSION) >> offset::VERSION) as u8,
+            mode,
+        )?;
+
+        Ok(StandardControlField {
+            frame_kind,
+            security_enabled: data & mask::SECURITY_ENABLED != 0,
+            frame_pending: data & mask::FRAME_PENDING != 0,
+            ack_required: data & mask::ACK_REQUIRED != 0,
+            pan_id_compression: data & mask::PAN_ID_COMPRESSION != 0,
+            seq_no_present: data & mask::SEQ_NO_SUPPRESSION == 0,
+            ie_present: data & mask::IE_PRESENT != 0,
+            version,
+            dst_addressing_mode: ((data & mask::DST_ADDRESSING_MODE) >> offset::DST_ADDRESSING_MODE)
+                as u8,
+            src_addressing_mode: ((data & mask::SRC_ADDRESSING_MODE) >> offset::SRC_ADDRESSING_MODE)
+                as u8,
+        })
+    }
+
+    /// Encodes the frame control field, the inverse of
+    /// [`StandardControlField::from_bytes`].
+    /// Chapter 7.2.2
+    ///
+    /// # Errors
+    ///
+    /// If the frame type does not use the standard layout or the frame
+    /// version is invalid for it, returns `InvalidHeader`.
+    pub fn bits(&self) -> Result<[u8; STANDARD_CONTROL_FIELD_SIZE], crate::composer::Error> {
+        if !matches!(
+            self.frame_kind,
+            FrameKind::Beacon | FrameKind::Data | FrameKind::Acknowledgment | FrameKind::MacCommand
+        ) {
+            return Err(crate::composer::Error::InvalidHeader);
+        }
+
+        let kind = self.frame_kind.bits() as u16;
+        let version = self.version.bits(self.frame_kind)? as u16;
+
+        Ok((((kind << offset::FRAME_KIND) & mask::FRAME_KIND)
+            | ((self.security_enabled as u16) << offset::SECURITY_ENABLED)
+            | ((self.frame_pending as u16) << offset::FRAME_PENDING)
+            | ((self.ack_required as u16) << offset::ACK_REQUIRED)
+            | ((self.pan_id_compression as u16) << offset::PAN_ID_COMPRESSION)
+            | ((!self.seq_no_present as u16) << offset::SEQ_NO_SUPPRESSION)
+            | ((self.ie_present as u16) << offset::IE_PRESENT)
+            | (((self.dst_addressing_mode as u16) << offset::DST_ADDRESSING_MODE)
+                & mask::DST_ADDRESSING_MODE)
+            | ((version << offset::VERSION) & mask::VERSION)
+            | (((self.src_addressing_mode as u16) << offset::SRC_ADDRESSING_MODE)
+                & mask::SRC_ADDRESSING_MODE))
+            .to_le_bytes())
+    }
+
+    /// Control field of an unsecured frame with a sequence number, and
+    /// without acknowledgment request, pending data nor IEs. The addressing
+    /// modes and the PAN ID compression are derived from the addresses when
+    /// composing.
+    pub const fn new(frame_kind: FrameKind, version: FrameVersion) -> Self {
+        StandardControlField {
+            frame_kind,
+            security_enabled: false,
+            frame_pending: false,
+            ack_required: false,
+            pan_id_compression: false,
+            seq_no_present: true,
+            ie_present: false,
+            version,
+            dst_addressing_mode: AddressingMode::None.bits(),
+            src_addressing_mode: AddressingMode::None.bits(),
+        }
+    }
+
+    /// Checks that the field can be encoded and that its flags make sense
+    /// together.
+    /// Chapter 7.2.2
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidHeader` if [`StandardControlField::bits`] fails, if
+    /// an acknowledgment is requested for a beacon or an acknowledgment, if
+    /// a MAC command announces pending data, or if the sequence number is
+    /// suppressed or IEs are present in a frame older than
+    /// IEEE 802.15.4-2015.
+    pub fn validate(&self) -> Result<(), crate::composer::Error> {
+        self.bits()?;
+
+        let legacy = matches!(
+            self.version,
+            FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+        );
+
+        let invalid = (self.ack_required
+            && matches!(
+                self.frame_kind,
+                FrameKind::Beacon | FrameKind::Acknowledgment
+            ))
+            // Chapter 7.5, the commands set the Frame Pending field to zero
+            || (self.frame_pending && self.frame_kind == FrameKind::MacCommand)
+            || (legacy && (!self.seq_no_present || self.ie_present));
+
+        match invalid {
+            true => Err(crate::composer::Error::InvalidHeader),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns whether [`StandardControlField::validate`] passes.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    pub const fn frame_kind(&self) -> FrameKind {
+        self.frame_kind
+    }
+
+    pub const fn security_enabled(&self) -> bool {
+        self.security_enabled
+    }
+
+    pub const fn frame_pending(&self) -> bool {
+        self.frame_pending
+    }
+
+    pub const fn ack_required(&self) -> bool {
+        self.ack_required
+    }
+
+    pub const fn pan_id_compression(&self) -> bool {
+        self.pan_id_compression
+    }
+
+    pub const fn seq_no_present(&self) -> bool {
+        self.seq_no_present
+    }
+
+    pub const fn ie_present(&self) -> bool {
+        self.ie_present
+    }
+
+    pub const fn version(&self) -> FrameVersion {
+        self.version
+    }
+
+    /// # Error
+    ///
+    /// If the mode is reserved, returns `InvalidAddressingMode`.
+    pub const fn dst_addressing_mode(&self) -> Result<AddressingMode, crate::parser::Error> {
+        AddressingMode::from_bits(self.dst_addressing_mode)
+    }
+
+    /// # Error
+    ///
+    /// If the mode is reserved, returns `InvalidAddressingMode`.
+    pub const fn src_addressing_mode(&self) -> Result<AddressingMode, crate::parser::Error> {
+        AddressingMode::from_bits(self.src_addressing_mode)
+    }
+
+    pub const fn with_ack_required(mut self, ack_required: bool) -> Self {
+        self.ack_required = ack_required;
+        self
+    }
+
+    /// The frame then needs an auxiliary security header.
+    pub const fn with_security(mut self, enabled: bool) -> Self {
+        self.security_enabled = enabled;
+        self
+    }
+
+    pub const fn with_frame_pending(mut self, pending: bool) -> Self {
+        self.frame_pending = pending;
+        self
+    }
+
+    /// Only used by [`StandardControlField::bits`], the composer deriving
+    /// the bit from the addresses of the frame.
+    pub const fn with_pan_id_compression(mut self, compression: bool) -> Self {
+        self.pan_id_compression = compression;
+        self
+    }
+
+    pub const fn with_seq_no_suppressed(mut self, suppressed: bool) -> Self {
+        self.seq_no_present = !suppressed;
+        self
+    }
+
+    pub const fn with_ie_present(mut self, present: bool) -> Self {
+        self.ie_present = present;
+        self
+    }
+}
+
+/// Frame control field of multipurpose frames, in its 1-octet short form or
+/// 2-octet long form.
+/// Chapter 7.3.5.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipurposeControlField {
+    /// Forces the long form when composing. The long form is used anyway as
+    /// soon as one of the fields missing from the short form is needed.
+    pub(crate) long_frame_control: bool,
+    pub(crate) pan_id_present: bool,
+    pub(crate) security_enabled: bool,
+    pub(crate) seq_no_present: bool,
+    pub(crate) frame_pending: bool,
+    pub(crate) ack_required: bool,
+    pub(crate) ie_present: bool,
+    pub(crate) version: FrameVersion,
+    pub(crate) dst_addressing_mode: AddressingMode,
+    pub(crate) src_addressing_mode: AddressingMode,
+}
+
+pub(crate) mod multipurpose_offset {
+    pub(crate) const FRAME_KIND: usize = 0;
+    pub(crate) const LONG_FRAME_CONTROL: usize = 3;
+    pub(crate) const DST_ADDRESSING_MODE: usize = 4;
+    pub(crate) const SRC_ADDRESSING_MODE: usize = 6;
+    pub(crate) const PAN_ID_PRESENT: usize = 8;
+    pub(crate) const SECURITY_ENABLED: usize = 9;
+    pub(crate) const SEQ_NO_SUPPRESSION: usize = 10;
+    pub(crate) const FRAME_PENDING: usize = 11;
+    pub(crate) const VERSION: usize = 12;
+    pub(crate) const ACK_REQUIRED: usize = 14;
+    pub(crate) const IE_PRESENT: usize = 15;
+}
+
+pub(crate) mod multipurpose_mask {
+    use super::multipurpose_offset as offset;
+
+    pub(crate) const FRAME_KIND: u16 = 0x7 << offset::FRAME_KIND;
+    pub(crate) const LONG_FRAME_CONTROL: u16 = 0x1 << offset::LONG_FRAME_CONTROL;
+    pub(crate) const DST_ADDRESSING_MODE: u16 = 0x3 << offset::DST_ADDRESSING_MODE;
+    pub(crate) const SRC_ADDRESSING_MODE: u16 = 0x3 << offset::SRC_ADDRESSING_MODE;
+    pub(crate) const PAN_ID_PRESENT: u16 = 0x1 << offset::PAN_ID_PRESENT;
+    pub(crate) const SECURITY_ENABLED: u16 = 0x1 << offset::SECURITY_ENABLED;
+    pub(crate) const SEQ_NO_SUPPRESSION: u16 = 0x1 << offset::SEQ_NO_SUPPRESSION;
+    pub(crate) const FRAME_PENDING: u16 = 0x1 << offset::FRAME_PENDING;
+    pub(crate) const VERSION: u16 = 0x3 << offset::VERSION;
+    pub(crate) const ACK_REQUIRED: u16 = 0x1 << offset::ACK_REQUIRED;
+    pub(crate) const IE_PRESENT: u16 = 0x1 << offset::IE_PRESENT;
+}
+
+/// Size in bytes of the short form of the multipurpose frame control field
+pub const MULTIPURPOSE_SHORT_CONTROL_FIELD_SIZE: usize = 1;
+/// Size in bytes of the long form of the multipurpose frame control field
+pub const MULTIPURPOSE_LONG_CONTROL_FIELD_SIZE: usize = 2;
+
+impl MultipurposeControlField {
+    /// Decodes the frame control field at the start of `data`, its form
+    /// being given by the Long Frame Control bit. The short form has a
+    /// sequence number and none of the other optional fields.
+    ///
+    /// # Error
+    ///
+    /// If `data` is too short, returns `Truncated`. If the frame type is not
+    /// multipurpose, returns `InvalidFrameKind`. If an addressing mode is
+    /// reserved, returns `InvalidAddressingMode`. If the frame version is
+    /// invalid, returns `InvalidFrameVersion`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        use multipurpose_mask as mask;
+        use multipurpose_offset as offset;
+
+        let first = *data.first().ok_or(crate::parser::Error::Truncated)?;
+
+        let frame_kind =
+            FrameKind::from_byte(((first as u16 & mask::FRAME_KIND) >> offset::FRAME_KIND) as u8)?;
+
+        if frame_kind != FrameKind::MultiPurpose {
+            return Err(crate::parser::Error::InvalidFrameKind(frame_kind.bits()));
+        }
+
+        let dst_addressing_mode = AddressingMode::from_bits(
+            ((first as u16 & mask::DST_ADDRESSING_MODE) >> offset::DST_ADDRESSING_MODE) as u8,
+        )?;
+        let src_addressing_mode = AddressingMode::from_bits(
+            ((first as u16 & mask::SRC_ADDRESSING_MODE) >> offset::SRC_ADDRESSING_MODE) as u8,
+        )?;
+
+        if first as u16 & mask::LONG_FRAME_CONTROL == 0 {
+            return Ok(MultipurposeControlField {
+                long_frame_control: false,
+                pan_id_present: false,
+                security_enabled: false,
+                seq_no_present: true,
+                frame_pending: false,
+                ack_required: false,
+                ie_present: false,
+                version: FrameVersion::Ieee802154,
+                dst_addressing_mode,
+                src_addressing_mode,
+            });
+        }
+
+        let data: u16 = match data.get(..MULTIPURPOSE_LONG_CONTROL_FIELD_SIZE) {
+            Some(&[low, high]) => u16::from_le_bytes([low, high]),
+            _ => return Err(crate::parser::Error::Truncated),
+        };
+
+        let version = FrameVersion::from_byte(
+            frame_kind,
+            ((data & mask::VERSION) >> offset::VERSION) as u8,
+        )?;
+
+        Ok(MultipurposeControlField {
+            long_frame_control: true,
+            pan_id_present: data & mask::PAN_ID_PRESENT != 0,
+            security_enabled: data & mask::SECURITY_ENABLED != 0,
+            seq_no_present: data & mask::SEQ_NO_SUPPRESSION == 0,
+            frame_pending: data & mask::FRAME_PENDING != 0,
+            ack_required: data & mask::ACK_REQUIRED != 0,
+            ie_present: data & mask::IE_PRESENT != 0,
+            version,
+            dst_addressing_mode,
+            src_addressing_mode,
+        })
+    }
+
+    /// Returns whether the long form is needed to encode the field.
+    pub const fn is_long(&self) -> bool {
+        self.long_frame_control
+            || self.pan_id_present
+            || self.security_enabled
+            || !self.seq_no_present
+            || self.frame_pending
+            || self.ack_required
+            || self.ie_present
+    }
+
+    /// Size in bytes of the encoded field.
+    pub const fn size(&self) -> usize {
+        match self.is_long() {
+            true => MULTIPURPOSE_LONG_CONTROL_FIELD_SIZE,
+            false => MULTIPURPOSE_SHORT_CONTROL_FIELD_SIZE,
+        }
+    }
+
+    /// Returns the bits of the field in the form given by
+    /// [`MultipurposeControlField::is_long`], the inverse of
+    /// [`MultipurposeControlField::from_bytes`]. Only the low byte is
+    /// meaningful for the short form.
+    ///
+    /// # Errors
+    ///
+    /// If the frame version is not valid for multipurpose frames, returns
+    /// `InvalidHeader`.
+    pub fn bits(&self) -> Result<u16, crate::composer::Error> {
+        use multipurpose_mask as mask;
+        use multipurpose_offset as offset;
+
+        let version = self.version.bits(FrameKind::MultiPurpose)? as u16;
+
+        let first = (((FrameKind::MultiPurpose.bits() as u16) << offset::FRAME_KIND)
+            & mask::FRAME_KIND)
+            | (((self.dst_addressing_mode.bits() as u16) << offset::DST_ADDRESSING_MODE)
+                & mask::DST_ADDRESSING_MODE)
+            | (((self.src_addressing_mode.bits() as u16) << offset::SRC_ADDRESSING_MODE)
+                & mask::SRC_ADDRESSING_MODE);
+
+        if !self.is_long() {
+            return Ok(first);
+        }
+
+        Ok(first
+            | mask::LONG_FRAME_CONTROL
+            | ((self.pan_id_present as u16) << offset::PAN_ID_PRESENT)
+            | ((self.security_enabled as u16) << offset::SECURITY_ENABLED)
+            | ((!self.seq_no_present as u16) << offset::SEQ_NO_SUPPRESSION)
+            | ((self.frame_pending as u16) << offset::FRAME_PENDING)
+            | ((version << offset::VERSION) & mask::VERSION)
+            | ((self.ack_required as u16) << offset::ACK_REQUIRED)
+            | ((self.ie_present as u16) << offset::IE_PRESENT))
+    }
+}
+
+/// First octet of the frame control field of fragment and Frak frames, whose
+/// layout is not decoded.
+/// Chapter 7.2.2.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentControl(u8);
+
+impl FragmentControl {
+    pub const fn from_byte(value: u8) -> Self {
+        FragmentControl(value)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
 }
 
 const BEACON_VALUE: u8 = 0x0;
 const DATA_VALUE: u8 = 0x1;
 const ACKNOWLEDGMENT_VALUE: u8 = 0x2;
 const MAC_COMMAND_VALUE: u8 = 0x3;
+const RESERVED_VALUE: u8 = 0x4;
 const MULTIPURPOSE_VALUE: u8 = 0x5;
 const FRAK_VALUE: u8 = 0x6;
 const EXTENDED_VALUE: u8 = 0x7;
@@ -22,7 +494,7 @@ const EXTENDED_VALUE: u8 = 0x7;
 /// Different frame type for the first 3 bits of the frame control.
 /// Chapter 7.2.2.1
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameKind {
     Beacon,
     Data,
@@ -31,6 +503,8 @@ pub enum FrameKind {
     MultiPurpose,
     Frak,
     Extended,
+    /// Reserved value, only produced in lenient mode
+    Reserved(u8),
 }
 
 impl FrameKind {
@@ -43,6 +517,7 @@ impl FrameKind {
             FrameKind::MultiPurpose => MULTIPURPOSE_VALUE,
             FrameKind::Frak => FRAK_VALUE,
             FrameKind::Extended => EXTENDED_VALUE,
+            FrameKind::Reserved(value) => *value,
         }
     }
 
@@ -55,7 +530,19 @@ impl FrameKind {
             MULTIPURPOSE_VALUE => Ok(FrameKind::MultiPurpose),
             FRAK_VALUE => Ok(FrameKind::Frak),
             EXTENDED_VALUE => Ok(FrameKind::Extended),
-            _ => Err(crate::parser::Error::InvalidHeader),
+            _ => Err(crate::parser::Error::InvalidFrameKind(value)),
+        }
+    }
+
+    /// Same as [`FrameKind::from_byte`], the reserved value being returned as
+    /// [`FrameKind::Reserved`] in lenient mode.
+    pub const fn from_byte_with_mode(
+        value: u8,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match (value, mode) {
+            (RESERVED_VALUE, ParseMode::Lenient) => Ok(FrameKind::Reserved(value)),
+            _ => Self::from_byte(value),
         }
     }
 }
@@ -77,16 +564,20 @@ impl TryFrom<u8> for FrameKind {
 /// Frame version. field not present for fragment frame and extended frame.
 /// Chapter 7.2.2.10
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameVersion {
     Ieee802154_2003,
     Ieee802154_2006,
     Ieee802154,
+    /// Reserved value, only produced in lenient mode and refused by the
+    /// composer
+    Reserved(u8),
 }
 
 const IEEE_2003_STD_VALUE: u8 = 0x0;
 const IEEE_2006_STD_VALUE: u8 = 0x1;
 const IEEE_STD_VALUE: u8 = 0x02;
+const IEEE_RESERVED_STD_VALUE: u8 = 0x03;
 const IEEE_MULTIPURPOSE_VALUE: u8 = 0x0;
 
 impl FrameVersion {
@@ -95,14 +586,14 @@ impl FrameVersion {
             IEEE_2003_STD_VALUE => Ok(FrameVersion::Ieee802154_2003),
             IEEE_2006_STD_VALUE => Ok(FrameVersion::Ieee802154_2006),
             IEEE_STD_VALUE => Ok(FrameVersion::Ieee802154),
-            _ => Err(crate::parser::Error::InvalidHeader),
+            _ => Err(crate::parser::Error::InvalidFrameVersion(value)),
         }
     }
 
     const fn from_byte_multipurpose(value: u8) -> Result<Self, crate::parser::Error> {
         match value {
             IEEE_MULTIPURPOSE_VALUE => Ok(FrameVersion::Ieee802154),
-            _ => Err(crate::parser::Error::InvalidHeader),
+            _ => Err(crate::parser::Error::InvalidFrameVersion(value)),
         }
     }
 
@@ -113,8 +604,8 @@ impl FrameVersion {
     ///
     /// # Error
     ///
-    /// If the value is invalid or the frame type does not have a version
-    /// field, returns `InvalidHeader`.
+    /// If the value is invalid, returns `InvalidFrameVersion`. If the frame
+    /// type does not have a version field, returns `InvalidFrameKind`.
     pub const fn from_byte(kind: FrameKind, value: u8) -> Result<Self, crate::parser::Error> {
         match kind {
             FrameKind::Beacon
@@ -122,15 +613,40 @@ impl FrameVersion {
             | FrameKind::Acknowledgment
             | FrameKind::MacCommand => Self::from_byte_std(value),
             FrameKind::MultiPurpose => Self::from_byte_multipurpose(value),
-            _ => Err(crate::parser::Error::InvalidHeader),
+            kind => Err(crate::parser::Error::InvalidFrameKind(kind.bits())),
         }
     }
 
-    const fn bits_std(&self) -> u8 {
+    /// Same as [`FrameVersion::from_byte`]. In lenient mode, the reserved
+    /// value is returned as [`FrameVersion::Reserved`], and the version of a
+    /// reserved frame type is decoded as for the standard layout.
+    pub const fn from_byte_with_mode(
+        kind: FrameKind,
+        value: u8,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match (kind, mode) {
+            (
+                FrameKind::Beacon
+                | FrameKind::Data
+                | FrameKind::Acknowledgment
+                | FrameKind::MacCommand
+                | FrameKind::Reserved(_),
+                ParseMode::Lenient,
+            ) => match value {
+                IEEE_RESERVED_STD_VALUE => Ok(FrameVersion::Reserved(value)),
+                _ => Self::from_byte_std(value),
+            },
+            (kind, _) => Self::from_byte(kind, value),
+        }
+    }
+
+    const fn bits_std(&self) -> Result<u8, crate::composer::Error> {
         match self {
-            FrameVersion::Ieee802154_2003 => IEEE_2003_STD_VALUE,
-            FrameVersion::Ieee802154_2006 => IEEE_2006_STD_VALUE,
-            FrameVersion::Ieee802154 => IEEE_STD_VALUE,
+            FrameVersion::Ieee802154_2003 => Ok(IEEE_2003_STD_VALUE),
+            FrameVersion::Ieee802154_2006 => Ok(IEEE_2006_STD_VALUE),
+            FrameVersion::Ieee802154 => Ok(IEEE_STD_VALUE),
+            FrameVersion::Reserved(_) => Err(crate::composer::Error::InvalidHeader),
         }
     }
 
@@ -152,7 +668,7 @@ impl FrameVersion {
             FrameKind::Beacon
             | FrameKind::Data
             | FrameKind::Acknowledgment
-            | FrameKind::MacCommand => Ok(self.bits_std()),
+            | FrameKind::MacCommand => self.bits_std(),
             FrameKind::MultiPurpose => self.bits_multipurpose(),
             _ => Err(crate::composer::Error::InvalidHeader),
         }
@@ -193,15 +709,15 @@ mod tests {
     fn frame_kind_from_byte_invalid_values() {
         assert!(matches!(
             FrameKind::from_byte(0xFF),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameKind(0xFF))
         ));
         assert!(matches!(
             FrameKind::from_byte(0x8),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameKind(0x8))
         ));
         assert!(matches!(
             FrameKind::from_byte(0xA),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameKind(0xA))
         ));
     }
 
@@ -233,23 +749,25 @@ mod tests {
     fn frame_version_from_byte_invalid_values() {
         assert!(matches!(
             FrameVersion::from_byte(FrameKind::Frak, IEEE_2003_STD_VALUE),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameKind(FRAK_VALUE))
         ));
         assert!(matches!(
             FrameVersion::from_byte(FrameKind::Extended, IEEE_STD_VALUE),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameKind(EXTENDED_VALUE))
         ));
         assert!(matches!(
             FrameVersion::from_byte(FrameKind::MultiPurpose, IEEE_STD_VALUE),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameVersion(IEEE_STD_VALUE))
         ));
         assert!(matches!(
             FrameVersion::from_byte(FrameKind::MultiPurpose, IEEE_2006_STD_VALUE),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameVersion(
+                IEEE_2006_STD_VALUE
+            ))
         ));
         assert!(matches!(
             FrameVersion::from_byte(FrameKind::Beacon, 0x4),
-            Err(crate::parser::Error::InvalidHeader)
+            Err(crate::parser::Error::InvalidFrameVersion(0x4))
         ));
     }
 
@@ -283,8 +801,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reserved_values_from_byte_with_mode() {
+        assert!(matches!(
+            FrameKind::from_byte_with_mode(RESERVED_VALUE, ParseMode::Strict),
+            Err(crate::parser::Error::InvalidFrameKind(RESERVED_VALUE))
+        ));
+        assert_eq!(
+            FrameKind::from_byte_with_mode(RESERVED_VALUE, ParseMode::Lenient),
+            Ok(FrameKind::Reserved(RESERVED_VALUE))
+        );
+        assert!(matches!(
+            FrameVersion::from_byte_with_mode(
+                FrameKind::Data,
+                IEEE_RESERVED_STD_VALUE,
+                ParseMode::Strict
+            ),
+            Err(crate::parser::Error::InvalidFrameVersion(
+                IEEE_RESERVED_STD_VALUE
+            ))
+        ));
+        assert_eq!(
+            FrameVersion::from_byte_with_mode(
+                FrameKind::Data,
+                IEEE_RESERVED_STD_VALUE,
+                ParseMode::Lenient
+            ),
+            Ok(FrameVersion::Reserved(IEEE_RESERVED_STD_VALUE))
+        );
+    }
+
     #[test]
     fn frame_version_bits_invalid_values() {
+        assert!(matches!(
+            FrameVersion::Reserved(IEEE_RESERVED_STD_VALUE).bits(FrameKind::Data),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
         assert!(matches!(
             FrameVersion::Ieee802154.bits(FrameKind::Frak),
             Err(crate::composer::Error::InvalidHeader)
@@ -302,4 +854,202 @@ mod tests {
             Err(crate::composer::Error::InvalidHeader)
         ));
     }
+
+    #[test]
+    fn standard_control_field_bits_round_trip() {
+        let mut decoded = 0;
+
+        // Bit 7 is reserved, and ignored by the decoder
+        for word in (0..=u16::MAX).filter(|word| word & 0x80 == 0) {
+            let bytes = word.to_le_bytes();
+
+            if let Ok(control) = StandardControlField::from_bytes(&bytes) {
+                assert_eq!(control.bits().unwrap(), bytes);
+                decoded += 1;
+            }
+        }
+
+        // 4 frame types and 3 frame versions
+        assert_eq!(decoded, 4 * 3 * (1 << 10));
+    }
+
+    #[test]
+    fn standard_control_field_bits_invalid() {
+        let mut control = StandardControlField::from_bytes(&[0x41, 0x88]).unwrap();
+
+        control.version = FrameVersion::Reserved(IEEE_RESERVED_STD_VALUE);
+        assert!(matches!(
+            control.bits(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        control.version = FrameVersion::Ieee802154;
+        control.frame_kind = FrameKind::MultiPurpose;
+        assert!(matches!(
+            control.bits(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn standard_control_field_builder() {
+        let control = StandardControlField::new(FrameKind::Data, FrameVersion::Ieee802154)
+            .with_ack_required(true)
+            .with_security(true)
+            .with_frame_pending(true)
+            .with_pan_id_compression(true)
+            .with_seq_no_suppressed(true)
+            .with_ie_present(true);
+
+        assert_eq!(control.frame_kind(), FrameKind::Data);
+        assert_eq!(control.version(), FrameVersion::Ieee802154);
+        assert!(control.ack_required());
+        assert!(control.security_enabled());
+        assert!(control.frame_pending());
+        assert!(control.pan_id_compression());
+        assert!(!control.seq_no_present());
+        assert!(control.ie_present());
+        assert!(control.is_valid());
+        assert_eq!(control.bits().unwrap(), [0x79, 0x23]);
+
+        let control = StandardControlField::new(FrameKind::Beacon, FrameVersion::Ieee802154_2003);
+        assert_eq!(control.bits().unwrap(), [0x00, 0x00]);
+        assert_eq!(
+            StandardControlField::from_bytes(&[0x00, 0x00]).unwrap(),
+            control
+        );
+
+        assert!(!StandardControlField::new(FrameKind::Frak, FrameVersion::Ieee802154).is_valid());
+        assert!(!StandardControlField::new(
+            FrameKind::Data,
+            FrameVersion::Reserved(IEEE_RESERVED_STD_VALUE)
+        )
+        .is_valid());
+    }
+
+    #[test]
+    fn standard_control_field_validate() {
+        let data = StandardControlField::new(FrameKind::Data, FrameVersion::Ieee802154_2006);
+        assert!(data.validate().is_ok());
+        assert!(data
+            .clone()
+            .with_ack_required(true)
+            .with_security(true)
+            .is_valid());
+
+        for kind in [FrameKind::Beacon, FrameKind::Acknowledgment] {
+            let control = StandardControlField::new(kind, FrameVersion::Ieee802154);
+            assert!(control.validate().is_ok());
+            assert!(matches!(
+                control.with_ack_required(true).validate(),
+                Err(crate::composer::Error::InvalidHeader)
+            ));
+        }
+
+        let command = StandardControlField::new(FrameKind::MacCommand, FrameVersion::Ieee802154);
+        assert!(data.clone().with_frame_pending(true).is_valid());
+        assert!(matches!(
+            command.with_frame_pending(true).validate(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+
+        for version in [FrameVersion::Ieee802154_2003, FrameVersion::Ieee802154_2006] {
+            let control = StandardControlField::new(FrameKind::Data, version);
+            assert!(matches!(
+                control.clone().with_seq_no_suppressed(true).validate(),
+                Err(crate::composer::Error::InvalidHeader)
+            ));
+            assert!(matches!(
+                control.with_ie_present(true).validate(),
+                Err(crate::composer::Error::InvalidHeader)
+            ));
+        }
+
+        let control = StandardControlField::new(FrameKind::Data, FrameVersion::Ieee802154)
+            .with_seq_no_suppressed(true)
+            .with_ie_present(true);
+        assert!(control.validate().is_ok());
+
+        assert!(matches!(
+            StandardControlField::new(FrameKind::MultiPurpose, FrameVersion::Ieee802154).validate(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn multipurpose_control_field_bits_round_trip() {
+        let mut decoded = 0;
+
+        for word in (0..=u16::MAX).filter(|word| word & 0x7 == 0x5) {
+            let bytes = word.to_le_bytes();
+
+            let Ok(control) = MultipurposeControlField::from_bytes(&bytes) else {
+                continue;
+            };
+            decoded += 1;
+
+            match control.is_long() {
+                true => assert_eq!(control.bits().unwrap(), word),
+                false => {
+                    assert_eq!(control.size(), 1);
+                    assert_eq!(control.bits().unwrap(), word & 0xFF);
+                }
+            }
+
+            let short = MultipurposeControlField::from_bytes(&bytes[..1]);
+            assert_eq!(short.is_ok(), word & 0x8 == 0);
+        }
+
+        // 3 addressing modes each, long form with its 6 flags and version 0,
+        // or short form whatever the high byte
+        assert_eq!(decoded, 3 * 3 * ((1 << 6) + (1 << 8)));
+    }
+
+    #[test]
+    fn multipurpose_control_field_short_form() {
+        // Short source and long destination addresses
+        let control = MultipurposeControlField::from_bytes(&[0xB5]).unwrap();
+
+        assert!(!control.is_long());
+        assert!(control.seq_no_present);
+        assert_eq!(control.version, FrameVersion::Ieee802154);
+        assert_eq!(control.dst_addressing_mode, AddressingMode::Long);
+        assert_eq!(control.src_addressing_mode, AddressingMode::Short);
+        assert_eq!(control.bits().unwrap(), 0xB5);
+
+        // Long form forced without any long form field set
+        let control = MultipurposeControlField::from_bytes(&[0xBD, 0x00]).unwrap();
+        assert!(control.is_long());
+        assert_eq!(control.bits().unwrap(), 0x00BD);
+    }
+
+    #[test]
+    fn multipurpose_control_field_reserved_values() {
+        // Reserved destination then source addressing mode
+        assert!(matches!(
+            MultipurposeControlField::from_bytes(&[0x15]),
+            Err(crate::parser::Error::InvalidAddressingMode(0x1))
+        ));
+        assert!(matches!(
+            MultipurposeControlField::from_bytes(&[0x45]),
+            Err(crate::parser::Error::InvalidAddressingMode(0x1))
+        ));
+        // Frame version other than 0
+        assert!(matches!(
+            MultipurposeControlField::from_bytes(&[0x0D, 0x10]),
+            Err(crate::parser::Error::InvalidFrameVersion(0x1))
+        ));
+        // Not a multipurpose frame
+        assert!(matches!(
+            MultipurposeControlField::from_bytes(&[0x41, 0x88]),
+            Err(crate::parser::Error::InvalidFrameKind(0x1))
+        ));
+
+        let mut control = MultipurposeControlField::from_bytes(&[0x05]).unwrap();
+        control.version = FrameVersion::Ieee802154_2006;
+        assert!(matches!(
+            control.bits(),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
 }
@@ -0,0 +1,190 @@
+/// Size in bytes of the 16-bit FCS.
+/// Chapter 7.2.10
+pub const FCS_16_SIZE: usize = 2;
+/// Size in bytes of the 32-bit FCS, used by some SUN PHYs.
+/// Chapter 7.2.10
+pub const FCS_32_SIZE: usize = 4;
+
+/// ITU-T CRC-16 over `data`, bits being processed LSB first with a zero
+/// initial remainder.
+/// Chapter 7.2.10.1
+pub const fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    let mut i = 0;
+
+    while i < data.len() {
+        crc ^= data[i] as u16;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 0x1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ 0x8408,
+            };
+            bit += 1;
+        }
+
+        i += 1;
+    }
+
+    crc
+}
+
+/// ANSI X3.66 CRC-32 over `data`.
+/// Chapter 7.2.10.2
+pub const fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    let mut i = 0;
+
+    while i < data.len() {
+        crc ^= data[i] as u32;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 0x1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ 0xEDB8_8320,
+            };
+            bit += 1;
+        }
+
+        i += 1;
+    }
+
+    !crc
+}
+
+/// Whether frames end with an FCS, and which one.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FcsPolicy {
+    /// No FCS, it was already checked and stripped by the radio
+    #[default]
+    Absent,
+    /// [`crc16`], little endian
+    Crc16,
+    /// [`crc32`], little endian
+    Crc32,
+}
+
+impl FcsPolicy {
+    /// Size in bytes of the FCS.
+    pub const fn size(&self) -> usize {
+        match self {
+            FcsPolicy::Absent => 0,
+            FcsPolicy::Crc16 => FCS_16_SIZE,
+            FcsPolicy::Crc32 => FCS_32_SIZE,
+        }
+    }
+
+    /// Checks the FCS at the end of `frame`, and returns the frame without
+    /// it.
+    ///
+    /// # Error
+    ///
+    /// If `frame` is shorter than the FCS, returns `Truncated`. If the FCS
+    /// does not match the frame, returns `InvalidFcs`.
+    pub fn check<'a>(&self, frame: &'a [u8]) -> Result<&'a [u8], crate::parser::Error> {
+        let (data, fcs) = frame
+            .split_at_checked(frame.len().wrapping_sub(self.size()))
+            .ok_or(crate::parser::Error::Truncated)?;
+
+        match fcs == &self.compute(data)[..self.size()] {
+            true => Ok(data),
+            false => Err(crate::parser::Error::InvalidFcs),
+        }
+    }
+
+    /// Appends the FCS over `buf[..len]` to it, and returns the length of
+    /// the frame with its FCS.
+    ///
+    /// # Error
+    ///
+    /// If `buf` cannot hold the FCS after `len` bytes, returns
+    /// `BufferTooSmall`.
+    pub fn append(&self, buf: &mut [u8], len: usize) -> Result<usize, crate::composer::Error> {
+        let needed = len + self.size();
+
+        if buf.len() < needed {
+            return Err(crate::composer::Error::BufferTooSmall { needed });
+        }
+
+        let fcs = self.compute(&buf[..len]);
+        buf[len..needed].copy_from_slice(&fcs[..self.size()]);
+
+        Ok(needed)
+    }
+
+    /// FCS over `data`, little endian, in the first [`FcsPolicy::size`]
+    /// bytes.
+    const fn compute(&self, data: &[u8]) -> [u8; FCS_32_SIZE] {
+        match self {
+            FcsPolicy::Absent => [0; FCS_32_SIZE],
+            FcsPolicy::Crc16 => {
+                let [low, high] = crc16(data).to_le_bytes();
+
+                [low, high, 0, 0]
+            }
+            FcsPolicy::Crc32 => crc32(data).to_le_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc16, crc32, FcsPolicy};
+
+    #[test]
+    fn check_values() {
+        assert_eq!(crc16(b"123456789"), 0x2189);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc16(&[]), 0x0000);
+    }
+
+    #[test]
+    fn check_imm_ack() {
+        let frame: [u8; 5] = [0x02, 0x00, 0x2A, 0xE0, 0x3B];
+
+        assert_eq!(crc16(&frame[..3]), 0x3BE0);
+        assert_eq!(FcsPolicy::Crc16.check(&frame), Ok(&frame[..3]));
+        assert_eq!(
+            FcsPolicy::Crc16.check(&[0x02, 0x00, 0x2A, 0xE0, 0x3C]),
+            Err(crate::parser::Error::InvalidFcs)
+        );
+    }
+
+    #[test]
+    fn check_crc32() {
+        let mut frame = [0x02, 0x00, 0x2A, 0x00, 0x00, 0x00, 0x00];
+        let fcs = crc32(&frame[..3]).to_le_bytes();
+        frame[3..].copy_from_slice(&fcs);
+
+        assert_eq!(FcsPolicy::Crc32.check(&frame), Ok(&frame[..3]));
+        assert_eq!(
+            FcsPolicy::Crc16.check(&frame),
+            Err(crate::parser::Error::InvalidFcs)
+        );
+    }
+
+    #[test]
+    fn append_imm_ack() {
+        let mut buf = [0x02, 0x00, 0x2A, 0x00, 0x00];
+
+        assert_eq!(FcsPolicy::Crc16.append(&mut buf, 3).unwrap(), 5);
+        assert_eq!(buf, [0x02, 0x00, 0x2A, 0xE0, 0x3B]);
+        assert_eq!(FcsPolicy::Absent.append(&mut buf, 3).unwrap(), 3);
+        assert!(matches!(
+            FcsPolicy::Crc32.append(&mut buf, 3),
+            Err(crate::composer::Error::BufferTooSmall { needed: 7 })
+        ));
+    }
+
+    #[test]
+    fn check_truncated() {
+        assert_eq!(
+            FcsPolicy::Crc16.check(&[0x02]),
+            Err(crate::parser::Error::Truncated)
+        );
+        assert_eq!(FcsPolicy::Absent.check(&[]), Ok(&[][..]));
+    }
+}
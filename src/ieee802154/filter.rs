@@ -0,0 +1,328 @@
+use super::{
+    address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+    frame::Frame,
+};
+
+/// Third level of filtering of received frames, applied from the MAC PIB of
+/// the device.
+/// Chapter 6.7.2
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Filter {
+    /// macPanId
+    pub pan_id: PanId,
+    /// macShortAddress, `None` if the device has none assigned
+    pub short_addr: Option<ShortAddress>,
+    /// macExtendedAddress
+    pub long_addr: Option<LongAddress>,
+    pub is_pan_coordinator: bool,
+    /// macPromiscuousMode, every frame being accepted when set
+    pub promiscuous: bool,
+}
+
+impl Filter {
+    pub const fn new(pan_id: PanId) -> Self {
+        Filter {
+            pan_id,
+            short_addr: None,
+            long_addr: None,
+            is_pan_coordinator: false,
+            promiscuous: false,
+        }
+    }
+
+    /// Returns whether `frame` is addressed to the device:
+    /// - Frames of a reserved type, fragments and extended frames are
+    ///   dropped.
+    /// - The destination PAN ID is either broadcast or macPanId.
+    /// - The destination address is either broadcast or an address of the
+    ///   device.
+    /// - The source PAN ID of beacons is macPanId, unless macPanId is
+    ///   broadcast.
+    /// - Data, MAC command and multipurpose frames without destination
+    ///   address are only accepted by the PAN coordinator, from its PAN.
+    ///
+    /// Acknowledgments without destination address are accepted, being
+    /// matched on their sequence number instead.
+    pub fn accepts(&self, frame: &Frame) -> bool {
+        if self.promiscuous {
+            return true;
+        }
+
+        let (dst, source_only) = match frame {
+            Frame::Beacon(beacon) => {
+                return self.accepts_beacon(&beacon.header.dst_addr, &beacon.header.src_addr)
+            }
+            Frame::EnhBeacon(beacon) => {
+                return self.accepts_beacon(&beacon.header.dst_addr, &beacon.header.src_addr)
+            }
+            Frame::Acknowledgment(_) => return true,
+            Frame::EnhAcknowledgment(ack) => {
+                return ack.header.dst_addr.is_absent() || self.accepts_dst(&ack.header.dst_addr)
+            }
+            Frame::Data(data) => (&data.header.dst_addr, data.header.source_only()),
+            Frame::MacCommand(command) => (&command.header.dst_addr, command.header.source_only()),
+            Frame::MultiPurpose(frame) => (&frame.dst_addr, frame.source_only()),
+            Frame::Frak | Frame::Extended | Frame::Reserved(_) => return false,
+        };
+
+        match (dst, source_only) {
+            (AddressField::Absent, Some(src)) => {
+                self.is_pan_coordinator && src.pan_id() == self.pan_id
+            }
+            (AddressField::Absent, None) => false,
+            (dst, _) => self.accepts_dst(dst),
+        }
+    }
+
+    fn accepts_beacon(&self, dst: &AddressField, src: &Option<AddressKind>) -> bool {
+        let src_matches = match src {
+            Some(src) => self.pan_id.is_broadcast() || src.pan_id() == self.pan_id,
+            None => true,
+        };
+
+        src_matches && (dst.is_absent() || self.accepts_dst(dst))
+    }
+
+    /// Checks the destination PAN ID, then the destination address if any.
+    fn accepts_dst(&self, dst: &AddressField) -> bool {
+        let pan_matches = dst
+            .pan_id()
+            .is_some_and(|pan_id| pan_id.is_broadcast() || pan_id == self.pan_id);
+        if !pan_matches {
+            return false;
+        }
+
+        match dst.address() {
+            None => true,
+            Some(AddressKind::Short(address)) => {
+                address.is_broadcast()
+                    || self
+                        .short_addr
+                        .is_some_and(|own| own.value() == address.value())
+            }
+            Some(AddressKind::Long(address)) => self
+                .long_addr
+                .is_some_and(|own| own.value() == address.value()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ieee802154::{
+            address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+            control_field::{FrameKind, FrameVersion, StandardControlField},
+            frame::{
+                ack::AckFrame, beacon_builder::BeaconBuilder, command::CommandId, data::DataFrame,
+                Frame, MacHeader,
+            },
+            ie::{header::HeaderIeIterator, payload::PayloadIeIterator},
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    use super::Filter;
+
+    const PAN: PanId = PanId::new(0x1234);
+    const OTHER_PAN: PanId = PanId::new(0x4321);
+    const SHORT: u16 = 0x0001;
+    const LONG: u64 = 0x0102_0304_0506_0708;
+
+    fn filter(is_pan_coordinator: bool, promiscuous: bool) -> Filter {
+        Filter {
+            short_addr: Some(ShortAddress::new(PAN, SHORT)),
+            long_addr: Some(LongAddress::new(PAN, LONG)),
+            is_pan_coordinator,
+            promiscuous,
+            ..Filter::new(PAN)
+        }
+    }
+
+    fn data_frame(dst: impl Into<AddressField>, src: Option<AddressKind>) -> Frame<'static> {
+        Frame::Data(DataFrame {
+            header: MacHeader {
+                control: StandardControlField::new(FrameKind::Data, FrameVersion::Ieee802154_2006)
+                    .into(),
+                seq_no: Some(1),
+                dst_addr: dst.into(),
+                src_addr: src,
+                aux: None,
+                header_ies: HeaderIeIterator::new(&[]),
+            },
+            payload_ies: PayloadIeIterator::new(&[]),
+            payload: &[],
+            mic: &[],
+        })
+    }
+
+    fn short(pan: PanId, address: u16) -> Option<AddressKind> {
+        Some(ShortAddress::new(pan, address).into())
+    }
+
+    fn long(pan: PanId, address: u64) -> Option<AddressKind> {
+        Some(LongAddress::new(pan, address).into())
+    }
+
+    #[test]
+    fn destination_matrix() {
+        let src = short(PAN, 0x0002);
+        // (destination, accepted when not promiscuous)
+        let cases = [
+            (short(PAN, SHORT), true),
+            (short(PAN, 0xFFFF), true),
+            (short(PanId::BROADCAST, SHORT), true),
+            (short(PanId::BROADCAST, 0xFFFF), true),
+            (short(PAN, 0x0003), false),
+            (short(PAN, 0xFFFE), false),
+            (short(OTHER_PAN, SHORT), false),
+            (short(OTHER_PAN, 0xFFFF), false),
+            (long(PAN, LONG), true),
+            (long(PanId::BROADCAST, LONG), true),
+            (long(PAN, LONG + 1), false),
+            (long(PAN, 0xFFFF_FFFF_FFFF_FFFF), false),
+            (long(OTHER_PAN, LONG), false),
+        ];
+
+        for (dst, accepted) in cases {
+            let frame = data_frame(dst, src);
+            for is_pan_coordinator in [false, true] {
+                assert_eq!(
+                    filter(is_pan_coordinator, false).accepts(&frame),
+                    accepted,
+                    "{:?}, coordinator: {}",
+                    dst,
+                    is_pan_coordinator
+                );
+                assert!(filter(is_pan_coordinator, true).accepts(&frame));
+            }
+        }
+    }
+
+    #[test]
+    fn no_destination() {
+        // (source, accepted by the PAN coordinator)
+        let cases = [
+            (short(PAN, 0x0002), true),
+            (long(PAN, LONG + 1), true),
+            (short(OTHER_PAN, 0x0002), false),
+            (short(PanId::BROADCAST, 0x0002), false),
+            (None, false),
+        ];
+
+        for (src, accepted) in cases {
+            let frame = data_frame(None, src);
+            assert!(!filter(false, false).accepts(&frame), "{:?}", src);
+            assert_eq!(filter(true, false).accepts(&frame), accepted, "{:?}", src);
+            assert!(filter(false, true).accepts(&frame));
+            assert!(filter(true, true).accepts(&frame));
+        }
+    }
+
+    #[test]
+    fn pan_id_only_destination() {
+        // (destination PAN ID, accepted when not promiscuous)
+        let cases = [(PAN, true), (PanId::BROADCAST, true), (OTHER_PAN, false)];
+
+        for (pan, accepted) in cases {
+            let frame = data_frame(AddressField::PanOnly(pan), None);
+            for is_pan_coordinator in [false, true] {
+                assert_eq!(
+                    filter(is_pan_coordinator, false).accepts(&frame),
+                    accepted,
+                    "{:?}",
+                    pan
+                );
+                assert!(filter(is_pan_coordinator, true).accepts(&frame));
+            }
+        }
+    }
+
+    #[test]
+    fn unassigned_addresses() {
+        let filter = Filter::new(PAN);
+
+        assert!(filter.accepts(&data_frame(short(PAN, 0xFFFF), None)));
+        assert!(!filter.accepts(&data_frame(short(PAN, SHORT), None)));
+        assert!(!filter.accepts(&data_frame(long(PAN, LONG), None)));
+    }
+
+    #[test]
+    fn beacon_source_pan() {
+        let beacon = |pan: PanId| {
+            let beacon = BeaconBuilder::new()
+                .src(ShortAddress::new(pan, 0x0000))
+                .build()
+                .unwrap();
+            Frame::Beacon(beacon)
+        };
+
+        for is_pan_coordinator in [false, true] {
+            let own = filter(is_pan_coordinator, false);
+            assert!(own.accepts(&beacon(PAN)));
+            assert!(!own.accepts(&beacon(OTHER_PAN)));
+            assert!(filter(is_pan_coordinator, true).accepts(&beacon(OTHER_PAN)));
+
+            // Not associated yet, every beacon is accepted
+            let scanning = Filter {
+                is_pan_coordinator,
+                ..Filter::new(PanId::BROADCAST)
+            };
+            assert!(scanning.accepts(&beacon(PAN)));
+            assert!(scanning.accepts(&beacon(OTHER_PAN)));
+        }
+    }
+
+    #[test]
+    fn other_frame_kinds() {
+        let ack = Frame::Acknowledgment(AckFrame {
+            version: FrameVersion::Ieee802154_2006,
+            frame_pending: false,
+            seq_no: 1,
+        });
+
+        for is_pan_coordinator in [false, true] {
+            assert!(filter(is_pan_coordinator, false).accepts(&ack));
+            assert!(!filter(is_pan_coordinator, false).accepts(&Frame::Frak));
+            assert!(!filter(is_pan_coordinator, false).accepts(&Frame::Extended));
+            assert!(filter(is_pan_coordinator, true).accepts(&Frame::Extended));
+        }
+    }
+
+    #[test]
+    fn orphan_notification() {
+        // Orphan notification without destination, from 01:02:03:04:05:06:07:08
+        // in PAN 0x1234
+        let input: [u8; 14] = [
+            0x03, 0xD0, 0x2A, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x06,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(command.command_id, CommandId::OrphanNotification);
+        assert_eq!(command.header.source_only(), long(PAN, LONG).as_ref());
+
+        let device = Filter::new(PAN);
+        let coordinator = Filter {
+            is_pan_coordinator: true,
+            ..device
+        };
+
+        assert!(!device.accepts(&frame));
+        assert!(coordinator.accepts(&frame));
+        assert!(!Filter {
+            pan_id: OTHER_PAN,
+            ..coordinator
+        }
+        .accepts(&frame));
+        assert!(!Filter {
+            pan_id: PanId::BROADCAST,
+            ..coordinator
+        }
+        .accepts(&frame));
+    }
+}
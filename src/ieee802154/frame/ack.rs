@@ -0,0 +1,86 @@
+use crate::ieee802154::control_field::{mask, offset, FrameKind, FrameVersion};
+
+/// Size in bytes of an immediate acknowledgment frame, without FCS
+pub const IMM_ACK_SIZE: usize = 3;
+
+/// Immediate acknowledgment frame, sent by frame versions older than 2015.
+/// Chapter 7.3.3
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AckFrame {
+    pub version: FrameVersion,
+    pub frame_pending: bool,
+    /// Sequence number of the acknowledged frame
+    pub seq_no: u8,
+}
+
+impl AckFrame {
+    /// Writes the frame, FCS excluded, into `buf`. Does not allocate, so
+    /// that it can run right after the acknowledged frame is received.
+    /// Chapter 7.3.3
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidHeader` for version IEEE 802.15.4, which uses
+    /// enhanced acknowledgments instead.
+    pub fn compose_into(&self, buf: &mut [u8; IMM_ACK_SIZE]) -> Result<(), crate::composer::Error> {
+        let version = match self.version {
+            FrameVersion::Ieee802154 => return Err(crate::composer::Error::InvalidHeader),
+            ref version => version.bits(FrameKind::Acknowledgment)? as u16,
+        };
+
+        let control = (((FrameKind::Acknowledgment.bits() as u16) << offset::FRAME_KIND)
+            & mask::FRAME_KIND)
+            | (((self.frame_pending as u16) << offset::FRAME_PENDING) & mask::FRAME_PENDING)
+            | ((version << offset::VERSION) & mask::VERSION);
+        let [low, high] = control.to_le_bytes();
+
+        *buf = [low, high, self.seq_no];
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::Composer,
+        ieee802154::{control_field::FrameVersion, frame::Frame, Ieee802154},
+        parser::Parser,
+    };
+
+    use super::{AckFrame, IMM_ACK_SIZE};
+
+    #[test]
+    fn compose_into() {
+        let mut ack = AckFrame {
+            version: FrameVersion::Ieee802154_2003,
+            frame_pending: false,
+            seq_no: 0x2A,
+        };
+        let mut buf = [0u8; IMM_ACK_SIZE];
+
+        ack.compose_into(&mut buf).unwrap();
+        assert_eq!(buf, [0x02, 0x00, 0x2A]);
+
+        ack.frame_pending = true;
+        ack.compose_into(&mut buf).unwrap();
+        assert_eq!(buf, [0x12, 0x00, 0x2A]);
+
+        ack.version = FrameVersion::Ieee802154_2006;
+        ack.compose_into(&mut buf).unwrap();
+        assert_eq!(buf, [0x12, 0x10, 0x2A]);
+
+        ack.version = FrameVersion::Ieee802154;
+        assert!(ack.compose_into(&mut buf).is_err());
+    }
+
+    #[test]
+    fn compose_round_trip() {
+        let input: [u8; IMM_ACK_SIZE] = [0x12, 0x00, 0x7F];
+        let frame = Ieee802154::parse(&input).unwrap();
+
+        assert!(matches!(frame, Frame::Acknowledgment(_)));
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+}
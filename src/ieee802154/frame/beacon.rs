@@ -1,9 +1,16 @@
+use core::num::NonZeroU32;
+
+use byte::{ctx::Endian, BytesExt, TryRead, TryWrite};
+
 use crate::ieee802154::{
-    address::AddressKind, control_field::StandardControlField,
+    address::{AddressField, AddressKind},
+    ie::header::HeaderIeIterator,
     security_header::AuxiliarySecurityHeader,
 };
 
-use self::gts::Gts;
+use self::{gts::Gts, pending_address::PendingAddress};
+
+use super::{pan_descriptor::PanDescriptor, ControlField};
 
 mod offset {
     pub(crate) const BEACON_ORDER: usize = 0;
@@ -17,7 +24,7 @@ mod offset {
 mod mask {
     use super::offset;
 
-    pub(crate) const BEACON_ORDER: u16 = 0xF;
+    pub(crate) const BEACON_ORDER: u16 = 0xF << offset::BEACON_ORDER;
     pub(crate) const SUPER_FRAME_ORDER: u16 = 0xF << offset::SUPER_FRAME_ORDER;
     pub(crate) const FINAL_CAP_SLOT: u16 = 0xF << offset::FINAL_CAP_SLOT;
     pub(crate) const BLE: u16 = 0x1 << offset::BLE;
@@ -26,28 +33,48 @@ mod mask {
 }
 
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BeaconFrame<'a> {
-    pub header: BeaconHeader,
+    pub header: BeaconHeader<'a>,
     pub payload: BeaconPayload<'a>,
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
+
+impl<'a> BeaconFrame<'a> {
+    /// Summary of the beacon, as reported by a scan.
+    pub fn pan_descriptor(&self) -> PanDescriptor {
+        PanDescriptor::new(
+            &self.header.control,
+            self.header.src_addr,
+            Some(self.payload.super_frame),
+            self.payload.gts.permit,
+        )
+    }
 }
 
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
-pub struct BeaconHeader {
-    pub control: StandardControlField,
-    pub seq_no: u8,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconHeader<'a> {
+    /// Always [`ControlField::Standard`] for parsed frames
+    pub control: ControlField,
+    /// `None` if the sequence number is suppressed
+    pub seq_no: Option<u8>,
     pub src_addr: Option<AddressKind>,
-    pub dst_addr: Option<AddressKind>,
-    pub aux: AuxiliarySecurityHeader,
+    pub dst_addr: AddressField,
+    /// Present if `security_enabled` is set
+    pub aux: Option<AuxiliarySecurityHeader>,
+    /// Header IEs, empty if `ie_present` is not set
+    pub header_ies: HeaderIeIterator<'a>,
 }
 
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BeaconPayload<'a> {
     pub super_frame: SuperFrame,
     pub gts: Gts,
-    // TODO: add pending address
+    pub pending_address: PendingAddress,
     pub data: &'a [u8],
 }
 
@@ -57,7 +84,7 @@ pub const SUPER_FRAME_SIZE: usize = 2;
 /// Super frame structure
 /// Chapter 7.3.1.4
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SuperFrame {
     pub beacon_order: u8,
     pub super_frame_order: u8,
@@ -69,10 +96,12 @@ pub struct SuperFrame {
 
 impl SuperFrame {
     pub const fn from_bytes(data: &[u8; SUPER_FRAME_SIZE]) -> Self {
-        let data: u16 = u16::from_le_bytes(*data);
+        Self::from_bits(u16::from_le_bytes(*data))
+    }
 
+    const fn from_bits(data: u16) -> Self {
         SuperFrame {
-            beacon_order: (data & mask::BEACON_ORDER >> offset::BEACON_ORDER) as u8,
+            beacon_order: ((data & mask::BEACON_ORDER) >> offset::BEACON_ORDER) as u8,
             super_frame_order: ((data & mask::SUPER_FRAME_ORDER) >> offset::SUPER_FRAME_ORDER)
                 as u8,
             final_cap_slot: ((data & mask::FINAL_CAP_SLOT) >> offset::FINAL_CAP_SLOT) as u8,
@@ -83,16 +112,138 @@ impl SuperFrame {
         }
     }
 
-    pub const fn bytes(&self) -> [u8; 2] {
-        ((((self.beacon_order as u16) << offset::BEACON_ORDER) & mask::BEACON_ORDER)
+    pub const fn bytes(&self) -> [u8; SUPER_FRAME_SIZE] {
+        self.bits().to_le_bytes()
+    }
+
+    const fn bits(&self) -> u16 {
+        (((self.beacon_order as u16) << offset::BEACON_ORDER) & mask::BEACON_ORDER)
             | (((self.super_frame_order as u16) << offset::SUPER_FRAME_ORDER)
                 & mask::SUPER_FRAME_ORDER)
             | (((self.final_cap_slot as u16) << offset::FINAL_CAP_SLOT) & mask::FINAL_CAP_SLOT)
             | (((self.low_energy as u16) << offset::BLE) & mask::BLE)
             | (((self.pan_coordinator as u16) << offset::PAN_COORDINATOR) & mask::PAN_COORDINATOR)
             | (((self.association_permit as u16) << offset::ASSOCIATION_PERMIT)
-                & mask::ASSOCIATION_PERMIT))
-            .to_le_bytes()
+                & mask::ASSOCIATION_PERMIT)
+    }
+}
+
+/// aBaseSlotDuration, in symbols.
+/// Chapter 8.4.2
+pub const BASE_SLOT_DURATION: u32 = 60;
+/// aNumSuperframeSlots.
+/// Chapter 8.4.2
+pub const NUM_SUPERFRAME_SLOTS: u32 = 16;
+/// aBaseSuperframeDuration, in symbols.
+/// Chapter 8.4.2
+pub const BASE_SUPERFRAME_DURATION: u32 = BASE_SLOT_DURATION * NUM_SUPERFRAME_SLOTS;
+/// Beacon order and superframe order of a nonbeacon-enabled PAN.
+/// Chapter 6.2.1
+pub const NON_BEACON_ORDER: u8 = 15;
+/// aMinCapLength, in symbols.
+/// Chapter 8.4.2
+pub const MIN_CAP_LENGTH: u32 = 440;
+
+/// Duration in symbols.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolDuration(pub u32);
+
+impl SymbolDuration {
+    pub const fn symbols(&self) -> u32 {
+        self.0
+    }
+
+    /// Converts the duration to microseconds, `symbol_rate` being the
+    /// number of symbols per second of the PHY, like 62500 for O-QPSK in
+    /// the 2450 MHz band.
+    pub const fn to_microseconds(&self, symbol_rate: NonZeroU32) -> u64 {
+        self.0 as u64 * 1_000_000 / symbol_rate.get() as u64
+    }
+}
+
+impl SuperFrame {
+    /// Duration of the active portion of the superframe, or `None` in a
+    /// nonbeacon-enabled PAN.
+    /// Chapter 6.2.1
+    pub const fn superframe_duration_symbols(&self) -> Option<SymbolDuration> {
+        Self::order_duration(BASE_SUPERFRAME_DURATION, self.super_frame_order)
+    }
+
+    /// Interval between two beacons, or `None` in a nonbeacon-enabled PAN.
+    /// Chapter 6.2.1
+    pub const fn beacon_interval_symbols(&self) -> Option<SymbolDuration> {
+        Self::order_duration(BASE_SUPERFRAME_DURATION, self.beacon_order)
+    }
+
+    /// Duration of one of the [`NUM_SUPERFRAME_SLOTS`] slots of the active
+    /// portion, or `None` in a nonbeacon-enabled PAN.
+    /// Chapter 6.2.1
+    pub const fn slot_duration_symbols(&self) -> Option<SymbolDuration> {
+        Self::order_duration(BASE_SLOT_DURATION, self.super_frame_order)
+    }
+
+    /// Duration of the CAP, beacon slot included, or `None` in a
+    /// nonbeacon-enabled PAN.
+    /// Chapter 6.2.5
+    pub const fn cap_duration_symbols(&self) -> Option<SymbolDuration> {
+        match self.slot_duration_symbols() {
+            Some(slot) => Some(SymbolDuration(slot.0 * (self.final_cap_slot as u32 + 1))),
+            None => None,
+        }
+    }
+
+    /// Checks the superframe specification against the rules a coordinator
+    /// must follow, `gts` being the GTS fields of the same beacon.
+    /// Chapter 6.2.1, 6.2.5
+    ///
+    /// # Error
+    ///
+    /// Returns `InvalidSuperFrameSpec` if the superframe order is over the
+    /// beacon order, if the CAP is shorter than [`MIN_CAP_LENGTH`] while GTSs
+    /// are allocated, or if GTSs are permitted in a nonbeacon-enabled PAN.
+    pub fn validate(&self, gts: &Gts) -> Result<(), crate::parser::Error> {
+        if self.super_frame_order > self.beacon_order {
+            return Err(crate::parser::Error::InvalidSuperFrameSpec);
+        }
+
+        if self.beacon_order == NON_BEACON_ORDER && gts.permit {
+            return Err(crate::parser::Error::InvalidSuperFrameSpec);
+        }
+
+        if !gts.descriptors.is_empty() {
+            match self.cap_duration_symbols() {
+                Some(cap) if cap.symbols() >= MIN_CAP_LENGTH => {}
+                _ => return Err(crate::parser::Error::InvalidSuperFrameSpec),
+            }
+        }
+
+        Ok(())
+    }
+
+    const fn order_duration(base: u32, order: u8) -> Option<SymbolDuration> {
+        match order {
+            0..NON_BEACON_ORDER => Some(SymbolDuration(base << order)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> TryRead<'a, Endian> for SuperFrame {
+    fn try_read(bytes: &'a [u8], endian: Endian) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let bits: u16 = bytes.read_with(offset, endian)?;
+
+        Ok((SuperFrame::from_bits(bits), *offset))
+    }
+}
+
+impl TryWrite<Endian> for SuperFrame {
+    fn try_write(self, bytes: &mut [u8], endian: Endian) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.bits(), endian)?;
+
+        Ok(*offset)
     }
 }
 
@@ -101,13 +252,13 @@ pub mod gts {
     #[cfg(feature = "ufmt")]
     use ufmt::uwrite;
 
-    use crate::ieee802154::address::{PanId, ShortAddress};
+    use crate::ieee802154::address::{wire, PanId, ShortAddress};
 
     pub mod offset {
         pub const GTS_DESCRIPTOR_COUNT: usize = 0;
         pub const GTS_PERMIT: usize = 0x7;
         pub const GTS_STARTING_SLOT: usize = 0;
-        pub const GTS_DESC_LENGTH: usize = 3;
+        pub const GTS_DESC_LENGTH: usize = 4;
     }
 
     pub mod mask {
@@ -123,8 +274,12 @@ pub mod gts {
     pub const MAX_GTS_DESCRIPTOR: usize = 0x7;
     /// GTS descriptor size in bytes
     pub(crate) const GTS_DESCRIPTOR_SIZE: usize = 0x3;
+    /// GTS specification size in bytes
+    pub(crate) const GTS_SPEC_SIZE: usize = 0x1;
+    /// GTS directions size in bytes, only present with descriptors
+    pub(crate) const GTS_DIRECTIONS_SIZE: usize = 0x1;
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     pub struct Gts {
         pub permit: bool,
         /// Coordinator accepting GTS request
@@ -148,51 +303,136 @@ pub mod gts {
     }
 
     impl Gts {
-        pub fn from_bytes(pan: PanId, data: &[u8]) -> Result<Self, crate::parser::Error> {
-            if data.is_empty() {
-                return Err(crate::parser::Error::InvalidPayload);
-            }
-
-            let gts_spec: u8 = data[0];
+        /// Parses the GTS fields at the start of `data`, and returns them
+        /// along with their size in bytes.
+        /// Chapter 7.3.1.5
+        ///
+        /// # Error
+        ///
+        /// If `data` is empty, returns `Truncated`. If the GTS fields are
+        /// shorter than the descriptor count of the specification, returns
+        /// `InvalidGtsSpec`.
+        pub fn from_bytes(pan: PanId, data: &[u8]) -> Result<(Self, usize), crate::parser::Error> {
+            let gts_spec: u8 = *data.first().ok_or(crate::parser::Error::Truncated)?;
 
             let desciptor_count: usize =
                 ((gts_spec & mask::GTS_DESCRIPTOR_COUNT) >> offset::GTS_DESCRIPTOR_COUNT).into();
             let permit: bool = gts_spec & mask::GTS_PERMIT != 0;
             let mut descriptors = heapless::Vec::new();
 
-            if desciptor_count != 0 {
-                // GTS Spec + GTS direction + GTS list
-                if desciptor_count * GTS_DESCRIPTOR_SIZE + 2 < data.len() {
-                    return Err(crate::parser::Error::InvalidPayload);
-                }
-
-                let gts_direction: u8 = data[1] & mask::GTS_DIRECTION;
-
-                for i in 0..desciptor_count {
-                    let index = GTS_DESCRIPTOR_SIZE * i + 2;
-                    let gts_desc_info: u8 = data[index + 2];
-                    unsafe {
-                        descriptors.push_unchecked(GtsDescriptor {
-                            address: ShortAddress::new(
-                                pan,
-                                u16::from_le_bytes([data[index], data[index + 1]]),
-                            ),
-                            starting_slot: gts_desc_info & mask::GTS_STARTING_SLOT,
-                            length: (gts_desc_info & mask::GTS_DESC_LENGTH)
-                                >> offset::GTS_DESC_LENGTH,
-                            direction: GtsDirection::from_bit((gts_direction & (0x1 << i)) != 0),
-                        })
-                    }
-                }
+            if desciptor_count == 0 {
+                return Ok((
+                    Gts {
+                        permit,
+                        descriptors,
+                    },
+                    GTS_SPEC_SIZE,
+                ));
             }
 
-            Ok(Gts {
-                permit,
-                descriptors,
-            })
+            // GTS Spec + GTS directions + GTS list
+            let (gts_direction, list) = data[GTS_SPEC_SIZE..]
+                .split_first()
+                .map(|(direction, list)| (direction & mask::GTS_DIRECTION, list))
+                .ok_or(crate::parser::Error::InvalidGtsSpec)?;
+            let list = list
+                .get(..desciptor_count * GTS_DESCRIPTOR_SIZE)
+                .ok_or(crate::parser::Error::InvalidGtsSpec)?;
+
+            for (i, desc) in list.chunks_exact(GTS_DESCRIPTOR_SIZE).enumerate() {
+                // Short address on 2 bytes, then starting slot and length
+                let mut offset = 0;
+                let address = wire::read_short(desc, &mut offset, pan)?;
+                let gts_desc_info = desc[offset];
+
+                descriptors
+                    .push(GtsDescriptor {
+                        address,
+                        starting_slot: gts_desc_info & mask::GTS_STARTING_SLOT,
+                        length: (gts_desc_info & mask::GTS_DESC_LENGTH) >> offset::GTS_DESC_LENGTH,
+                        direction: GtsDirection::from_bit((gts_direction & (0x1 << i)) != 0),
+                    })
+                    .map_err(|_| crate::parser::Error::InvalidGtsSpec)?;
+            }
+
+            Ok((
+                Gts {
+                    permit,
+                    descriptors,
+                },
+                GTS_SPEC_SIZE + GTS_DIRECTIONS_SIZE + list.len(),
+            ))
+        }
+
+        /// Size in bytes of the GTS fields, the directions and list being
+        /// omitted without descriptors.
+        pub fn serialized_len(&self) -> usize {
+            match self.descriptors.len() {
+                0 => GTS_SPEC_SIZE,
+                count => GTS_SPEC_SIZE + GTS_DIRECTIONS_SIZE + count * GTS_DESCRIPTOR_SIZE,
+            }
+        }
+
+        /// Number of superframe slots allocated to GTSs, which form the CFP
+        /// following the CAP.
+        /// Chapter 6.2.6
+        pub fn cfp_slots(&self) -> u8 {
+            self.descriptors.iter().map(|desc| desc.length).sum()
+        }
+
+        /// Returns the descriptor allocated to `address`, if any. Only the
+        /// address value is compared, the list being relative to the
+        /// coordinator PAN.
+        pub fn descriptor_for(&self, address: &ShortAddress) -> Option<&GtsDescriptor> {
+            self.descriptors
+                .iter()
+                .find(|desc| desc.address.value() == address.value())
+        }
+
+        /// Writes the GTS fields into `buf`, and returns the number of bytes
+        /// written.
+        /// Chapter 7.3.1.5
+        ///
+        /// # Errors
+        ///
+        /// If `buf` is too small, returns `BufferTooSmall`.
+        pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+            let len = self.serialized_len();
+            let buf = buf
+                .get_mut(..len)
+                .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+            buf[0] = (((self.descriptors.len() as u8) << offset::GTS_DESCRIPTOR_COUNT)
+                & mask::GTS_DESCRIPTOR_COUNT)
+                | (((self.permit as u8) << offset::GTS_PERMIT) & mask::GTS_PERMIT);
+
+            if self.descriptors.is_empty() {
+                return Ok(len);
+            }
+
+            let (directions, list) = buf[GTS_SPEC_SIZE..].split_at_mut(GTS_DIRECTIONS_SIZE);
+            directions[0] = 0;
+
+            for (i, (desc, out)) in self
+                .descriptors
+                .iter()
+                .zip(list.chunks_exact_mut(GTS_DESCRIPTOR_SIZE))
+                .enumerate()
+            {
+                let mut address_end = 0;
+                wire::write_short(out, &mut address_end, &desc.address)?;
+
+                out[address_end] = ((desc.starting_slot << offset::GTS_STARTING_SLOT)
+                    & mask::GTS_STARTING_SLOT)
+                    | ((desc.length << offset::GTS_DESC_LENGTH) & mask::GTS_DESC_LENGTH);
+                directions[0] |= ((desc.direction.bit() as u8) << i) & mask::GTS_DIRECTION;
+            }
+
+            Ok(len)
         }
     }
 
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
     #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub enum GtsDirection {
@@ -213,12 +453,34 @@ pub mod gts {
                 false => GtsDirection::Transmit,
             }
         }
+
+        const fn bit(&self) -> bool {
+            matches!(self, GtsDirection::Receive)
+        }
+    }
+
+    /// Side of a GTS the local device is on.
+    #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Role {
+        /// PAN coordinator that allocated the GTS
+        Coordinator,
+        /// Device the GTS is allocated to
+        Device,
+    }
+
+    /// What the local device does during a GTS.
+    #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TrafficDirection {
+        Transmit,
+        Receive,
     }
 
     /// Format of a GTS descriptor.
     /// Figure 7-11
     #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     pub struct GtsDescriptor {
         pub address: ShortAddress,
         pub starting_slot: u8,
@@ -226,14 +488,24 @@ pub mod gts {
         pub direction: GtsDirection,
     }
 
+    impl GtsDescriptor {
+        /// Whether `role` transmits or receives during the GTS. The
+        /// direction bit is relative to the device the GTS is allocated to,
+        /// so the coordinator does the opposite.
+        /// Chapter 7.3.1.5.3
+        pub const fn direction_for(&self, role: Role) -> TrafficDirection {
+            match (role, self.direction.bit()) {
+                (Role::Device, true) | (Role::Coordinator, false) => TrafficDirection::Receive,
+                (Role::Device, false) | (Role::Coordinator, true) => TrafficDirection::Transmit,
+            }
+        }
+    }
+
     #[cfg(test)]
     mod tests {
-        use crate::{
-            address::Address,
-            ieee802154::{
-                address::{PanId, ShortAddress},
-                frame::beacon::gts::GtsDirection,
-            },
+        use crate::ieee802154::{
+            address::{PanId, ShortAddress},
+            frame::beacon::gts::{GtsDescriptor, GtsDirection, Role, TrafficDirection},
         };
 
         use super::Gts;
@@ -242,10 +514,11 @@ pub mod gts {
         fn from_bytes_zero_gts_descriptors() {
             let payload = [0x0];
 
-            let gts = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+            let (gts, len) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
 
             assert_eq!(gts.permit, false);
             assert_eq!(gts.descriptors.len(), 0);
+            assert_eq!(len, 1);
         }
 
         #[test]
@@ -255,10 +528,11 @@ pub mod gts {
                 0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA,
             ];
 
-            let gts = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+            let (gts, len) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
 
             assert_eq!(gts.permit, true);
             assert_eq!(gts.descriptors.len(), 7);
+            assert_eq!(len, payload.len());
 
             for i in 0..7 {
                 let desc = &gts.descriptors[i];
@@ -274,16 +548,422 @@ pub mod gts {
                 assert_eq!(desc.length, 0xF);
             }
         }
+
+        #[test]
+        fn from_bytes_max_gts_descriptors_short_buffer() {
+            let payload: [u8; 22] = [
+                0x87, 0xF0, 0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA,
+                0xAB, 0xCD, 0xFA, 0xAB, 0xCD, 0xFA, 0xAB, 0xCD,
+            ];
+
+            assert!(matches!(
+                Gts::from_bytes(PanId::broadcast(), &payload),
+                Err(crate::parser::Error::InvalidGtsSpec)
+            ));
+            assert!(matches!(
+                Gts::from_bytes(PanId::broadcast(), &payload[..2]),
+                Err(crate::parser::Error::InvalidGtsSpec)
+            ));
+        }
+
+        #[test]
+        fn from_bytes_with_trailing_beacon_payload() {
+            let payload: [u8; 8] = [0x81, 0x00, 0x34, 0x12, 0x21, 0x00, 0xDE, 0xAD];
+
+            let (gts, len) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+
+            assert_eq!(gts.descriptors.len(), 1);
+            assert_eq!(len, 5);
+            assert_eq!(
+                gts.descriptors[0].address,
+                ShortAddress::new(PanId::broadcast(), 0x1234)
+            );
+        }
+
+        #[test]
+        fn from_bytes_exact_buffer() {
+            let payload: [u8; 1 + 1 + 3 * 2] = [0x82, 0x02, 0x01, 0x00, 0x10, 0x02, 0x00, 0x23];
+
+            let (gts, len) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+
+            assert_eq!(len, payload.len());
+            assert_eq!(gts.descriptors.len(), 2);
+            assert_eq!(
+                gts.descriptors[0].address,
+                ShortAddress::new(PanId::broadcast(), 0x0001)
+            );
+            assert_eq!(gts.descriptors[0].direction, GtsDirection::Transmit);
+            assert_eq!(
+                gts.descriptors[1].address,
+                ShortAddress::new(PanId::broadcast(), 0x0002)
+            );
+            assert_eq!(gts.descriptors[1].direction, GtsDirection::Receive);
+
+            assert!(matches!(
+                Gts::from_bytes(PanId::broadcast(), &payload[..payload.len() - 1]),
+                Err(crate::parser::Error::InvalidGtsSpec)
+            ));
+        }
+
+        #[test]
+        fn from_bytes_starting_slot_and_length() {
+            let payload: [u8; 5] = [0x81, 0x00, 0x34, 0x12, 0x2B];
+
+            let (gts, _) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+
+            assert_eq!(gts.descriptors[0].starting_slot, 0xB);
+            assert_eq!(gts.descriptors[0].length, 0x2);
+        }
+
+        #[test]
+        fn write_into_round_trip_without_descriptors() {
+            for payload in [[0x00], [0x80]] {
+                let (gts, _) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+                let mut buf = [0u8; 1];
+
+                assert_eq!(gts.write_into(&mut buf).unwrap(), 1);
+                assert_eq!(buf, payload);
+            }
+        }
+
+        #[test]
+        fn write_into_round_trip_one_descriptor() {
+            let payload: [u8; 5] = [0x81, 0x01, 0x34, 0x12, 0x2B];
+            let (gts, _) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+            let mut buf = [0u8; 5];
+
+            assert_eq!(gts.write_into(&mut buf).unwrap(), 5);
+            assert_eq!(buf, payload);
+            assert_eq!(
+                gts.write_into(&mut buf[..4]),
+                Err(crate::composer::Error::BufferTooSmall { needed: 5 })
+            );
+        }
+
+        #[test]
+        fn write_into_round_trip_max_descriptors() {
+            let payload: [u8; 23] = [
+                0x87, 0x55, 0x01, 0x00, 0x10, 0x02, 0x00, 0x21, 0x03, 0x00, 0x32, 0x04, 0x00, 0x43,
+                0x05, 0x00, 0x54, 0x06, 0x00, 0x65, 0x07, 0x00, 0x76,
+            ];
+            let (gts, _) = Gts::from_bytes(PanId::broadcast(), &payload).unwrap();
+            let mut buf = [0u8; 23];
+
+            assert_eq!(gts.descriptors[0].direction, GtsDirection::Receive);
+            assert_eq!(gts.descriptors[1].direction, GtsDirection::Transmit);
+            assert_eq!(gts.write_into(&mut buf).unwrap(), 23);
+            assert_eq!(buf, payload);
+        }
+
+        #[test]
+        fn serialized_len_matches_from_bytes() {
+            let payloads: [&[u8]; 3] = [
+                &[0x80],
+                &[0x81, 0x01, 0x34, 0x12, 0x2B],
+                &[0x82, 0x02, 0x01, 0x00, 0x10, 0x02, 0x00, 0x23],
+            ];
+
+            for payload in payloads {
+                let (gts, len) = Gts::from_bytes(PanId::broadcast(), payload).unwrap();
+
+                assert_eq!(gts.serialized_len(), len);
+            }
+        }
+
+        #[test]
+        fn cfp_slots_and_descriptor_for() {
+            let payload: [u8; 8] = [0x82, 0x02, 0x01, 0x00, 0x1D, 0x02, 0x00, 0x2B];
+            let (gts, _) = Gts::from_bytes(PanId::new(0x1234), &payload).unwrap();
+
+            assert_eq!(gts.cfp_slots(), 3);
+
+            let desc = gts
+                .descriptor_for(&ShortAddress::new(PanId::broadcast(), 0x0002))
+                .unwrap();
+            assert_eq!(desc.starting_slot, 0xB);
+            assert_eq!(desc.length, 0x2);
+            assert!(gts
+                .descriptor_for(&ShortAddress::new(PanId::new(0x1234), 0x0003))
+                .is_none());
+        }
+
+        #[test]
+        fn direction_for_roles() {
+            let mut desc = GtsDescriptor {
+                address: ShortAddress::new(PanId::broadcast(), 0x0001),
+                starting_slot: 0xA,
+                length: 0x1,
+                direction: GtsDirection::Receive,
+            };
+
+            assert_eq!(desc.direction_for(Role::Device), TrafficDirection::Receive);
+            assert_eq!(
+                desc.direction_for(Role::Coordinator),
+                TrafficDirection::Transmit
+            );
+
+            desc.direction = GtsDirection::Transmit;
+
+            assert_eq!(desc.direction_for(Role::Device), TrafficDirection::Transmit);
+            assert_eq!(
+                desc.direction_for(Role::Coordinator),
+                TrafficDirection::Receive
+            );
+        }
+
+        #[test]
+        fn from_bytes_count_over_buffer() {
+            let payload: [u8; 5] = [0x83, 0x00, 0x34, 0x12, 0x21];
+
+            assert!(matches!(
+                Gts::from_bytes(PanId::broadcast(), &payload),
+                Err(crate::parser::Error::InvalidGtsSpec)
+            ));
+        }
+
+        #[test]
+        fn from_bytes_empty_buffer() {
+            assert!(matches!(
+                Gts::from_bytes(PanId::broadcast(), &[]),
+                Err(crate::parser::Error::Truncated)
+            ));
+        }
     }
 }
 
-#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
-pub struct PendingAddress {}
+pub mod pending_address {
+
+    #[cfg(feature = "ufmt")]
+    use ufmt::uwrite;
+
+    use crate::ieee802154::address::{
+        wire, AddressKind, LongAddress, PanId, ShortAddress, LONG_ADDRESS_SIZE, SHORT_ADDRESS_SIZE,
+    };
+
+    pub mod offset {
+        pub const SHORT_ADDRESS_COUNT: usize = 0;
+        pub const LONG_ADDRESS_COUNT: usize = 4;
+    }
+
+    pub mod mask {
+        use super::offset;
+
+        pub const SHORT_ADDRESS_COUNT: u8 = 0x7 << offset::SHORT_ADDRESS_COUNT;
+        pub const LONG_ADDRESS_COUNT: u8 = 0x7 << offset::LONG_ADDRESS_COUNT;
+    }
+
+    pub const MAX_PENDING_ADDRESS: usize = 0x7;
+    /// Pending address specification size in bytes
+    pub(crate) const PENDING_ADDRESS_SPEC_SIZE: usize = 0x1;
+
+    /// Addresses of the devices the coordinator holds pending frames for.
+    /// Chapter 7.3.1.6
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct PendingAddress {
+        pub short_addresses: heapless::Vec<ShortAddress, MAX_PENDING_ADDRESS>,
+        pub long_addresses: heapless::Vec<LongAddress, MAX_PENDING_ADDRESS>,
+    }
+
+    #[cfg(feature = "ufmt")]
+    impl ufmt::uDebug for PendingAddress {
+        fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: ufmt::uWrite + ?Sized,
+        {
+            uwrite!(f, "PendingAddress {{ short_addresses: [ ")?;
+
+            for address in &self.short_addresses {
+                uwrite!(f, "{:?} ", address)?;
+            }
+
+            uwrite!(f, "] long_addresses: [ ")?;
+
+            for address in &self.long_addresses {
+                uwrite!(f, "{:?} ", address)?;
+            }
+
+            uwrite!(f, "] }}")
+        }
+    }
+
+    impl PendingAddress {
+        /// Parses the pending address fields, addresses being associated
+        /// with the coordinator `pan`.
+        pub fn from_bytes(pan: PanId, data: &[u8]) -> Result<Self, crate::parser::Error> {
+            let spec: u8 = *data.first().ok_or(crate::parser::Error::Truncated)?;
+
+            let short_count: usize =
+                ((spec & mask::SHORT_ADDRESS_COUNT) >> offset::SHORT_ADDRESS_COUNT).into();
+            let long_count: usize =
+                ((spec & mask::LONG_ADDRESS_COUNT) >> offset::LONG_ADDRESS_COUNT).into();
+
+            let short_start = PENDING_ADDRESS_SPEC_SIZE;
+            let long_start = short_start + short_count * SHORT_ADDRESS_SIZE;
+            let end = long_start + long_count * LONG_ADDRESS_SIZE;
+
+            if data.len() < end {
+                return Err(crate::parser::Error::Truncated);
+            }
+
+            let mut pending = PendingAddress::default();
+
+            let mut offset = short_start;
+
+            for _ in 0..short_count {
+                pending
+                    .short_addresses
+                    .push(wire::read_short(data, &mut offset, pan)?)
+                    .map_err(|_| crate::parser::Error::InvalidPayload)?;
+            }
+
+            for _ in 0..long_count {
+                pending
+                    .long_addresses
+                    .push(wire::read_long(data, &mut offset, pan)?)
+                    .map_err(|_| crate::parser::Error::InvalidPayload)?;
+            }
+
+            Ok(pending)
+        }
+
+        /// Size in bytes of the pending address fields, specification
+        /// included.
+        pub fn serialized_len(&self) -> usize {
+            PENDING_ADDRESS_SPEC_SIZE
+                + self.short_addresses.len() * SHORT_ADDRESS_SIZE
+                + self.long_addresses.len() * LONG_ADDRESS_SIZE
+        }
+
+        /// Writes the pending address fields into `buf`, and returns the
+        /// number of bytes written.
+        /// Chapter 7.3.1.6
+        ///
+        /// # Errors
+        ///
+        /// If `buf` is too small, returns `BufferTooSmall`.
+        pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+            let len = self.serialized_len();
+            let buf = buf
+                .get_mut(..len)
+                .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+            buf[0] = (((self.short_addresses.len() as u8) << offset::SHORT_ADDRESS_COUNT)
+                & mask::SHORT_ADDRESS_COUNT)
+                | (((self.long_addresses.len() as u8) << offset::LONG_ADDRESS_COUNT)
+                    & mask::LONG_ADDRESS_COUNT);
+
+            let mut offset = PENDING_ADDRESS_SPEC_SIZE;
+
+            for address in &self.short_addresses {
+                wire::write_short(buf, &mut offset, address)?;
+            }
+
+            for address in &self.long_addresses {
+                wire::write_long(buf, &mut offset, address)?;
+            }
+
+            Ok(len)
+        }
+
+        /// Whether the coordinator has frames pending for `address`. Only
+        /// the address value is compared, the list being relative to the
+        /// coordinator PAN.
+        pub fn contains(&self, address: &AddressKind) -> bool {
+            match address {
+                AddressKind::Short(address) => self
+                    .short_addresses
+                    .iter()
+                    .any(|pending| pending.value() == address.value()),
+                AddressKind::Long(address) => self
+                    .long_addresses
+                    .iter()
+                    .any(|pending| pending.value() == address.value()),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::ieee802154::address::{AddressKind, LongAddress, PanId, ShortAddress};
+
+        use super::PendingAddress;
+
+        #[test]
+        fn from_bytes_no_pending_address() {
+            let pending = PendingAddress::from_bytes(PanId::new(0x1234), &[0x00, 0x42]).unwrap();
+
+            assert!(pending.short_addresses.is_empty());
+            assert!(pending.long_addresses.is_empty());
+            assert_eq!(pending.serialized_len(), 1);
+        }
+
+        #[test]
+        fn from_bytes_short_and_long_addresses() {
+            let payload: [u8; 13] = [
+                0x12, 0xCD, 0xAB, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01,
+            ];
+            let pan = PanId::new(0x1234);
+
+            let pending = PendingAddress::from_bytes(pan, &payload).unwrap();
+
+            assert_eq!(
+                pending.short_addresses,
+                [
+                    ShortAddress::new(pan, 0xABCD),
+                    ShortAddress::new(pan, 0x1234)
+                ]
+            );
+            assert_eq!(
+                pending.long_addresses,
+                [LongAddress::new(pan, 0x0102_0304_0506_0708)]
+            );
+            assert_eq!(pending.serialized_len(), payload.len());
+
+            assert!(pending.contains(&AddressKind::Short(ShortAddress::new(pan, 0x1234))));
+            assert!(!pending.contains(&AddressKind::Short(ShortAddress::new(pan, 0x0001))));
+            assert!(pending.contains(&AddressKind::Long(LongAddress::new(
+                pan,
+                0x0102_0304_0506_0708
+            ))));
+        }
+
+        #[test]
+        fn from_bytes_truncated() {
+            assert!(PendingAddress::from_bytes(PanId::new(0x1234), &[]).is_err());
+            assert!(PendingAddress::from_bytes(PanId::new(0x1234), &[0x10, 0x01, 0x02]).is_err());
+        }
+
+        #[test]
+        fn write_into_round_trip() {
+            let payload: [u8; 13] = [
+                0x12, 0xCD, 0xAB, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01,
+            ];
+            let pending = PendingAddress::from_bytes(PanId::new(0x1234), &payload).unwrap();
+
+            let mut buf = [0u8; 13];
+            assert_eq!(pending.write_into(&mut buf).unwrap(), payload.len());
+            assert_eq!(buf, payload);
+
+            assert_eq!(
+                pending.write_into(&mut [0u8; 12]),
+                Err(crate::composer::Error::BufferTooSmall { needed: 13 })
+            );
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use super::SuperFrame;
+    use core::num::NonZeroU32;
+
+    use byte::{BytesExt, LE};
+
+    use crate::ieee802154::address::{PanId, ShortAddress};
+
+    use super::{
+        gts::{Gts, GtsDescriptor, GtsDirection},
+        SuperFrame, SymbolDuration,
+    };
 
     #[test]
     fn from_bits_parse_correct_values() {
@@ -305,4 +985,156 @@ mod tests {
 
         assert_eq!(SuperFrame::from_bytes(&bytes).bytes(), bytes);
     }
+
+    #[test]
+    fn from_bits_beacon_order_next_to_super_frame_order() {
+        let bytes: [u8; 2] = [0x3E, 0x00];
+
+        let super_frame = SuperFrame::from_bytes(&bytes);
+
+        assert_eq!(super_frame.beacon_order, 0xE);
+        assert_eq!(super_frame.super_frame_order, 0x3);
+        assert_eq!(super_frame.final_cap_slot, 0x0);
+    }
+
+    #[test]
+    fn from_bits_eq_bits_exhaustive() {
+        // Bit 13 is reserved and not kept
+        const RESERVED: u16 = 0x1 << 13;
+
+        for value in 0..=u16::MAX {
+            let bytes = value.to_le_bytes();
+
+            assert_eq!(
+                u16::from_le_bytes(SuperFrame::from_bytes(&bytes).bytes()),
+                value & !RESERVED
+            );
+        }
+    }
+
+    #[test]
+    fn timing_in_symbols() {
+        let super_frame = SuperFrame::from_bytes(&[0x36, 0x0E]);
+
+        assert_eq!(
+            super_frame.beacon_interval_symbols(),
+            Some(SymbolDuration(960 * 64))
+        );
+        assert_eq!(
+            super_frame.superframe_duration_symbols(),
+            Some(SymbolDuration(960 * 8))
+        );
+        assert_eq!(
+            super_frame.slot_duration_symbols(),
+            Some(SymbolDuration(60 * 8))
+        );
+        assert_eq!(
+            super_frame.cap_duration_symbols(),
+            Some(SymbolDuration(60 * 8 * 15))
+        );
+        assert_eq!(
+            super_frame
+                .beacon_interval_symbols()
+                .unwrap()
+                .to_microseconds(NonZeroU32::new(62_500).unwrap()),
+            983_040
+        );
+    }
+
+    #[test]
+    fn timing_in_non_beacon_enabled_pan() {
+        let super_frame = SuperFrame::from_bytes(&[0xFF, 0x0F]);
+
+        assert_eq!(super_frame.beacon_interval_symbols(), None);
+        assert_eq!(super_frame.superframe_duration_symbols(), None);
+        assert_eq!(super_frame.slot_duration_symbols(), None);
+        assert_eq!(super_frame.cap_duration_symbols(), None);
+
+        let super_frame = SuperFrame::from_bytes(&[0x0E, 0x0F]);
+
+        assert_eq!(
+            super_frame.beacon_interval_symbols(),
+            Some(SymbolDuration(960 << 14))
+        );
+    }
+
+    #[test]
+    fn validate_orders() {
+        let gts = Gts {
+            permit: false,
+            descriptors: heapless::Vec::new(),
+        };
+
+        assert_eq!(SuperFrame::from_bytes(&[0x36, 0x0E]).validate(&gts), Ok(()));
+        assert_eq!(SuperFrame::from_bytes(&[0xFF, 0x0F]).validate(&gts), Ok(()));
+        assert_eq!(
+            SuperFrame::from_bytes(&[0x63, 0x0E]).validate(&gts),
+            Err(crate::parser::Error::InvalidSuperFrameSpec)
+        );
+    }
+
+    #[test]
+    fn validate_gts() {
+        let mut gts = Gts {
+            permit: true,
+            descriptors: heapless::Vec::new(),
+        };
+
+        assert_eq!(
+            SuperFrame::from_bytes(&[0xFF, 0x0F]).validate(&gts),
+            Err(crate::parser::Error::InvalidSuperFrameSpec)
+        );
+
+        gts.descriptors
+            .push(GtsDescriptor {
+                address: ShortAddress::new(PanId::broadcast(), 0x1234),
+                starting_slot: 0xF,
+                length: 0x1,
+                direction: GtsDirection::Receive,
+            })
+            .unwrap();
+
+        // 7 slots of 60 symbols, under aMinCapLength
+        assert_eq!(
+            SuperFrame::from_bytes(&[0x00, 0x06]).validate(&gts),
+            Err(crate::parser::Error::InvalidSuperFrameSpec)
+        );
+        assert_eq!(SuperFrame::from_bytes(&[0x00, 0x07]).validate(&gts), Ok(()));
+        assert_eq!(SuperFrame::from_bytes(&[0x11, 0x03]).validate(&gts), Ok(()));
+    }
+
+    #[test]
+    fn read_from_slice() {
+        let bytes: [u8; 3] = [0x3E, 0x92, 0xFF];
+        let offset = &mut 0;
+
+        let super_frame: SuperFrame = bytes.read_with(offset, LE).unwrap();
+
+        assert_eq!(*offset, 2);
+        assert_eq!(super_frame.beacon_order, 0xE);
+        assert_eq!(super_frame.super_frame_order, 0x3);
+        assert_eq!(super_frame.final_cap_slot, 0x2);
+        assert!(super_frame.association_permit);
+
+        assert!(matches!(
+            bytes[2..].read_with::<SuperFrame>(&mut 0, LE),
+            Err(byte::Error::Incomplete)
+        ));
+    }
+
+    #[test]
+    fn write_into_slice() {
+        let bytes: [u8; 2] = [0b10100101, 0b01010010];
+        let mut buf = [0u8; 3];
+        let offset = &mut 0;
+
+        buf.write_with(offset, SuperFrame::from_bytes(&bytes), LE)
+            .unwrap();
+
+        assert_eq!(*offset, 2);
+        assert_eq!(buf[..2], bytes);
+        assert!(buf[2..]
+            .write_with(&mut 0, SuperFrame::from_bytes(&bytes), LE)
+            .is_err());
+    }
 }
@@ -0,0 +1,295 @@
+use crate::ieee802154::{
+    address::{AddressField, AddressKind, AddressingMode, LongAddress, ShortAddress},
+    control_field::{FrameKind, FrameVersion, StandardControlField},
+    ie::header::HeaderIeIterator,
+};
+
+use super::beacon::{
+    gts::{Gts, GtsDescriptor},
+    pending_address::PendingAddress,
+    BeaconFrame, BeaconHeader, BeaconPayload, SuperFrame, NON_BEACON_ORDER,
+};
+
+/// Builds an unsecured [`BeaconFrame`] of version IEEE 802.15.4-2003.
+///
+/// Without further settings, the beacon is the one of a nonbeacon-enabled
+/// PAN, with no GTS nor pending address.
+#[derive(Debug, Clone)]
+pub struct BeaconBuilder<'a> {
+    seq_no: u8,
+    src_addr: Option<AddressKind>,
+    super_frame: SuperFrame,
+    gts: Gts,
+    pending_address: PendingAddress,
+    payload: &'a [u8],
+    /// Set when a GTS descriptor or a pending address did not fit
+    overflow: bool,
+}
+
+impl<'a> Default for BeaconBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> BeaconBuilder<'a> {
+    pub fn new() -> Self {
+        BeaconBuilder {
+            seq_no: 0,
+            src_addr: None,
+            super_frame: SuperFrame {
+                beacon_order: NON_BEACON_ORDER,
+                super_frame_order: NON_BEACON_ORDER,
+                final_cap_slot: 0xF,
+                low_energy: false,
+                pan_coordinator: false,
+                association_permit: false,
+            },
+            gts: Gts {
+                permit: false,
+                descriptors: heapless::Vec::new(),
+            },
+            pending_address: PendingAddress::default(),
+            payload: &[],
+            overflow: false,
+        }
+    }
+
+    /// Short or extended address of the coordinator, carrying the PAN ID.
+    pub fn src(mut self, address: impl Into<AddressKind>) -> Self {
+        self.src_addr = Some(address.into());
+        self
+    }
+
+    /// Extended address of the coordinator, carrying the PAN ID.
+    pub fn src_long(self, address: LongAddress) -> Self {
+        self.src(address)
+    }
+
+    pub fn seq_no(mut self, seq_no: u8) -> Self {
+        self.seq_no = seq_no;
+        self
+    }
+
+    pub fn beacon_order(mut self, order: u8) -> Self {
+        self.super_frame.beacon_order = order;
+        self
+    }
+
+    pub fn superframe_order(mut self, order: u8) -> Self {
+        self.super_frame.super_frame_order = order;
+        self
+    }
+
+    pub fn final_cap_slot(mut self, slot: u8) -> Self {
+        self.super_frame.final_cap_slot = slot;
+        self
+    }
+
+    pub fn battery_life_extension(mut self, enabled: bool) -> Self {
+        self.super_frame.low_energy = enabled;
+        self
+    }
+
+    pub fn pan_coordinator(mut self, pan_coordinator: bool) -> Self {
+        self.super_frame.pan_coordinator = pan_coordinator;
+        self
+    }
+
+    pub fn association_permit(mut self, permit: bool) -> Self {
+        self.super_frame.association_permit = permit;
+        self
+    }
+
+    pub fn gts_permit(mut self, permit: bool) -> Self {
+        self.gts.permit = permit;
+        self
+    }
+
+    pub fn add_gts(mut self, descriptor: GtsDescriptor) -> Self {
+        self.overflow |= self.gts.descriptors.push(descriptor).is_err();
+        self
+    }
+
+    pub fn pending_short(mut self, address: ShortAddress) -> Self {
+        self.overflow |= self.pending_address.short_addresses.push(address).is_err();
+        self
+    }
+
+    pub fn pending_long(mut self, address: LongAddress) -> Self {
+        self.overflow |= self.pending_address.long_addresses.push(address).is_err();
+        self
+    }
+
+    pub fn payload(mut self, payload: &'a [u8]) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Returns the beacon frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MissingAddress` if the source address is missing.
+    /// Returns `InvalidHeader` if too many GTS descriptors or pending
+    /// addresses were added, or if the superframe specification does not
+    /// pass [`SuperFrame::validate`].
+    pub fn build(self) -> Result<BeaconFrame<'a>, crate::composer::Error> {
+        if self.src_addr.is_none() {
+            return Err(crate::composer::Error::MissingAddress);
+        }
+
+        if self.overflow {
+            return Err(crate::composer::Error::InvalidHeader);
+        }
+
+        self.super_frame
+            .validate(&self.gts)
+            .map_err(|_| crate::composer::Error::InvalidHeader)?;
+
+        Ok(BeaconFrame {
+            header: BeaconHeader {
+                control: StandardControlField {
+                    frame_kind: FrameKind::Beacon,
+                    security_enabled: false,
+                    frame_pending: false,
+                    ack_required: false,
+                    pan_id_compression: false,
+                    seq_no_present: true,
+                    ie_present: false,
+                    version: FrameVersion::Ieee802154_2003,
+                    dst_addressing_mode: AddressingMode::None.bits(),
+                    src_addressing_mode: AddressingMode::of(&self.src_addr).bits(),
+                }
+                .into(),
+                seq_no: Some(self.seq_no),
+                src_addr: self.src_addr,
+                dst_addr: AddressField::Absent,
+                aux: None,
+                header_ies: HeaderIeIterator::new(&[]),
+            },
+            payload: BeaconPayload {
+                super_frame: self.super_frame,
+                gts: self.gts,
+                pending_address: self.pending_address,
+                data: self.payload,
+            },
+            mic: &[],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            address::{AddressKind, LongAddress, PanId, ShortAddress},
+            frame::{
+                beacon::gts::{GtsDescriptor, GtsDirection},
+                Frame,
+            },
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    use super::BeaconBuilder;
+
+    #[test]
+    fn build_and_compose() {
+        let pan = PanId::new(0x1234);
+        let beacon = BeaconBuilder::new()
+            .src(ShortAddress::new(pan, 0x0000))
+            .seq_no(0x2A)
+            .beacon_order(6)
+            .superframe_order(6)
+            .association_permit(true)
+            .pending_short(ShortAddress::new(pan, 0x0001))
+            .payload(&[0x01, 0x02, 0x03, 0x04])
+            .build()
+            .unwrap();
+
+        let frame = Ieee802154::compose(&Frame::Beacon(beacon)).unwrap();
+
+        assert_eq!(
+            frame,
+            [
+                0x00, 0x80, 0x2A, 0x34, 0x12, 0x00, 0x00, 0x66, 0x8F, 0x00, 0x01, 0x01, 0x00, 0x01,
+                0x02, 0x03, 0x04
+            ]
+        );
+
+        let Frame::Beacon(beacon) = Ieee802154::parse(&frame).unwrap() else {
+            panic!("expected a beacon frame");
+        };
+        assert_eq!(beacon.payload.super_frame.beacon_order, 6);
+        assert!(beacon.payload.super_frame.association_permit);
+        assert_eq!(beacon.payload.data, &[0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn build_with_long_address() {
+        let address = LongAddress::new(PanId::new(0x1234), 0x0102_0304_0506_0708);
+        let beacon = BeaconBuilder::new().src(address).build().unwrap();
+
+        assert_eq!(beacon.header.src_addr, Some(AddressKind::Long(address)));
+        assert_eq!(
+            BeaconBuilder::new().src_long(address).build().unwrap(),
+            beacon
+        );
+    }
+
+    #[test]
+    fn build_with_gts() {
+        let pan = PanId::new(0x1234);
+        let beacon = BeaconBuilder::new()
+            .src(ShortAddress::new(pan, 0x0000))
+            .beacon_order(6)
+            .superframe_order(6)
+            .final_cap_slot(13)
+            .gts_permit(true)
+            .add_gts(GtsDescriptor {
+                address: ShortAddress::new(pan, 0x0001),
+                starting_slot: 14,
+                length: 2,
+                direction: GtsDirection::Transmit,
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(beacon.payload.gts.cfp_slots(), 2);
+    }
+
+    #[test]
+    fn build_invalid() {
+        let src = ShortAddress::new(PanId::new(0x1234), 0x0000);
+
+        // Superframe order over beacon order
+        assert!(BeaconBuilder::new()
+            .src(src)
+            .beacon_order(5)
+            .superframe_order(6)
+            .build()
+            .is_err());
+
+        // GTS permitted in a nonbeacon-enabled PAN
+        assert!(BeaconBuilder::new()
+            .src(src)
+            .gts_permit(true)
+            .build()
+            .is_err());
+
+        // Missing source address
+        assert!(matches!(
+            BeaconBuilder::new().build(),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+
+        // Too many pending addresses
+        let builder = (0..8).fold(BeaconBuilder::new().src(src), |builder, i| {
+            builder.pending_short(ShortAddress::new(PanId::new(0x1234), i))
+        });
+        assert!(builder.build().is_err());
+    }
+}
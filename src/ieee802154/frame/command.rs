@@ -0,0 +1,1878 @@
+use crate::{
+    ieee802154::{
+        address::{AddressField, AddressKind, AddressingMode, PanId, ShortAddress},
+        composer::pan_id_compression,
+        control_field::{FrameKind, FrameVersion, StandardControlField},
+        ie::{header::HeaderIeIterator, payload::PayloadIeIterator},
+    },
+    parser::ParseMode,
+};
+
+use super::{Frame, MacHeader};
+
+/// Size in bytes of the command identifier
+pub const COMMAND_ID_SIZE: usize = 1;
+
+/// Identifier of a MAC command.
+/// Table 7-49
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandId {
+    AssociationRequest,
+    AssociationResponse,
+    DisassociationNotification,
+    DataRequest,
+    PanIdConflictNotification,
+    OrphanNotification,
+    BeaconRequest,
+    CoordinatorRealignment,
+    GtsRequest,
+    TrleManagementRequest,
+    TrleManagementResponse,
+    DsmeAssociationRequest,
+    DsmeAssociationResponse,
+    DsmeGtsRequest,
+    DsmeGtsReply,
+    DsmeGtsNotify,
+    DsmeInformationRequest,
+    DsmeInformationReply,
+    DsmeBeaconAllocationNotification,
+    DsmeBeaconCollisionNotification,
+    DsmeLinkReport,
+    RitDataRequest,
+    DbsRequest,
+    DbsResponse,
+    RitDataResponse,
+    VendorSpecific,
+    SrmRequest,
+    SrmResponse,
+    SrmReport,
+    SrmInformation,
+    /// Reserved value, only produced in lenient mode and refused by the
+    /// composer
+    Unknown(u8),
+}
+
+impl CommandId {
+    /// # Error
+    ///
+    /// If `value` is reserved, returns `InvalidCommandId`.
+    pub const fn from_byte(value: u8) -> Result<Self, crate::parser::Error> {
+        match value {
+            0x01 => Ok(CommandId::AssociationRequest),
+            0x02 => Ok(CommandId::AssociationResponse),
+            0x03 => Ok(CommandId::DisassociationNotification),
+            0x04 => Ok(CommandId::DataRequest),
+            0x05 => Ok(CommandId::PanIdConflictNotification),
+            0x06 => Ok(CommandId::OrphanNotification),
+            0x07 => Ok(CommandId::BeaconRequest),
+            0x08 => Ok(CommandId::CoordinatorRealignment),
+            0x09 => Ok(CommandId::GtsRequest),
+            0x0A => Ok(CommandId::TrleManagementRequest),
+            0x0B => Ok(CommandId::TrleManagementResponse),
+            0x13 => Ok(CommandId::DsmeAssociationRequest),
+            0x14 => Ok(CommandId::DsmeAssociationResponse),
+            0x15 => Ok(CommandId::DsmeGtsRequest),
+            0x16 => Ok(CommandId::DsmeGtsReply),
+            0x17 => Ok(CommandId::DsmeGtsNotify),
+            0x18 => Ok(CommandId::DsmeInformationRequest),
+            0x19 => Ok(CommandId::DsmeInformationReply),
+            0x1A => Ok(CommandId::DsmeBeaconAllocationNotification),
+            0x1B => Ok(CommandId::DsmeBeaconCollisionNotification),
+            0x1C => Ok(CommandId::DsmeLinkReport),
+            0x20 => Ok(CommandId::RitDataRequest),
+            0x21 => Ok(CommandId::DbsRequest),
+            0x22 => Ok(CommandId::DbsResponse),
+            0x23 => Ok(CommandId::RitDataResponse),
+            0x24 => Ok(CommandId::VendorSpecific),
+            0x25 => Ok(CommandId::SrmRequest),
+            0x26 => Ok(CommandId::SrmResponse),
+            0x27 => Ok(CommandId::SrmReport),
+            0x28 => Ok(CommandId::SrmInformation),
+            _ => Err(crate::parser::Error::InvalidCommandId(value)),
+        }
+    }
+
+    /// Same as [`CommandId::from_byte`], reserved values being returned as
+    /// [`CommandId::Unknown`] in lenient mode.
+    pub const fn from_byte_with_mode(
+        value: u8,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match (Self::from_byte(value), mode) {
+            (Err(_), ParseMode::Lenient) => Ok(CommandId::Unknown(value)),
+            (result, _) => result,
+        }
+    }
+
+    /// # Error
+    ///
+    /// If the identifier is reserved, returns `InvalidCommandId`.
+    pub const fn bits(&self) -> Result<u8, crate::composer::Error> {
+        Ok(match self {
+            CommandId::AssociationRequest => 0x01,
+            CommandId::AssociationResponse => 0x02,
+            CommandId::DisassociationNotification => 0x03,
+            CommandId::DataRequest => 0x04,
+            CommandId::PanIdConflictNotification => 0x05,
+            CommandId::OrphanNotification => 0x06,
+            CommandId::BeaconRequest => 0x07,
+            CommandId::CoordinatorRealignment => 0x08,
+            CommandId::GtsRequest => 0x09,
+            CommandId::TrleManagementRequest => 0x0A,
+            CommandId::TrleManagementResponse => 0x0B,
+            CommandId::DsmeAssociationRequest => 0x13,
+            CommandId::DsmeAssociationResponse => 0x14,
+            CommandId::DsmeGtsRequest => 0x15,
+            CommandId::DsmeGtsReply => 0x16,
+            CommandId::DsmeGtsNotify => 0x17,
+            CommandId::DsmeInformationRequest => 0x18,
+            CommandId::DsmeInformationReply => 0x19,
+            CommandId::DsmeBeaconAllocationNotification => 0x1A,
+            CommandId::DsmeBeaconCollisionNotification => 0x1B,
+            CommandId::DsmeLinkReport => 0x1C,
+            CommandId::RitDataRequest => 0x20,
+            CommandId::DbsRequest => 0x21,
+            CommandId::DbsResponse => 0x22,
+            CommandId::RitDataResponse => 0x23,
+            CommandId::VendorSpecific => 0x24,
+            CommandId::SrmRequest => 0x25,
+            CommandId::SrmResponse => 0x26,
+            CommandId::SrmReport => 0x27,
+            CommandId::SrmInformation => 0x28,
+            CommandId::Unknown(value) => match Self::from_byte(*value) {
+                Ok(_) => *value,
+                Err(_) => return Err(crate::composer::Error::InvalidCommandId(*value)),
+            },
+        })
+    }
+
+    /// Size in bytes of the content of the commands whose size is fixed,
+    /// `None` for the others.
+    pub const fn content_size(&self) -> Option<usize> {
+        match self {
+            CommandId::AssociationRequest => Some(ASSOCIATION_REQUEST_SIZE),
+            CommandId::AssociationResponse => Some(ASSOCIATION_RESPONSE_SIZE),
+            CommandId::DisassociationNotification => Some(DISASSOCIATION_NOTIFICATION_SIZE),
+            CommandId::DataRequest
+            | CommandId::PanIdConflictNotification
+            | CommandId::OrphanNotification
+            | CommandId::BeaconRequest => Some(0),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<u8> for CommandId {
+    type Error = crate::parser::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        CommandId::from_byte(value)
+    }
+}
+
+/// Capability of a device requesting association.
+/// Figure 7-51
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CapabilityInformation {
+    /// Device able to become the PAN coordinator
+    pub alternate_pan_coordinator: bool,
+    /// Full-function device, reduced-function device otherwise
+    pub full_function_device: bool,
+    /// Device receiving power from the alternating current mains
+    pub mains_powered: bool,
+    /// Receiver kept enabled during idle periods
+    pub rx_on_when_idle: bool,
+    /// Device able to secure MAC frames
+    pub security_capable: bool,
+    /// Device wishing the coordinator to allocate it a short address
+    pub allocate_address: bool,
+}
+
+mod capability {
+    pub const ALTERNATE_PAN_COORDINATOR: u8 = 1 << 0;
+    pub const DEVICE_TYPE: u8 = 1 << 1;
+    pub const POWER_SOURCE: u8 = 1 << 2;
+    pub const RX_ON_WHEN_IDLE: u8 = 1 << 3;
+    pub const SECURITY_CAPABILITY: u8 = 1 << 6;
+    pub const ALLOCATE_ADDRESS: u8 = 1 << 7;
+}
+
+impl CapabilityInformation {
+    /// Reserved bits are ignored.
+    pub const fn from_bits(bits: u8) -> Self {
+        CapabilityInformation {
+            alternate_pan_coordinator: bits & capability::ALTERNATE_PAN_COORDINATOR != 0,
+            full_function_device: bits & capability::DEVICE_TYPE != 0,
+            mains_powered: bits & capability::POWER_SOURCE != 0,
+            rx_on_when_idle: bits & capability::RX_ON_WHEN_IDLE != 0,
+            security_capable: bits & capability::SECURITY_CAPABILITY != 0,
+            allocate_address: bits & capability::ALLOCATE_ADDRESS != 0,
+        }
+    }
+
+    pub const fn bits(&self) -> u8 {
+        (self.alternate_pan_coordinator as u8 * capability::ALTERNATE_PAN_COORDINATOR)
+            | (self.full_function_device as u8 * capability::DEVICE_TYPE)
+            | (self.mains_powered as u8 * capability::POWER_SOURCE)
+            | (self.rx_on_when_idle as u8 * capability::RX_ON_WHEN_IDLE)
+            | (self.security_capable as u8 * capability::SECURITY_CAPABILITY)
+            | (self.allocate_address as u8 * capability::ALLOCATE_ADDRESS)
+    }
+}
+
+/// Size in bytes of the association request content
+pub const ASSOCIATION_REQUEST_SIZE: usize = 1;
+
+/// Association request, sent by a device to the coordinator it wishes to
+/// join with its extended address as source address.
+/// Chapter 7.5.2
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AssociationRequest {
+    pub capability: CapabilityInformation,
+}
+
+impl AssociationRequest {
+    /// # Error
+    ///
+    /// If `data` is not exactly [`ASSOCIATION_REQUEST_SIZE`] bytes long,
+    /// returns `InvalidCommand`.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [capability] => Ok(AssociationRequest {
+                capability: CapabilityInformation::from_bits(*capability),
+            }),
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::AssociationRequest,
+            }),
+        }
+    }
+
+    pub const fn bytes(&self) -> [u8; ASSOCIATION_REQUEST_SIZE] {
+        [self.capability.bits()]
+    }
+}
+
+/// Outcome of an association attempt.
+/// Table 7-51
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssociationStatus {
+    Success,
+    PanAtCapacity,
+    PanAccessDenied,
+    /// Hopping sequence offset already used by another device
+    HoppingSequenceOffsetDuplication,
+    /// Reserved value, only produced in lenient mode and refused by the
+    /// composer
+    Reserved(u8),
+}
+
+impl AssociationStatus {
+    /// # Error
+    ///
+    /// If `value` is reserved, returns `InvalidAssociationStatus`.
+    pub const fn from_byte(value: u8) -> Result<Self, crate::parser::Error> {
+        match value {
+            0x00 => Ok(AssociationStatus::Success),
+            0x01 => Ok(AssociationStatus::PanAtCapacity),
+            0x02 => Ok(AssociationStatus::PanAccessDenied),
+            0x03 => Ok(AssociationStatus::HoppingSequenceOffsetDuplication),
+            _ => Err(crate::parser::Error::InvalidAssociationStatus(value)),
+        }
+    }
+
+    /// Same as [`AssociationStatus::from_byte`], reserved values being
+    /// returned as [`AssociationStatus::Reserved`] in lenient mode.
+    pub const fn from_byte_with_mode(
+        value: u8,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match (Self::from_byte(value), mode) {
+            (Err(_), ParseMode::Lenient) => Ok(AssociationStatus::Reserved(value)),
+            (result, _) => result,
+        }
+    }
+
+    /// # Error
+    ///
+    /// If the status is reserved, returns `InvalidAssociationStatus`.
+    pub const fn bits(&self) -> Result<u8, crate::composer::Error> {
+        Ok(match self {
+            AssociationStatus::Success => 0x00,
+            AssociationStatus::PanAtCapacity => 0x01,
+            AssociationStatus::PanAccessDenied => 0x02,
+            AssociationStatus::HoppingSequenceOffsetDuplication => 0x03,
+            AssociationStatus::Reserved(value) => match Self::from_byte(*value) {
+                Ok(_) => *value,
+                Err(_) => return Err(crate::composer::Error::InvalidAssociationStatus(*value)),
+            },
+        })
+    }
+}
+
+/// Size in bytes of the association response content
+pub const ASSOCIATION_RESPONSE_SIZE: usize = 3;
+
+/// Association response, sent by the coordinator to the extended address
+/// of the device that requested association.
+/// Chapter 7.5.3
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssociationResponse {
+    /// Address allocated to the device in the PAN joined. `0xFFFE` if the
+    /// device is to use its extended address, `0xFFFF` if the association
+    /// failed.
+    pub short_address: ShortAddress,
+    pub status: AssociationStatus,
+}
+
+impl AssociationResponse {
+    /// Same as [`AssociationResponse::from_bytes_with_mode`] in strict
+    /// mode.
+    pub const fn from_bytes(data: &[u8], pan: PanId) -> Result<Self, crate::parser::Error> {
+        Self::from_bytes_with_mode(data, pan, ParseMode::Strict)
+    }
+
+    /// The short address takes the PAN ID `pan`.
+    ///
+    /// # Error
+    ///
+    /// If `data` is not exactly [`ASSOCIATION_RESPONSE_SIZE`] bytes long,
+    /// returns `InvalidCommand`. If the status is reserved in strict mode,
+    /// returns `InvalidAssociationStatus`.
+    pub const fn from_bytes_with_mode(
+        data: &[u8],
+        pan: PanId,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match data {
+            [address_low, address_high, status] => {
+                match AssociationStatus::from_byte_with_mode(*status, mode) {
+                    Ok(status) => Ok(AssociationResponse {
+                        short_address: ShortAddress::new(
+                            pan,
+                            u16::from_le_bytes([*address_low, *address_high]),
+                        ),
+                        status,
+                    }),
+                    Err(error) => Err(error),
+                }
+            }
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::AssociationResponse,
+            }),
+        }
+    }
+
+    /// # Error
+    ///
+    /// If the status is reserved, returns `InvalidAssociationStatus`.
+    pub const fn bytes(&self) -> Result<[u8; ASSOCIATION_RESPONSE_SIZE], crate::composer::Error> {
+        let address = self.short_address.value().to_le_bytes();
+
+        match self.status.bits() {
+            Ok(status) => Ok([address[0], address[1], status]),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns the short address allocated to the device, `None` if the
+    /// association failed or if the device is to use its extended address.
+    pub const fn assigned_address(&self) -> Option<ShortAddress> {
+        match self.status {
+            AssociationStatus::Success if self.short_address.is_valid_unicast() => {
+                Some(self.short_address)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reason of a disassociation.
+/// Table 7-52
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisassociationReason {
+    /// The coordinator wishes the device to leave the PAN
+    CoordinatorRequest,
+    /// The device wishes to leave the PAN
+    DeviceRequest,
+    /// Reserved value, only produced in lenient mode and refused by the
+    /// composer
+    Reserved(u8),
+}
+
+impl DisassociationReason {
+    /// # Error
+    ///
+    /// If `value` is reserved, returns `InvalidDisassociationReason`.
+    pub const fn from_byte(value: u8) -> Result<Self, crate::parser::Error> {
+        match value {
+            0x01 => Ok(DisassociationReason::CoordinatorRequest),
+            0x02 => Ok(DisassociationReason::DeviceRequest),
+            _ => Err(crate::parser::Error::InvalidDisassociationReason(value)),
+        }
+    }
+
+    /// Same as [`DisassociationReason::from_byte`], reserved values being
+    /// returned as [`DisassociationReason::Reserved`] in lenient mode.
+    pub const fn from_byte_with_mode(
+        value: u8,
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match (Self::from_byte(value), mode) {
+            (Err(_), ParseMode::Lenient) => Ok(DisassociationReason::Reserved(value)),
+            (result, _) => result,
+        }
+    }
+
+    /// # Error
+    ///
+    /// If the reason is reserved, returns `InvalidDisassociationReason`.
+    pub const fn bits(&self) -> Result<u8, crate::composer::Error> {
+        Ok(match self {
+            DisassociationReason::CoordinatorRequest => 0x01,
+            DisassociationReason::DeviceRequest => 0x02,
+            DisassociationReason::Reserved(value) => match Self::from_byte(*value) {
+                Ok(_) => *value,
+                Err(_) => return Err(crate::composer::Error::InvalidDisassociationReason(*value)),
+            },
+        })
+    }
+}
+
+/// Size in bytes of the disassociation notification content
+pub const DISASSOCIATION_NOTIFICATION_SIZE: usize = 1;
+
+/// Disassociation notification, sent by the coordinator or the device
+/// ending the association, with its extended address as source address.
+/// Chapter 7.5.4
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisassociationNotification {
+    pub reason: DisassociationReason,
+}
+
+impl DisassociationNotification {
+    /// Same as [`DisassociationNotification::from_bytes_with_mode`] in
+    /// strict mode.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        Self::from_bytes_with_mode(data, ParseMode::Strict)
+    }
+
+    /// # Error
+    ///
+    /// If `data` is not exactly [`DISASSOCIATION_NOTIFICATION_SIZE`] bytes
+    /// long, returns `InvalidCommand`. If the reason is reserved in strict
+    /// mode, returns `InvalidDisassociationReason`.
+    pub const fn from_bytes_with_mode(
+        data: &[u8],
+        mode: ParseMode,
+    ) -> Result<Self, crate::parser::Error> {
+        match data {
+            [reason] => match DisassociationReason::from_byte_with_mode(*reason, mode) {
+                Ok(reason) => Ok(DisassociationNotification { reason }),
+                Err(error) => Err(error),
+            },
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::DisassociationNotification,
+            }),
+        }
+    }
+
+    /// # Error
+    ///
+    /// If the reason is reserved, returns `InvalidDisassociationReason`.
+    pub const fn bytes(
+        &self,
+    ) -> Result<[u8; DISASSOCIATION_NOTIFICATION_SIZE], crate::composer::Error> {
+        match self.reason.bits() {
+            Ok(reason) => Ok([reason]),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Data request, sent by a device polling its coordinator for pending data.
+/// The command has no content. The destination address may be left out if
+/// the coordinator is the PAN coordinator.
+/// Chapter 7.5.5
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DataRequest;
+
+impl DataRequest {
+    /// # Error
+    ///
+    /// If `data` is not empty, returns `InvalidCommand`.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [] => Ok(DataRequest),
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::DataRequest,
+            }),
+        }
+    }
+
+    /// Returns the unsecured IEEE 802.15.4-2003 data request sent by `me` to
+    /// `coordinator`, requesting an acknowledgment. The source PAN ID is
+    /// elided when both addresses share their PAN ID.
+    pub fn poll(coordinator: AddressKind, me: AddressKind, seq: u8) -> Frame<'static> {
+        Frame::MacCommand(command_frame(
+            CommandId::DataRequest,
+            AddressField::Present(coordinator),
+            Some(me),
+            seq,
+        ))
+    }
+}
+
+/// PAN ID conflict notification, sent by a device to the extended address of
+/// its PAN coordinator with its own extended address. The command has no
+/// content.
+/// Chapter 7.5.6
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PanIdConflictNotification;
+
+impl PanIdConflictNotification {
+    /// # Error
+    ///
+    /// If `data` is not empty, returns `InvalidCommand`.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [] => Ok(PanIdConflictNotification),
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::PanIdConflictNotification,
+            }),
+        }
+    }
+}
+
+/// Orphan notification, sent by a device having lost its coordinator with
+/// its extended address. The destination address is either absent or the
+/// broadcast short address in the broadcast PAN. The command has no
+/// content.
+/// Chapter 7.5.7
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OrphanNotification;
+
+impl OrphanNotification {
+    /// # Error
+    ///
+    /// If `data` is not empty, returns `InvalidCommand`.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [] => Ok(OrphanNotification),
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::OrphanNotification,
+            }),
+        }
+    }
+}
+
+/// Beacon request of an IEEE 802.15.4-2003 or 2006 frame, broadcast to
+/// every coordinator in range during an active scan, without source
+/// address. The command has no content.
+/// Chapter 7.5.8
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BeaconRequest {
+    pub seq_no: u8,
+}
+
+impl BeaconRequest {
+    pub const fn new(seq_no: u8) -> Self {
+        BeaconRequest { seq_no }
+    }
+}
+
+/// Returns the unsecured IEEE 802.15.4-2003 beacon request.
+impl From<BeaconRequest> for Frame<'static> {
+    fn from(request: BeaconRequest) -> Self {
+        Frame::MacCommand(command_frame(
+            CommandId::BeaconRequest,
+            AddressField::Present(AddressKind::Short(ShortAddress::broadcast())),
+            None,
+            request.seq_no,
+        ))
+    }
+}
+
+/// Beacon request of an IEEE 802.15.4-2015 frame, requesting enhanced
+/// beacons. Its payload IEs, such as the EB Filter IE, select the
+/// coordinators that answer.
+/// Chapter 7.5.8
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnhancedBeaconRequest<'a> {
+    /// `None` if the sequence number is suppressed
+    pub seq_no: Option<u8>,
+    pub payload_ies: PayloadIeIterator<'a>,
+}
+
+impl<'a> EnhancedBeaconRequest<'a> {
+    pub const fn new(seq_no: u8, payload_ies: PayloadIeIterator<'a>) -> Self {
+        EnhancedBeaconRequest {
+            seq_no: Some(seq_no),
+            payload_ies,
+        }
+    }
+}
+
+/// Returns the unsecured enhanced beacon request.
+impl<'a> From<EnhancedBeaconRequest<'a>> for Frame<'a> {
+    fn from(request: EnhancedBeaconRequest<'a>) -> Self {
+        let mut frame: MacCommandFrame<'a> = command_frame(
+            CommandId::BeaconRequest,
+            AddressField::Present(AddressKind::Short(ShortAddress::broadcast())),
+            None,
+            0,
+        );
+        if let Some(control) = frame.header.control.as_standard_mut() {
+            control.version = FrameVersion::Ieee802154;
+            control.seq_no_present = request.seq_no.is_some();
+            control.ie_present = !request.payload_ies.as_bytes().is_empty();
+        }
+        frame.header.seq_no = request.seq_no;
+        frame.payload_ies = request.payload_ies;
+
+        Frame::MacCommand(frame)
+    }
+}
+
+/// Size in bytes of the coordinator realignment content, without channel
+/// page
+pub const COORDINATOR_REALIGNMENT_SIZE: usize = 7;
+
+/// Coordinator realignment, broadcast by a coordinator changing its
+/// superframe configuration or sent to the extended address of an orphaned
+/// device, in the broadcast PAN.
+/// Chapter 7.5.10
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordinatorRealignment {
+    /// PAN ID the coordinator intends to use
+    pub pan_id: PanId,
+    pub coordinator_short_address: u16,
+    /// Channel the coordinator intends to use
+    pub channel_number: u8,
+    /// Short address of the orphaned device, `0xFFFF` when broadcast
+    pub short_address: u16,
+    /// Channel page the coordinator intends to use, `None` if the field is
+    /// left out
+    pub channel_page: Option<u8>,
+}
+
+impl CoordinatorRealignment {
+    /// # Error
+    ///
+    /// If `data` is neither [`COORDINATOR_REALIGNMENT_SIZE`] bytes long nor
+    /// one more with the channel page, returns `InvalidCommand`.
+    pub const fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        let (fields, channel_page) = match data {
+            [fields @ .., channel_page] if fields.len() == COORDINATOR_REALIGNMENT_SIZE => {
+                (fields, Some(*channel_page))
+            }
+            fields => (fields, None),
+        };
+
+        match fields {
+            [pan_0, pan_1, coordinator_0, coordinator_1, channel_number, address_0, address_1] => {
+                Ok(CoordinatorRealignment {
+                    pan_id: PanId::new(u16::from_le_bytes([*pan_0, *pan_1])),
+                    coordinator_short_address: u16::from_le_bytes([*coordinator_0, *coordinator_1]),
+                    channel_number: *channel_number,
+                    short_address: u16::from_le_bytes([*address_0, *address_1]),
+                    channel_page,
+                })
+            }
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::CoordinatorRealignment,
+            }),
+        }
+    }
+
+    /// Writes the content into `buf`, and returns the number of bytes
+    /// written.
+    ///
+    /// # Error
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let [pan_low, pan_high] = self.pan_id.value().to_le_bytes();
+        let [coordinator_low, coordinator_high] = self.coordinator_short_address.to_le_bytes();
+        let [address_low, address_high] = self.short_address.to_le_bytes();
+        let content = [
+            pan_low,
+            pan_high,
+            coordinator_low,
+            coordinator_high,
+            self.channel_number,
+            address_low,
+            address_high,
+            self.channel_page.unwrap_or_default(),
+        ];
+        let len = COORDINATOR_REALIGNMENT_SIZE + self.channel_page.is_some() as usize;
+
+        write_content(&content[..len], buf)
+    }
+
+    /// Short address of the coordinator, in its new PAN.
+    pub const fn coordinator_address(&self) -> ShortAddress {
+        ShortAddress::new(self.pan_id, self.coordinator_short_address)
+    }
+
+    /// Short address of the orphaned device, in the new PAN of its
+    /// coordinator.
+    pub const fn orphan_address(&self) -> ShortAddress {
+        ShortAddress::new(self.pan_id, self.short_address)
+    }
+}
+
+/// Returns the unsecured IEEE 802.15.4-2003 command frame without content
+/// from `src_addr` to `dst_addr`, requesting an acknowledgment unless sent
+/// to the broadcast address.
+fn command_frame(
+    command_id: CommandId,
+    dst_addr: AddressField,
+    src_addr: Option<AddressKind>,
+    seq_no: u8,
+) -> MacCommandFrame<'static> {
+    let version = FrameVersion::Ieee802154_2003;
+
+    MacCommandFrame {
+        header: MacHeader {
+            control: StandardControlField {
+                frame_kind: FrameKind::MacCommand,
+                security_enabled: false,
+                frame_pending: false,
+                ack_required: !dst_addr.is_broadcast(),
+                pan_id_compression: pan_id_compression(&version, &dst_addr, src_addr.as_ref()),
+                seq_no_present: true,
+                ie_present: false,
+                version,
+                dst_addressing_mode: dst_addr.addressing_mode().bits(),
+                src_addressing_mode: AddressingMode::of(&src_addr).bits(),
+            }
+            .into(),
+            seq_no: Some(seq_no),
+            dst_addr,
+            src_addr,
+            aux: None,
+            header_ies: HeaderIeIterator::new(&[]),
+        },
+        payload_ies: PayloadIeIterator::new(&[]),
+        command_id,
+        content: &[],
+        mic: &[],
+    }
+}
+
+/// MAC command interpreted according to its identifier.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command<'a> {
+    AssociationRequest(AssociationRequest),
+    AssociationResponse(AssociationResponse),
+    DisassociationNotification(DisassociationNotification),
+    DataRequest(DataRequest),
+    PanIdConflictNotification(PanIdConflictNotification),
+    OrphanNotification(OrphanNotification),
+    BeaconRequest(BeaconRequest),
+    EnhancedBeaconRequest(EnhancedBeaconRequest<'a>),
+    CoordinatorRealignment(CoordinatorRealignment),
+    /// Command not modelled by the crate, kept verbatim
+    Unknown {
+        id: CommandId,
+        content: &'a [u8],
+    },
+}
+
+impl<'a> Command<'a> {
+    pub const fn command_id(&self) -> CommandId {
+        match self {
+            Command::AssociationRequest(_) => CommandId::AssociationRequest,
+            Command::AssociationResponse(_) => CommandId::AssociationResponse,
+            Command::DisassociationNotification(_) => CommandId::DisassociationNotification,
+            Command::DataRequest(_) => CommandId::DataRequest,
+            Command::PanIdConflictNotification(_) => CommandId::PanIdConflictNotification,
+            Command::OrphanNotification(_) => CommandId::OrphanNotification,
+            Command::BeaconRequest(_) | Command::EnhancedBeaconRequest(_) => {
+                CommandId::BeaconRequest
+            }
+            Command::CoordinatorRealignment(_) => CommandId::CoordinatorRealignment,
+            Command::Unknown { id, .. } => *id,
+        }
+    }
+
+    /// Writes the command content, identifier excluded, into `buf`, and
+    /// returns the number of bytes written. Unknown commands are written
+    /// back verbatim.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If a field holds a
+    /// reserved value, returns the matching error.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        match self {
+            Command::AssociationRequest(request) => write_content(&request.bytes(), buf),
+            Command::AssociationResponse(response) => write_content(&response.bytes()?, buf),
+            Command::DisassociationNotification(notification) => {
+                write_content(&notification.bytes()?, buf)
+            }
+            Command::DataRequest(_)
+            | Command::PanIdConflictNotification(_)
+            | Command::OrphanNotification(_)
+            | Command::BeaconRequest(_)
+            | Command::EnhancedBeaconRequest(_) => write_content(&[], buf),
+            Command::CoordinatorRealignment(realignment) => realignment.write_into(buf),
+            Command::Unknown { content, .. } => write_content(content, buf),
+        }
+    }
+}
+
+impl From<AssociationRequest> for Command<'_> {
+    fn from(request: AssociationRequest) -> Self {
+        Command::AssociationRequest(request)
+    }
+}
+
+impl From<AssociationResponse> for Command<'_> {
+    fn from(response: AssociationResponse) -> Self {
+        Command::AssociationResponse(response)
+    }
+}
+
+impl From<DisassociationNotification> for Command<'_> {
+    fn from(notification: DisassociationNotification) -> Self {
+        Command::DisassociationNotification(notification)
+    }
+}
+
+impl From<DataRequest> for Command<'_> {
+    fn from(request: DataRequest) -> Self {
+        Command::DataRequest(request)
+    }
+}
+
+impl From<PanIdConflictNotification> for Command<'_> {
+    fn from(notification: PanIdConflictNotification) -> Self {
+        Command::PanIdConflictNotification(notification)
+    }
+}
+
+impl From<OrphanNotification> for Command<'_> {
+    fn from(notification: OrphanNotification) -> Self {
+        Command::OrphanNotification(notification)
+    }
+}
+
+impl From<BeaconRequest> for Command<'_> {
+    fn from(request: BeaconRequest) -> Self {
+        Command::BeaconRequest(request)
+    }
+}
+
+impl<'a> From<EnhancedBeaconRequest<'a>> for Command<'a> {
+    fn from(request: EnhancedBeaconRequest<'a>) -> Self {
+        Command::EnhancedBeaconRequest(request)
+    }
+}
+
+impl From<CoordinatorRealignment> for Command<'_> {
+    fn from(realignment: CoordinatorRealignment) -> Self {
+        Command::CoordinatorRealignment(realignment)
+    }
+}
+
+fn write_content(content: &[u8], buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+    buf.get_mut(..content.len())
+        .ok_or(crate::composer::Error::BufferTooSmall {
+            needed: content.len(),
+        })?
+        .copy_from_slice(content);
+
+    Ok(content.len())
+}
+
+/// MAC command frame.
+/// Chapter 7.3.4
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacCommandFrame<'a> {
+    pub header: MacHeader<'a>,
+    /// Payload IEs, present when the header IEs end with a Header
+    /// Termination 1 IE
+    pub payload_ies: PayloadIeIterator<'a>,
+    pub command_id: CommandId,
+    /// Command content, following the command identifier
+    pub content: &'a [u8],
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
+
+impl<'a> MacCommandFrame<'a> {
+    /// Same as [`MacCommandFrame::command_with_mode`] in strict mode.
+    pub fn command(&self) -> Result<Command<'a>, crate::parser::Error> {
+        self.command_with_mode(ParseMode::Strict)
+    }
+
+    /// Interprets the content according to the command identifier. Commands
+    /// not modelled by the crate are returned as [`Command::Unknown`]. The
+    /// content of a secured frame must have been unsecured first.
+    ///
+    /// Short addresses of the content take the PAN ID of the destination,
+    /// or of the source if there is no destination PAN ID.
+    ///
+    /// # Error
+    ///
+    /// If the content of a modelled command is malformed, returns
+    /// `InvalidCommand`. If it holds a reserved value in strict mode,
+    /// returns the matching error.
+    pub fn command_with_mode(&self, mode: ParseMode) -> Result<Command<'a>, crate::parser::Error> {
+        match self.command_id {
+            CommandId::AssociationRequest => {
+                AssociationRequest::from_bytes(self.content).map(Command::AssociationRequest)
+            }
+            CommandId::AssociationResponse => {
+                AssociationResponse::from_bytes_with_mode(self.content, self.pan_id(), mode)
+                    .map(Command::AssociationResponse)
+            }
+            CommandId::DisassociationNotification => {
+                DisassociationNotification::from_bytes_with_mode(self.content, mode)
+                    .map(Command::DisassociationNotification)
+            }
+            CommandId::DataRequest => {
+                DataRequest::from_bytes(self.content).map(Command::DataRequest)
+            }
+            CommandId::PanIdConflictNotification => {
+                PanIdConflictNotification::from_bytes(self.content)
+                    .map(Command::PanIdConflictNotification)
+            }
+            CommandId::OrphanNotification => {
+                OrphanNotification::from_bytes(self.content).map(Command::OrphanNotification)
+            }
+            CommandId::BeaconRequest => self.beacon_request(),
+            CommandId::CoordinatorRealignment => CoordinatorRealignment::from_bytes(self.content)
+                .map(Command::CoordinatorRealignment),
+            id => Ok(Command::Unknown {
+                id,
+                content: self.content,
+            }),
+        }
+    }
+
+    /// Beacon requests of IEEE 802.15.4-2015 frames are enhanced ones, the
+    /// older frames always carrying a sequence number.
+    fn beacon_request(&self) -> Result<Command<'a>, crate::parser::Error> {
+        match (
+            self.content,
+            self.header.control.version(),
+            self.header.seq_no,
+        ) {
+            ([], Some(FrameVersion::Ieee802154), seq_no) => {
+                Ok(Command::EnhancedBeaconRequest(EnhancedBeaconRequest {
+                    seq_no,
+                    payload_ies: self.payload_ies.clone(),
+                }))
+            }
+            ([], _, Some(seq_no)) => Ok(Command::BeaconRequest(BeaconRequest { seq_no })),
+            _ => Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::BeaconRequest,
+            }),
+        }
+    }
+
+    fn pan_id(&self) -> PanId {
+        match (self.header.dst_addr.pan_id(), &self.header.src_addr) {
+            (Some(pan), _) => pan,
+            (None, Some(src)) => src.pan_id(),
+            (None, None) => PanId::broadcast(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::{ComposeInto, Composer},
+        ieee802154::{
+            address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+            fcs::FcsPolicy,
+            frame::Frame,
+            ie::payload::{self, PayloadIe, PayloadIeGroup, PayloadIeIterator},
+            Ieee802154,
+        },
+        parser::{ParseError, ParseMode, Parser, Section},
+    };
+
+    use super::{
+        AssociationRequest, AssociationResponse, AssociationStatus, BeaconRequest,
+        CapabilityInformation, Command, CommandId, CoordinatorRealignment, DataRequest,
+        DisassociationNotification, DisassociationReason, EnhancedBeaconRequest, MacCommandFrame,
+        OrphanNotification, PanIdConflictNotification,
+    };
+
+    #[test]
+    fn data_request_round_trip() {
+        let input: [u8; 10] = [0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x04];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+
+        assert_eq!(command.command_id, CommandId::DataRequest);
+        assert!(command.content.is_empty());
+        assert_eq!(command.command(), Ok(Command::DataRequest(DataRequest)));
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn data_request_poll() {
+        let pan = PanId::new(0x1234);
+        let coordinator = AddressKind::Short(ShortAddress::new(pan, 0x0000));
+
+        // Short address of the device
+        let frame = DataRequest::poll(coordinator, ShortAddress::new(pan, 0xABCD).into(), 0x2A);
+        assert_eq!(
+            Ieee802154::compose(&frame).unwrap(),
+            [0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x04]
+        );
+        assert_eq!(
+            Ieee802154::parse(&Ieee802154::compose(&frame).unwrap()),
+            Ok(frame)
+        );
+
+        // Extended address of a device not yet allocated a short address
+        let frame = DataRequest::poll(
+            coordinator,
+            LongAddress::new(pan, 0x0102_0304_0506_0708).into(),
+            0x2B,
+        );
+        assert_eq!(
+            Ieee802154::compose(&frame).unwrap(),
+            [
+                0x63, 0xC8, 0x2B, 0x34, 0x12, 0x00, 0x00, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+                0x01, 0x04
+            ]
+        );
+
+        // Device polling a coordinator of another PAN
+        let frame = DataRequest::poll(
+            coordinator,
+            ShortAddress::new(PanId::new(0x4321), 0xABCD).into(),
+            0x2C,
+        );
+        assert_eq!(
+            Ieee802154::compose(&frame).unwrap(),
+            [0x23, 0x88, 0x2C, 0x34, 0x12, 0x00, 0x00, 0x21, 0x43, 0xCD, 0xAB, 0x04]
+        );
+
+        assert_eq!(
+            DataRequest::from_bytes(&[0x00]),
+            Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::DataRequest
+            })
+        );
+    }
+
+    #[test]
+    fn beacon_request_round_trip() {
+        let input: [u8; 8] = [0x03, 0x08, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x07];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+
+        assert_eq!(command.command_id, CommandId::BeaconRequest);
+        assert_eq!(
+            command.command(),
+            Ok(Command::BeaconRequest(BeaconRequest::new(0x2A)))
+        );
+        assert_eq!(
+            command.header.dst_addr,
+            AddressField::Present(AddressKind::Short(ShortAddress::broadcast()))
+        );
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn command_with_content_round_trip() {
+        // Association response, from a coordinator to a device
+        let input: [u8; 25] = [
+            0x63, 0xCC, 0x11, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x18,
+            0x17, 0x16, 0x15, 0x14, 0x13, 0x12, 0x11, 0x02, 0x01, 0x00, 0x00,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+
+        assert_eq!(command.command_id, CommandId::AssociationResponse);
+        assert_eq!(command.content, &[0x01, 0x00, 0x00]);
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn orphan_notification_round_trip() {
+        // Orphan notification without destination address
+        let input: [u8; 14] = [
+            0x03, 0xD0, 0x2A, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x06,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+
+        assert_eq!(command.command_id, CommandId::OrphanNotification);
+        assert_eq!(
+            command.command(),
+            Ok(Command::OrphanNotification(OrphanNotification))
+        );
+        assert_eq!(command.header.dst_addr, AddressField::Absent);
+        assert_eq!(
+            command.header.source_only(),
+            Some(&AddressKind::Long(LongAddress::new(
+                PanId::new(0x1234),
+                0x0102_0304_0506_0708
+            )))
+        );
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn beacon_request() {
+        let frame = BeaconRequest::new(0x2A).into();
+        let mut buf = [0u8; 10];
+
+        let len = Ieee802154::compose_into(&frame, &mut buf).unwrap();
+        assert_eq!(len, 8);
+        assert_eq!(FcsPolicy::Crc16.append(&mut buf, len), Ok(10));
+        assert_eq!(
+            buf,
+            [0x03, 0x08, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x07, 0x56, 0x85]
+        );
+        assert_eq!(Ieee802154::parse(&buf[..len]), Ok(frame));
+    }
+
+    #[test]
+    fn enhanced_beacon_request() {
+        // MLME IE holding an EB Filter IE, requesting beacons of coordinators
+        // permitting joining
+        let mut ies = [0u8; 5];
+        let ies_len = payload::write_ies(
+            &[PayloadIe {
+                group: PayloadIeGroup::Mlme,
+                content: &[0x01, 0x1E, 0x01],
+            }],
+            &mut ies,
+        )
+        .unwrap();
+        let request = EnhancedBeaconRequest::new(0x2B, PayloadIeIterator::new(&ies[..ies_len]));
+
+        let composed = Ieee802154::compose(&request.clone().into()).unwrap();
+        assert_eq!(
+            composed,
+            [
+                0x03, 0x2A, 0x2B, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x3F, 0x03, 0x88, 0x01, 0x1E, 0x01,
+                0x00, 0xF8, 0x07
+            ]
+        );
+
+        let Frame::MacCommand(command) = Ieee802154::parse(&composed).unwrap() else {
+            panic!("expected a MAC command frame");
+        };
+        let Ok(Command::EnhancedBeaconRequest(parsed)) = command.command() else {
+            panic!("expected an enhanced beacon request");
+        };
+        assert_eq!(parsed.seq_no, Some(0x2B));
+        assert!(parsed.payload_ies.eq(request.payload_ies));
+
+        // Sequence number suppressed, without IE
+        let request = EnhancedBeaconRequest {
+            seq_no: None,
+            payload_ies: PayloadIeIterator::new(&[]),
+        };
+        let frame = request.clone().into();
+        let composed = Ieee802154::compose(&frame).unwrap();
+        assert_eq!(composed, [0x03, 0x29, 0xFF, 0xFF, 0xFF, 0xFF, 0x07]);
+
+        let Frame::MacCommand(command) = Ieee802154::parse(&composed).unwrap() else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::EnhancedBeaconRequest(request))
+        );
+
+        // Sent to a given coordinator
+        let Frame::MacCommand(mut invalid) = frame else {
+            panic!("expected a MAC command frame");
+        };
+        invalid.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0x0000,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+    }
+
+    #[test]
+    fn orphan_notification() {
+        let src = LongAddress::new(PanId::broadcast(), 0x0102_0304_0506_0708);
+
+        // Broadcast by an IEEE 802.15.4-2003 device
+        let input: [u8; 16] = [
+            0x43, 0xC8, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x06,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::OrphanNotification(OrphanNotification))
+        );
+        assert_eq!(command.header.dst_addr.pan_id(), Some(PanId::broadcast()));
+        assert_eq!(command.header.src_addr, Some(AddressKind::Long(src)));
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+
+        // Sent without destination address by an IEEE 802.15.4-2015 device
+        let input: [u8; 14] = [
+            0x03, 0xE0, 0x2B, 0xFF, 0xFF, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x06,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(command.header.source_only(), Some(&AddressKind::Long(src)));
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+
+        // Sent to the coordinator, or with a short address
+        let mut invalid = command.clone();
+        invalid.header.dst_addr =
+            AddressField::Present(AddressKind::Short(ShortAddress::new(PanId::broadcast(), 0)));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        let mut invalid = command.clone();
+        invalid.header.src_addr = Some(AddressKind::Short(ShortAddress::new(
+            PanId::broadcast(),
+            0x0001,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        assert_eq!(
+            OrphanNotification::from_bytes(&[0x00]),
+            Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::OrphanNotification
+            })
+        );
+    }
+
+    #[test]
+    fn pan_id_conflict_notification() {
+        // Sent by a device to its PAN coordinator
+        let input: [u8; 22] = [
+            0x63, 0xCC, 0x2A, 0x34, 0x12, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x08,
+            0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x05,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::PanIdConflictNotification(
+                PanIdConflictNotification
+            ))
+        );
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+
+        // Short address of the PAN coordinator
+        let mut invalid = command.clone();
+        invalid.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0x0000,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        let mut invalid = command.clone();
+        invalid.header.dst_addr = AddressField::Absent;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn coordinator_realignment() {
+        // Broadcast after a PAN ID change, without channel page
+        let input: [u8; 25] = [
+            0x03, 0xC8, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04,
+            0x03, 0x02, 0x01, 0x08, 0x78, 0x56, 0x00, 0x00, 0x0B, 0xFF, 0xFF,
+        ];
+        let realignment = CoordinatorRealignment {
+            pan_id: PanId::new(0x5678),
+            coordinator_short_address: 0x0000,
+            channel_number: 11,
+            short_address: 0xFFFF,
+            channel_page: None,
+        };
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::CoordinatorRealignment(realignment))
+        );
+        assert_eq!(
+            realignment.coordinator_address(),
+            ShortAddress::new(PanId::new(0x5678), 0x0000)
+        );
+
+        let mut content = [0u8; 8];
+        let len = Command::from(realignment).write_into(&mut content).unwrap();
+        assert_eq!(len, 7);
+        let composed = MacCommandFrame {
+            content: &content[..len],
+            ..command.clone()
+        };
+        assert_eq!(
+            Ieee802154::compose(&Frame::MacCommand(composed)).unwrap(),
+            input
+        );
+
+        // Sent to an orphaned device, with channel page
+        let input: [u8; 32] = [
+            0x23, 0xDC, 0x2B, 0xFF, 0xFF, 0x18, 0x17, 0x16, 0x15, 0x14, 0x13, 0x12, 0x11, 0x34,
+            0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x08, 0x34, 0x12, 0x00, 0x00,
+            0x0F, 0x01, 0x00, 0x00,
+        ];
+        let realignment = CoordinatorRealignment {
+            pan_id: PanId::new(0x1234),
+            coordinator_short_address: 0x0000,
+            channel_number: 15,
+            short_address: 0x0001,
+            channel_page: Some(0),
+        };
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::CoordinatorRealignment(realignment))
+        );
+        assert_eq!(
+            realignment.orphan_address(),
+            ShortAddress::new(PanId::new(0x1234), 0x0001)
+        );
+
+        let len = Command::from(realignment).write_into(&mut content).unwrap();
+        assert_eq!(&content[..len], command.content);
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+
+        // Orphaned device addressed in its PAN
+        let mut invalid = command.clone();
+        invalid.header.dst_addr = AddressField::Present(AddressKind::Long(LongAddress::new(
+            PanId::new(0x1234),
+            0x1112_1314_1516_1718,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        let mut invalid = command.clone();
+        invalid.header.src_addr = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+
+        for len in [6, 9] {
+            assert_eq!(
+                CoordinatorRealignment::from_bytes(&[0u8; 9][..len]),
+                Err(crate::parser::Error::InvalidCommand {
+                    id: CommandId::CoordinatorRealignment
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_addressing() {
+        let input: [u8; 8] = [0x03, 0x08, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x07];
+        let Frame::MacCommand(mut command) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a MAC command frame");
+        };
+
+        // Beacon request to a given coordinator
+        command.header.dst_addr = AddressField::Present(AddressKind::Short(ShortAddress::new(
+            PanId::broadcast(),
+            0x0000,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(command.clone())),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        // Data request without source address
+        command.command_id = CommandId::DataRequest;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(command)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn command_id_round_trip() {
+        let mut defined = 0;
+
+        for value in 0..=u8::MAX {
+            let lenient = CommandId::from_byte_with_mode(value, ParseMode::Lenient).unwrap();
+
+            match CommandId::from_byte(value) {
+                Ok(id) => {
+                    defined += 1;
+                    assert_eq!(id, lenient);
+                    assert_eq!(id.bits(), Ok(value));
+                }
+                Err(error) => {
+                    assert_eq!(error, crate::parser::Error::InvalidCommandId(value));
+                    assert_eq!(lenient, CommandId::Unknown(value));
+                    assert_eq!(
+                        lenient.bits(),
+                        Err(crate::composer::Error::InvalidCommandId(value))
+                    );
+                }
+            }
+        }
+
+        assert_eq!(defined, 30);
+        assert_eq!(
+            CommandId::try_from(0x13),
+            Ok(CommandId::DsmeAssociationRequest)
+        );
+        assert_eq!(CommandId::Unknown(0x04).bits(), Ok(0x04));
+    }
+
+    #[test]
+    fn reserved_command_id() {
+        let input: [u8; 10] = [0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x0C];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(
+                crate::parser::Error::InvalidCommandId(0x0C),
+                9,
+                Section::Payload
+            )
+        );
+
+        let frame = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(command.command_id, CommandId::Unknown(0x0C));
+        assert!(matches!(
+            Ieee802154::compose(&frame),
+            Err(crate::composer::Error::InvalidCommandId(0x0C))
+        ));
+    }
+
+    #[test]
+    fn association_request() {
+        // Association request of Annex C, unsecured
+        let input: [u8; 25] = [
+            0x23, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0xFF,
+            0xFF, 0x01, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01, 0xCE,
+        ];
+        let request = AssociationRequest {
+            capability: CapabilityInformation {
+                alternate_pan_coordinator: false,
+                full_function_device: true,
+                mains_powered: true,
+                rx_on_when_idle: true,
+                security_capable: true,
+                allocate_address: true,
+            },
+        };
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(command.command(), Ok(Command::AssociationRequest(request)));
+
+        let mut content = [0u8; 1];
+        assert_eq!(Command::from(request).write_into(&mut content), Ok(1));
+        assert_eq!(
+            Command::from(request).write_into(&mut []),
+            Err(crate::composer::Error::BufferTooSmall { needed: 1 })
+        );
+
+        let composed = MacCommandFrame {
+            content: &content,
+            ..command.clone()
+        };
+        assert_eq!(
+            Ieee802154::compose(&Frame::MacCommand(composed)).unwrap(),
+            input
+        );
+
+        // Reserved bits are ignored
+        assert_eq!(CapabilityInformation::from_bits(0x30), Default::default());
+        assert_eq!(
+            AssociationRequest::from_bytes(&[0xCE, 0x00]),
+            Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::AssociationRequest
+            })
+        );
+    }
+
+    #[test]
+    fn association_request_addressing() {
+        let input: [u8; 25] = [
+            0x23, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0xFF,
+            0xFF, 0x01, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01, 0xCE,
+        ];
+        let Frame::MacCommand(command) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a MAC command frame");
+        };
+        let pan = PanId::new(0x4321);
+
+        // Short source address
+        let mut invalid = command.clone();
+        invalid.header.src_addr = Some(AddressKind::Short(ShortAddress::new(pan, 0x0001)));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        // Broadcast destination
+        let mut invalid = command.clone();
+        invalid.header.dst_addr =
+            AddressField::Present(AddressKind::Short(ShortAddress::new(pan, 0xFFFF)));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        // No destination
+        let mut invalid = command;
+        invalid.header.dst_addr = AddressField::Absent;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn association_response() {
+        // Short address 0x3C1F allocated by a Zigbee coordinator
+        let input: [u8; 25] = [
+            0x63, 0xCC, 0x5A, 0x62, 0x1A, 0x04, 0x03, 0x02, 0x01, 0x00, 0x4B, 0x12, 0x00, 0x0D,
+            0x0C, 0x0B, 0x0A, 0x00, 0x4B, 0x12, 0x00, 0x02, 0x1F, 0x3C, 0x00,
+        ];
+        let pan = PanId::new(0x1A62);
+        let response = AssociationResponse {
+            short_address: ShortAddress::new(pan, 0x3C1F),
+            status: AssociationStatus::Success,
+        };
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::AssociationResponse(response))
+        );
+        assert_eq!(response.assigned_address(), Some(response.short_address));
+
+        let mut content = [0u8; 3];
+        assert_eq!(Command::from(response).write_into(&mut content), Ok(3));
+        let composed = MacCommandFrame {
+            content: &content,
+            ..command.clone()
+        };
+        assert_eq!(
+            Ieee802154::compose(&Frame::MacCommand(composed)).unwrap(),
+            input
+        );
+
+        // Associated without short address, or refused
+        for (content, status) in [
+            ([0xFE, 0xFF, 0x00], AssociationStatus::Success),
+            ([0xFF, 0xFF, 0x01], AssociationStatus::PanAtCapacity),
+            ([0xFF, 0xFF, 0x02], AssociationStatus::PanAccessDenied),
+            (
+                [0xFF, 0xFF, 0x03],
+                AssociationStatus::HoppingSequenceOffsetDuplication,
+            ),
+        ] {
+            let response = AssociationResponse::from_bytes(&content, pan).unwrap();
+
+            assert_eq!(response.status, status);
+            assert_eq!(response.assigned_address(), None);
+            assert_eq!(response.bytes(), Ok(content));
+        }
+
+        // Response without destination, or to a short address
+        let mut invalid = command.clone();
+        invalid.header.dst_addr = AddressField::Absent;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+
+        let mut invalid = command.clone();
+        invalid.header.dst_addr =
+            AddressField::Present(AddressKind::Short(ShortAddress::new(pan, 0x3C1F)));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+    }
+
+    #[test]
+    fn reserved_association_status() {
+        let pan = PanId::new(0x1A62);
+        let content = [0xFF, 0xFF, 0x80];
+
+        assert_eq!(
+            AssociationResponse::from_bytes(&content, pan),
+            Err(crate::parser::Error::InvalidAssociationStatus(0x80))
+        );
+
+        let response =
+            AssociationResponse::from_bytes_with_mode(&content, pan, ParseMode::Lenient).unwrap();
+        assert_eq!(response.status, AssociationStatus::Reserved(0x80));
+        assert_eq!(
+            Command::from(response).write_into(&mut [0u8; 3]),
+            Err(crate::composer::Error::InvalidAssociationStatus(0x80))
+        );
+
+        assert_eq!(
+            AssociationResponse::from_bytes(&content[..2], pan),
+            Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::AssociationResponse
+            })
+        );
+    }
+
+    #[test]
+    fn disassociation_notification() {
+        // Sent by a device leaving the PAN to its coordinator
+        let input: [u8; 17] = [
+            0x63, 0xC8, 0x2A, 0x34, 0x12, 0x00, 0x00, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x03, 0x02,
+        ];
+        let notification = DisassociationNotification {
+            reason: DisassociationReason::DeviceRequest,
+        };
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MacCommand(command) = &frame else {
+            panic!("expected a MAC command frame");
+        };
+        assert_eq!(
+            command.command(),
+            Ok(Command::DisassociationNotification(notification))
+        );
+
+        let mut content = [0u8; 1];
+        assert_eq!(Command::from(notification).write_into(&mut content), Ok(1));
+        let composed = MacCommandFrame {
+            content: &content,
+            ..command.clone()
+        };
+        assert_eq!(
+            Ieee802154::compose(&Frame::MacCommand(composed)).unwrap(),
+            input
+        );
+
+        // Source address other than the extended one
+        let mut invalid = command.clone();
+        invalid.header.src_addr = Some(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0x0001,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+
+        let mut invalid = command.clone();
+        invalid.header.src_addr = None;
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MacCommand(invalid)),
+            Err(crate::composer::Error::MissingAddress)
+        ));
+    }
+
+    #[test]
+    fn reserved_disassociation_reason() {
+        for value in [0x00, 0x03, 0xFF] {
+            assert_eq!(
+                DisassociationNotification::from_bytes(&[value]),
+                Err(crate::parser::Error::InvalidDisassociationReason(value))
+            );
+
+            let notification =
+                DisassociationNotification::from_bytes_with_mode(&[value], ParseMode::Lenient)
+                    .unwrap();
+            assert_eq!(notification.reason, DisassociationReason::Reserved(value));
+            assert_eq!(
+                notification.bytes(),
+                Err(crate::composer::Error::InvalidDisassociationReason(value))
+            );
+        }
+
+        assert_eq!(
+            DisassociationNotification::from_bytes(&[0x01]),
+            Ok(DisassociationNotification {
+                reason: DisassociationReason::CoordinatorRequest
+            })
+        );
+        assert_eq!(
+            DisassociationNotification::from_bytes(&[]),
+            Err(crate::parser::Error::InvalidCommand {
+                id: CommandId::DisassociationNotification
+            })
+        );
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let inputs: [&[u8]; 7] = [
+            // Data request
+            &[
+                0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x04, 0x11, 0x22,
+            ],
+            // Beacon request
+            &[0x03, 0x08, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x07, 0x11, 0x22],
+            // Orphan notification
+            &[
+                0x43, 0xC8, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+                0x01, 0x06, 0x11, 0x22,
+            ],
+            // PAN ID conflict notification
+            &[
+                0x63, 0xCC, 0x2A, 0x34, 0x12, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x08,
+                0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x05, 0x11, 0x22,
+            ],
+            // Association request
+            &[
+                0x23, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0xFF,
+                0xFF, 0x01, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01, 0xCE, 0x11, 0x22,
+            ],
+            // Association response
+            &[
+                0x63, 0xCC, 0x5A, 0x62, 0x1A, 0x04, 0x03, 0x02, 0x01, 0x00, 0x4B, 0x12, 0x00, 0x0D,
+                0x0C, 0x0B, 0x0A, 0x00, 0x4B, 0x12, 0x00, 0x02, 0x1F, 0x3C, 0x00, 0x11, 0x22,
+            ],
+            // Disassociation notification
+            &[
+                0x63, 0xC8, 0x2A, 0x34, 0x12, 0x00, 0x00, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+                0x01, 0x03, 0x02, 0x11, 0x22,
+            ],
+        ];
+
+        for input in inputs {
+            let end = input.len() - 2;
+
+            assert_eq!(
+                Ieee802154::parse(input).unwrap_err(),
+                ParseError::new(
+                    crate::parser::Error::TrailingBytes { count: 2 },
+                    end,
+                    Section::Payload
+                )
+            );
+
+            // The trailing bytes are left out in lenient mode
+            let frame = Ieee802154::parse_with_mode(input, ParseMode::Lenient).unwrap();
+            assert_eq!(Ieee802154::compose(&frame).unwrap(), input[..end]);
+        }
+    }
+
+    #[test]
+    fn truncated_content() {
+        // Each input along with the offset of the command content
+        let inputs: [(&[u8], usize); 2] = [
+            // Association response with one byte of content out of three
+            (
+                &[
+                    0x63, 0xCC, 0x5A, 0x62, 0x1A, 0x04, 0x03, 0x02, 0x01, 0x00, 0x4B, 0x12, 0x00,
+                    0x0D, 0x0C, 0x0B, 0x0A, 0x00, 0x4B, 0x12, 0x00, 0x02, 0x1F,
+                ],
+                22,
+            ),
+            // Disassociation notification without its reason
+            (
+                &[
+                    0x63, 0xC8, 0x2A, 0x34, 0x12, 0x00, 0x00, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
+                    0x02, 0x01, 0x03,
+                ],
+                16,
+            ),
+        ];
+
+        for (input, offset) in inputs {
+            assert_eq!(
+                Ieee802154::parse(input).unwrap_err(),
+                ParseError::new(crate::parser::Error::Truncated, offset, Section::Payload)
+            );
+
+            // The short content is kept in lenient mode
+            let Frame::MacCommand(command) =
+                Ieee802154::parse_with_mode(input, ParseMode::Lenient).unwrap()
+            else {
+                panic!("expected a MAC command frame");
+            };
+            assert_eq!(command.content, &input[offset..]);
+        }
+    }
+
+    #[test]
+    fn missing_command_id() {
+        assert!(Ieee802154::parse(&[0x03, 0x08, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF]).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+use crate::ieee802154::ie::payload::PayloadIeIterator;
+
+use super::MacHeader;
+
+/// Data frame.
+/// Chapter 7.3.2
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFrame<'a> {
+    pub header: MacHeader<'a>,
+    /// Payload IEs, present when the header IEs end with a Header
+    /// Termination 1 IE
+    pub payload_ies: PayloadIeIterator<'a>,
+    pub payload: &'a [u8],
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
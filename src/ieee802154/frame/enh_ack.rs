@@ -0,0 +1,223 @@
+use crate::ieee802154::{
+    address::AddressingMode,
+    control_field::{FrameKind, FrameVersion, StandardControlField},
+    ie::{header::HeaderIeIterator, payload::PayloadIeIterator, time_correction::TimeCorrection},
+};
+
+use super::{Frame, MacHeader};
+
+/// Enhanced acknowledgment frame, sent by frames of version IEEE 802.15.4.
+/// Chapter 7.3.3
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnhAckFrame<'a> {
+    /// MAC header, whose header IEs exclude the Time Correction IE
+    pub header: MacHeader<'a>,
+    /// Time Correction IE, written as the first header IE
+    pub time_correction: Option<TimeCorrection>,
+    /// Payload IEs, present when the header IEs end with a Header
+    /// Termination 1 IE
+    pub payload_ies: PayloadIeIterator<'a>,
+    pub payload: &'a [u8],
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
+
+impl EnhAckFrame<'static> {
+    /// Returns the enhanced acknowledgment of `frame`, carrying the Time
+    /// Correction IE. It is sent to the source of `frame` with the same
+    /// sequence number, or none if it was suppressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidHeader` if `frame` is never acknowledged, that is if
+    /// it is neither a data frame, a MAC command nor a multipurpose frame.
+    pub fn for_frame(
+        frame: &Frame,
+        correction_us: i16,
+        nack: bool,
+    ) -> Result<Self, crate::composer::Error> {
+        let (seq_no, src_addr) = match frame {
+            Frame::Data(data) => (data.header.seq_no, data.header.src_addr),
+            Frame::MacCommand(command) => (command.header.seq_no, command.header.src_addr),
+            Frame::MultiPurpose(multipurpose) => (multipurpose.seq_no, multipurpose.src_addr),
+            _ => return Err(crate::composer::Error::InvalidHeader),
+        };
+
+        Ok(EnhAckFrame {
+            header: MacHeader {
+                control: StandardControlField {
+                    frame_kind: FrameKind::Acknowledgment,
+                    security_enabled: false,
+                    frame_pending: false,
+                    ack_required: false,
+                    pan_id_compression: false,
+                    seq_no_present: seq_no.is_some(),
+                    ie_present: true,
+                    version: FrameVersion::Ieee802154,
+                    dst_addressing_mode: AddressingMode::of(&src_addr).bits(),
+                    src_addressing_mode: AddressingMode::None.bits(),
+                }
+                .into(),
+                seq_no,
+                dst_addr: src_addr.into(),
+                src_addr: None,
+                aux: None,
+                header_ies: HeaderIeIterator::new(&[]),
+            },
+            time_correction: Some(TimeCorrection {
+                correction_us,
+                nack,
+            }),
+            payload_ies: PayloadIeIterator::new(&[]),
+            payload: &[],
+            mic: &[],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+            frame::Frame,
+            ie::time_correction::TimeCorrection,
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    use super::EnhAckFrame;
+
+    #[test]
+    fn for_data_frame() {
+        let data: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let data = Ieee802154::parse(&data).unwrap();
+
+        let ack = EnhAckFrame::for_frame(&data, -100, false).unwrap();
+        let frame = Ieee802154::compose(&Frame::EnhAcknowledgment(ack)).unwrap();
+
+        assert_eq!(
+            frame,
+            [0x02, 0x2A, 0x01, 0x34, 0x12, 0xCD, 0xAB, 0x02, 0x0F, 0x9C, 0x0F]
+        );
+
+        let Frame::EnhAcknowledgment(ack) = Ieee802154::parse(&frame).unwrap() else {
+            panic!("expected an enhanced acknowledgment");
+        };
+        assert_eq!(ack.header.seq_no, Some(0x01));
+        assert_eq!(
+            ack.header.dst_addr,
+            AddressField::Present(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert_eq!(
+            ack.time_correction,
+            Some(TimeCorrection {
+                correction_us: -100,
+                nack: false,
+            })
+        );
+        assert!(ack.header.header_ies.as_bytes().is_empty());
+        assert!(ack.payload.is_empty());
+    }
+
+    #[test]
+    fn for_frame_with_suppressed_seq_no() {
+        let data: [u8; 9] = [0x41, 0xA9, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x42];
+        let data = Ieee802154::parse(&data).unwrap();
+
+        let ack = EnhAckFrame::for_frame(&data, 12, true).unwrap();
+        let frame = Ieee802154::compose(&Frame::EnhAcknowledgment(ack)).unwrap();
+
+        assert_eq!(
+            frame,
+            [0x02, 0x2B, 0x34, 0x12, 0xCD, 0xAB, 0x02, 0x0F, 0x0C, 0x80]
+        );
+    }
+
+    #[test]
+    fn termination_before_payload() {
+        let data: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+        let data = Ieee802154::parse(&data).unwrap();
+
+        let mut ack = EnhAckFrame::for_frame(&data, 0, false).unwrap();
+        ack.payload = &[0x42];
+        let frame = Ieee802154::compose(&Frame::EnhAcknowledgment(ack)).unwrap();
+
+        assert_eq!(
+            frame,
+            [0x02, 0x2A, 0x01, 0x34, 0x12, 0xCD, 0xAB, 0x02, 0x0F, 0x00, 0x00, 0x80, 0x3F, 0x42]
+        );
+
+        let Frame::EnhAcknowledgment(ack) = Ieee802154::parse(&frame).unwrap() else {
+            panic!("expected an enhanced acknowledgment");
+        };
+        assert_eq!(ack.payload, &[0x42]);
+        assert_eq!(
+            Ieee802154::compose(&Frame::EnhAcknowledgment(ack)).unwrap(),
+            frame
+        );
+    }
+
+    #[test]
+    fn for_command_and_multipurpose_frames() {
+        // Data request, then long form multipurpose frame from a long
+        // address
+        let inputs: [(&[u8], AddressKind); 2] = [
+            (
+                &[0x63, 0x88, 0x2A, 0x34, 0x12, 0x00, 0x00, 0xCD, 0xAB, 0x04],
+                AddressKind::Short(ShortAddress::new(PanId::new(0x1234), 0xABCD)),
+            ),
+            (
+                &[
+                    0xED, 0xC1, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03,
+                    0x02, 0x01, 0x81, 0x0E, 0xAB, 0x80, 0x3F, 0x42, 0x43, 0x44,
+                ],
+                AddressKind::Long(LongAddress::new(PanId::broadcast(), 0x0102030405060708)),
+            ),
+        ];
+
+        for (input, src_addr) in inputs {
+            let frame = Ieee802154::parse(input).unwrap();
+
+            let ack = EnhAckFrame::for_frame(&frame, 0, false).unwrap();
+            let frame = Ieee802154::compose(&Frame::EnhAcknowledgment(ack)).unwrap();
+
+            let Frame::EnhAcknowledgment(ack) = Ieee802154::parse(&frame).unwrap() else {
+                panic!("expected an enhanced acknowledgment");
+            };
+            assert_eq!(ack.header.seq_no, Some(0x2A));
+            assert_eq!(ack.header.dst_addr, AddressField::Present(src_addr));
+        }
+    }
+
+    #[test]
+    fn for_other_frames() {
+        // Immediate acknowledgment, then beacon
+        let inputs: [&[u8]; 2] = [
+            &[0x02, 0x00, 0x2A],
+            &[
+                0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+            ],
+        ];
+
+        for input in inputs {
+            let frame = Ieee802154::parse(input).unwrap();
+
+            assert_eq!(
+                EnhAckFrame::for_frame(&frame, 0, false),
+                Err(crate::composer::Error::InvalidHeader)
+            );
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ieee802154::ie::payload::PayloadIeIterator;
+
+use super::{pan_descriptor::PanDescriptor, MacHeader};
+
+/// Enhanced beacon frame, a beacon of version IEEE 802.15.4 carrying IEs in
+/// place of the superframe, GTS and pending address fields.
+/// Chapter 7.3.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnhBeaconFrame<'a> {
+    pub header: MacHeader<'a>,
+    /// Payload IEs, present when the header IEs end with a Header
+    /// Termination 1 IE
+    pub payload_ies: PayloadIeIterator<'a>,
+    /// Beacon payload
+    pub payload: &'a [u8],
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
+
+impl<'a> EnhBeaconFrame<'a> {
+    /// Summary of the beacon, as reported by a scan. Enhanced beacons have
+    /// no superframe specification nor GTS fields.
+    pub fn pan_descriptor(&self) -> PanDescriptor {
+        PanDescriptor::new(&self.header.control, self.header.src_addr, None, false)
+    }
+}
@@ -1,18 +1,380 @@
-use self::beacon::BeaconFrame;
+use self::{
+    ack::AckFrame, beacon::BeaconFrame, command::MacCommandFrame, data::DataFrame,
+    enh_ack::EnhAckFrame, enh_beacon::EnhBeaconFrame, multipurpose::MultipurposeFrame,
+};
 
+use super::{
+    address::{AddressField, AddressKind},
+    control_field::{
+        mask, offset, FragmentControl, FrameKind, FrameVersion, MultipurposeControlField,
+        StandardControlField, STANDARD_CONTROL_FIELD_SIZE,
+    },
+    ie::header::HeaderIeIterator,
+    security_header::AuxiliarySecurityHeader,
+};
+
+pub mod ack;
 pub mod beacon;
+pub mod beacon_builder;
+pub mod command;
+pub mod data;
+pub mod enh_ack;
+pub mod enh_beacon;
+pub mod multipurpose;
+pub mod pan_descriptor;
 
 /// General kind of frames
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
 pub enum Frame<'a> {
     Beacon(BeaconFrame<'a>),
-    EnhBeacon,
-    Data,
-    Acknowledgment,
-    EnhAcknowledgment,
-    MacCommand,
-    MultiPurpose,
+    EnhBeacon(EnhBeaconFrame<'a>),
+    Data(DataFrame<'a>),
+    Acknowledgment(AckFrame),
+    EnhAcknowledgment(EnhAckFrame<'a>),
+    MacCommand(MacCommandFrame<'a>),
+    MultiPurpose(MultipurposeFrame<'a>),
     Frak,
     Extended,
+    /// Frame of a reserved type, only produced in lenient mode
+    Reserved(ReservedFrame<'a>),
+}
+
+impl<'a> Frame<'a> {
+    /// Frame control field of the frame, `None` for fragment and extended
+    /// frames which are not decoded.
+    pub fn control(&self) -> Option<ControlField> {
+        let control = match self {
+            Frame::Beacon(beacon) => beacon.header.control.clone(),
+            Frame::EnhBeacon(beacon) => beacon.header.control.clone(),
+            Frame::Data(data) => data.header.control.clone(),
+            Frame::Acknowledgment(ack) => {
+                StandardControlField::new(FrameKind::Acknowledgment, ack.version)
+                    .with_frame_pending(ack.frame_pending)
+                    .into()
+            }
+            Frame::EnhAcknowledgment(ack) => ack.header.control.clone(),
+            Frame::MacCommand(command) => command.header.control.clone(),
+            Frame::MultiPurpose(frame) => frame.control.clone().into(),
+            Frame::Reserved(frame) => frame.control.clone().into(),
+            Frame::Frak | Frame::Extended => return None,
+        };
+
+        Some(control)
+    }
+}
+
+/// MAC header of frames using the standard frame control field.
+/// Chapter 7.2.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacHeader<'a> {
+    /// Always [`ControlField::Standard`] for parsed frames
+    pub control: ControlField,
+    /// `None` if the sequence number is suppressed
+    pub seq_no: Option<u8>,
+    pub dst_addr: AddressField,
+    pub src_addr: Option<AddressKind>,
+    /// Present if `security_enabled` is set
+    pub aux: Option<AuxiliarySecurityHeader>,
+    /// Header IEs, empty if `ie_present` is not set
+    pub header_ies: HeaderIeIterator<'a>,
+}
+
+impl<'a> MacHeader<'a> {
+    /// Source address of frames carrying no destination address, only
+    /// accepted by the PAN coordinator of the source PAN.
+    /// Chapter 6.7.2
+    pub const fn source_only(&self) -> Option<&AddressKind> {
+        match (&self.dst_addr, &self.src_addr) {
+            (AddressField::Absent, Some(src)) => Some(src),
+            _ => None,
+        }
+    }
+}
+
+/// Frame of a reserved type. Its layout being unknown, only the frame control
+/// field is decoded.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedFrame<'a> {
+    pub control: StandardControlField,
+    /// Everything following the frame control field
+    pub data: &'a [u8],
+}
+
+/// Frame control field, whose layout depends on the frame type.
+/// Chapter 7.2.2
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlField {
+    /// Beacon, data, acknowledgment and MAC command frames
+    Standard(StandardControlField),
+    Multipurpose(MultipurposeControlField),
+    /// Fragment and Frak frames
+    Fragment(FragmentControl),
+    /// First octet of extended frames, whose layout is not decoded
+    Extended(u8),
+}
+
+impl ControlField {
+    /// Decodes the frame control field at the start of `data`, with the
+    /// layout given by its frame type.
+    ///
+    /// # Error
+    ///
+    /// Fails as [`StandardControlField::from_bytes`] or
+    /// [`MultipurposeControlField::from_bytes`] would. If `data` is too
+    /// short, returns `Truncated`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        let first = *data.first().ok_or(crate::parser::Error::Truncated)?;
+        let kind =
+            FrameKind::from_byte(((first as u16 & mask::FRAME_KIND) >> offset::FRAME_KIND) as u8)?;
+
+        match kind {
+            FrameKind::MultiPurpose => {
+                return MultipurposeControlField::from_bytes(data).map(ControlField::Multipurpose)
+            }
+            FrameKind::Frak => {
+                return Ok(ControlField::Fragment(FragmentControl::from_byte(first)))
+            }
+            FrameKind::Extended => return Ok(ControlField::Extended(first)),
+            _ => {}
+        }
+
+        let data = data
+            .get(..STANDARD_CONTROL_FIELD_SIZE)
+            .and_then(|data| data.try_into().ok())
+            .ok_or(crate::parser::Error::Truncated)?;
+
+        StandardControlField::from_bytes(data).map(ControlField::Standard)
+    }
+
+    /// Size in bytes of the encoded field, only the first octet being
+    /// decoded for fragment and extended frames.
+    pub const fn size(&self) -> usize {
+        match self {
+            ControlField::Standard(_) => STANDARD_CONTROL_FIELD_SIZE,
+            ControlField::Multipurpose(control) => control.size(),
+            ControlField::Fragment(_) | ControlField::Extended(_) => 1,
+        }
+    }
+
+    pub const fn as_standard(&self) -> Option<&StandardControlField> {
+        match self {
+            ControlField::Standard(control) => Some(control),
+            _ => None,
+        }
+    }
+
+    pub fn as_standard_mut(&mut self) -> Option<&mut StandardControlField> {
+        match self {
+            ControlField::Standard(control) => Some(control),
+            _ => None,
+        }
+    }
+
+    pub const fn frame_kind(&self) -> FrameKind {
+        match self {
+            ControlField::Standard(control) => control.frame_kind,
+            ControlField::Multipurpose(_) => FrameKind::MultiPurpose,
+            ControlField::Fragment(_) => FrameKind::Frak,
+            ControlField::Extended(_) => FrameKind::Extended,
+        }
+    }
+
+    /// `None` for fragment and extended frames, which have no frame version
+    /// field.
+    pub const fn version(&self) -> Option<FrameVersion> {
+        match self {
+            ControlField::Standard(control) => Some(control.version),
+            ControlField::Multipurpose(control) => Some(control.version),
+            ControlField::Fragment(_) | ControlField::Extended(_) => None,
+        }
+    }
+
+    /// Always `false` for fragment and extended frames.
+    pub const fn security_enabled(&self) -> bool {
+        match self {
+            ControlField::Standard(control) => control.security_enabled,
+            ControlField::Multipurpose(control) => control.security_enabled,
+            ControlField::Fragment(_) | ControlField::Extended(_) => false,
+        }
+    }
+
+    /// Always `false` for fragment and extended frames.
+    pub const fn frame_pending(&self) -> bool {
+        match self {
+            ControlField::Standard(control) => control.frame_pending,
+            ControlField::Multipurpose(control) => control.frame_pending,
+            ControlField::Fragment(_) | ControlField::Extended(_) => false,
+        }
+    }
+
+    /// Always `false` for fragment and extended frames.
+    pub const fn ack_required(&self) -> bool {
+        match self {
+            ControlField::Standard(control) => control.ack_required,
+            ControlField::Multipurpose(control) => control.ack_required,
+            ControlField::Fragment(_) | ControlField::Extended(_) => false,
+        }
+    }
+
+    /// Always `false` for fragment and extended frames.
+    pub const fn seq_no_present(&self) -> bool {
+        match self {
+            ControlField::Standard(control) => control.seq_no_present,
+            ControlField::Multipurpose(control) => control.seq_no_present,
+            ControlField::Fragment(_) | ControlField::Extended(_) => false,
+        }
+    }
+
+    /// Always `false` for fragment and extended frames.
+    pub const fn ie_present(&self) -> bool {
+        match self {
+            ControlField::Standard(control) => control.ie_present,
+            ControlField::Multipurpose(control) => control.ie_present,
+            ControlField::Fragment(_) | ControlField::Extended(_) => false,
+        }
+    }
+}
+
+impl From<StandardControlField> for ControlField {
+    fn from(value: StandardControlField) -> Self {
+        ControlField::Standard(value)
+    }
+}
+
+impl From<MultipurposeControlField> for ControlField {
+    fn from(value: MultipurposeControlField) -> Self {
+        ControlField::Multipurpose(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ControlField, Frame};
+    use crate::{
+        ieee802154::{
+            control_field::{
+                FragmentControl, FrameKind, FrameVersion, MultipurposeControlField,
+                StandardControlField,
+            },
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    #[test]
+    fn control_field_from_bytes() {
+        let Ok(ControlField::Standard(control)) = ControlField::from_bytes(&[0x41, 0x88, 0x01])
+        else {
+            panic!("expected a standard frame control field");
+        };
+        assert_eq!(control.frame_kind(), FrameKind::Data);
+        assert_eq!(ControlField::Standard(control).size(), 2);
+
+        // Short and long forms of the multipurpose frame control field
+        let Ok(control @ ControlField::Multipurpose(_)) = ControlField::from_bytes(&[0xA5]) else {
+            panic!("expected a multipurpose frame control field");
+        };
+        assert_eq!(control.size(), 1);
+        assert_eq!(
+            ControlField::from_bytes(&[0xAD, 0x00]).map(|control| control.size()),
+            Ok(2)
+        );
+
+        assert_eq!(
+            ControlField::from_bytes(&[]),
+            Err(crate::parser::Error::Truncated)
+        );
+        assert_eq!(
+            ControlField::from_bytes(&[0x41]),
+            Err(crate::parser::Error::Truncated)
+        );
+        assert_eq!(
+            ControlField::from_bytes(&[0xAD]),
+            Err(crate::parser::Error::Truncated)
+        );
+        assert_eq!(
+            ControlField::from_bytes(&[0x04, 0x00]),
+            Err(crate::parser::Error::InvalidFrameKind(0x4))
+        );
+    }
+
+    #[test]
+    fn control_field_fragment_and_extended() {
+        let control = ControlField::from_bytes(&[0x0E]).unwrap();
+        assert_eq!(
+            control,
+            ControlField::Fragment(FragmentControl::from_byte(0x0E))
+        );
+        assert_eq!(control.frame_kind(), FrameKind::Frak);
+        assert_eq!(control.version(), None);
+        assert!(!control.security_enabled());
+        assert_eq!(control.size(), 1);
+
+        let control = ControlField::from_bytes(&[0x3F]).unwrap();
+        assert_eq!(control, ControlField::Extended(0x3F));
+        assert_eq!(control.frame_kind(), FrameKind::Extended);
+        assert!(!control.seq_no_present());
+    }
+
+    #[test]
+    fn control_field_accessors() {
+        // Secured data frame requesting an acknowledgment
+        let control = ControlField::from_bytes(&[0x69, 0x88]).unwrap();
+        assert_eq!(control.frame_kind(), FrameKind::Data);
+        assert_eq!(control.version(), Some(FrameVersion::Ieee802154_2003));
+        assert!(control.security_enabled());
+        assert!(control.ack_required());
+        assert!(!control.frame_pending());
+        assert!(control.seq_no_present());
+        assert!(!control.ie_present());
+
+        // Long form with sequence number suppression and IEs
+        let control = ControlField::from_bytes(&[0x0D, 0x84]).unwrap();
+        assert_eq!(control.frame_kind(), FrameKind::MultiPurpose);
+        assert_eq!(control.version(), Some(FrameVersion::Ieee802154));
+        assert!(!control.security_enabled());
+        assert!(!control.seq_no_present());
+        assert!(control.ie_present());
+    }
+
+    #[test]
+    fn frame_control() {
+        let input = [0x12, 0x10, 0x2A];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Some(ControlField::Standard(control)) = frame.control() else {
+            panic!("expected a standard frame control field");
+        };
+        assert_eq!(control.frame_kind(), FrameKind::Acknowledgment);
+        assert_eq!(control.version(), FrameVersion::Ieee802154_2006);
+        assert!(control.frame_pending());
+
+        let input = [0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB];
+        let frame = Ieee802154::parse(&input).unwrap();
+        assert_eq!(
+            frame.control(),
+            Some(ControlField::from_bytes(&input).unwrap())
+        );
+
+        assert_eq!(Frame::Extended.control(), None);
+    }
+
+    #[test]
+    fn control_field_from() {
+        let control = StandardControlField::new(FrameKind::Data, FrameVersion::Ieee802154);
+        assert_eq!(
+            ControlField::from(control.clone()),
+            ControlField::Standard(control)
+        );
+
+        let control = MultipurposeControlField::from_bytes(&[0x05]).unwrap();
+        assert_eq!(
+            ControlField::from(control.clone()),
+            ControlField::Multipurpose(control)
+        );
+    }
 }
@@ -0,0 +1,199 @@
+use crate::ieee802154::{
+    address::{AddressField, AddressKind},
+    control_field::MultipurposeControlField,
+    ie::{header::HeaderIeIterator, payload::PayloadIeIterator},
+    security_header::AuxiliarySecurityHeader,
+};
+
+/// Multipurpose frame.
+///
+/// A single PAN ID field is present if `pan_id_present` is set: the
+/// destination one if there is a destination address or no address at all,
+/// the source one otherwise. Addresses without a PAN ID field take the
+/// broadcast PAN ID, and a PAN ID without any address is kept as
+/// [`AddressField::PanOnly`].
+/// Chapter 7.3.5
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipurposeFrame<'a> {
+    pub control: MultipurposeControlField,
+    /// `None` if the sequence number is suppressed
+    pub seq_no: Option<u8>,
+    pub dst_addr: AddressField,
+    pub src_addr: Option<AddressKind>,
+    /// Present if `security_enabled` is set
+    pub aux: Option<AuxiliarySecurityHeader>,
+    /// Header IEs, empty if `ie_present` is not set
+    pub header_ies: HeaderIeIterator<'a>,
+    /// Payload IEs, present when the header IEs end with a Header
+    /// Termination 1 IE
+    pub payload_ies: PayloadIeIterator<'a>,
+    pub payload: &'a [u8],
+    /// MIC of a secured frame, following the payload. Empty if the
+    /// security level has no MIC.
+    pub mic: &'a [u8],
+}
+
+impl<'a> MultipurposeFrame<'a> {
+    /// Same as [`MacHeader::source_only`](super::MacHeader::source_only).
+    pub const fn source_only(&self) -> Option<&AddressKind> {
+        match (&self.dst_addr, &self.src_addr) {
+            (AddressField::Absent, Some(src)) => Some(src),
+            _ => None,
+        }
+    }
+}
+
+/// Returns whether the destination and source PAN ID fields are present.
+/// Chapter 7.3.5.1
+pub(crate) const fn pan_id_presence(pan_id_present: bool, dst: bool, src: bool) -> (bool, bool) {
+    (
+        pan_id_present && (dst || !src),
+        pan_id_present && !dst && src,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+            control_field::FrameVersion,
+            frame::Frame,
+            ie::header::HeaderIe,
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    #[test]
+    fn short_form_round_trip() {
+        let input: [u8; 6] = [0x25, 0x2A, 0xFF, 0xFF, 0x01, 0x02];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MultiPurpose(multipurpose) = &frame else {
+            panic!("expected a multipurpose frame");
+        };
+
+        assert!(!multipurpose.control.is_long());
+        assert_eq!(multipurpose.seq_no, Some(0x2A));
+        assert_eq!(
+            multipurpose.dst_addr,
+            AddressField::Present(AddressKind::Short(ShortAddress::broadcast()))
+        );
+        assert_eq!(multipurpose.src_addr, None);
+        assert_eq!(multipurpose.payload, &[0x01, 0x02]);
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn long_form_round_trip() {
+        let input: [u8; 23] = [
+            0xED, 0xC1, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x81, 0x0E, 0xAB, 0x80, 0x3F, 0x42, 0x43, 0x44,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MultiPurpose(multipurpose) = &frame else {
+            panic!("expected a multipurpose frame");
+        };
+
+        assert!(multipurpose.control.pan_id_present);
+        assert!(multipurpose.control.ack_required);
+        assert_eq!(
+            multipurpose.dst_addr,
+            AddressField::Present(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert_eq!(
+            multipurpose.src_addr,
+            Some(AddressKind::Long(LongAddress::new(
+                PanId::broadcast(),
+                0x0102030405060708
+            )))
+        );
+        assert_eq!(
+            multipurpose.header_ies.clone().next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1D,
+                content: &[0xAB],
+            }))
+        );
+        assert_eq!(multipurpose.payload, &[0x42, 0x43, 0x44]);
+        assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+    }
+
+    #[test]
+    fn pan_id_only_round_trip() {
+        let input: [u8; 5] = [0x0D, 0x01, 0x2A, 0x34, 0x12];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MultiPurpose(mut multipurpose) = frame else {
+            panic!("expected a multipurpose frame");
+        };
+
+        assert_eq!(
+            multipurpose.dst_addr,
+            AddressField::PanOnly(PanId::new(0x1234))
+        );
+        assert_eq!(multipurpose.src_addr, None);
+        assert_eq!(
+            Ieee802154::compose(&Frame::MultiPurpose(multipurpose.clone())).unwrap(),
+            input
+        );
+
+        // The PAN ID field belongs to the source address when there is one
+        multipurpose.src_addr = Some(AddressKind::Short(ShortAddress::new(
+            PanId::new(0x1234),
+            0x0001,
+        )));
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MultiPurpose(multipurpose)),
+            Err(crate::composer::Error::InvalidAddressing)
+        ));
+    }
+
+    #[test]
+    fn forced_long_form_round_trip() {
+        let input: [u8; 3] = [0x0D, 0x00, 0x2A];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::MultiPurpose(mut multipurpose) = frame else {
+            panic!("expected a multipurpose frame");
+        };
+
+        assert!(multipurpose.control.is_long());
+        assert_eq!(
+            Ieee802154::compose(&Frame::MultiPurpose(multipurpose.clone())).unwrap(),
+            input
+        );
+
+        multipurpose.control.long_frame_control = false;
+        assert_eq!(
+            Ieee802154::compose(&Frame::MultiPurpose(multipurpose)).unwrap(),
+            [0x05, 0x2A]
+        );
+    }
+
+    #[test]
+    fn invalid_version() {
+        assert_eq!(
+            Ieee802154::parse(&[0x0D, 0x10, 0x2A]).unwrap_err().kind,
+            crate::parser::Error::InvalidFrameVersion(0x1)
+        );
+
+        let Frame::MultiPurpose(mut multipurpose) = Ieee802154::parse(&[0x05, 0x2A]).unwrap()
+        else {
+            panic!("expected a multipurpose frame");
+        };
+        multipurpose.control.version = FrameVersion::Ieee802154_2003;
+
+        assert!(matches!(
+            Ieee802154::compose(&Frame::MultiPurpose(multipurpose)),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ieee802154::address::{AddressKind, PanId};
+
+use super::{beacon::SuperFrame, ControlField};
+
+/// Description of a PAN, built from a received beacon. It does not borrow
+/// the frame, so it can be kept once the receive buffer is reused.
+/// Chapter 8.2.5
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanDescriptor {
+    /// Coordinator address, `None` if the beacon has no source address
+    pub coord_address: Option<AddressKind>,
+    pub coord_pan_id: PanId,
+    /// `None` for enhanced beacons
+    pub super_frame: Option<SuperFrame>,
+    pub gts_permit: bool,
+    /// Link quality of the beacon, left to 0 and filled in by the caller
+    /// from the radio
+    pub link_quality: u8,
+    pub security_enabled: bool,
+}
+
+impl PanDescriptor {
+    pub(crate) fn new(
+        control: &ControlField,
+        coord_address: Option<AddressKind>,
+        super_frame: Option<SuperFrame>,
+        gts_permit: bool,
+    ) -> Self {
+        let coord_pan_id = coord_address
+            .as_ref()
+            .map_or(PanId::BROADCAST, AddressKind::pan_id);
+
+        PanDescriptor {
+            coord_address,
+            coord_pan_id,
+            super_frame,
+            gts_permit,
+            link_quality: 0,
+            security_enabled: control.security_enabled(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ieee802154::{
+            address::{AddressKind, LongAddress, PanId},
+            frame::Frame,
+            Ieee802154,
+        },
+        parser::Parser,
+    };
+
+    #[test]
+    fn pan_descriptor_from_beacon() {
+        let input: [u8; 31] = [
+            0x00, 0xC0, 0x17, 0x34, 0x12, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x66,
+            0xCC, 0x81, 0x01, 0x01, 0x00, 0x2D, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0xDE, 0xAD,
+        ];
+        let Frame::Beacon(beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a beacon frame");
+        };
+
+        let descriptor = beacon.pan_descriptor();
+        drop(beacon);
+
+        assert_eq!(
+            descriptor.coord_address,
+            Some(AddressKind::Long(LongAddress::new(
+                PanId::new(0x1234),
+                0x0102_0304_0506_0708
+            )))
+        );
+        assert_eq!(descriptor.coord_pan_id, PanId::new(0x1234));
+        let super_frame = descriptor.super_frame.unwrap();
+        assert_eq!(super_frame.beacon_order, 6);
+        assert!(super_frame.association_permit);
+        assert!(descriptor.gts_permit);
+        assert!(!descriptor.security_enabled);
+
+        let mut scan: heapless::Vec<_, 4> = heapless::Vec::new();
+        scan.push(descriptor).unwrap();
+        assert_eq!(scan[0], descriptor);
+    }
+
+    #[test]
+    fn pan_descriptor_from_enh_beacon() {
+        let input: [u8; 14] = [
+            0x00, 0xA2, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x0F, 0xAB, 0x80, 0x3F, 0x42, 0x43,
+        ];
+        let Frame::EnhBeacon(beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected an enhanced beacon frame");
+        };
+
+        let descriptor = beacon.pan_descriptor();
+
+        assert_eq!(descriptor.coord_pan_id, PanId::new(0x1234));
+        assert_eq!(descriptor.super_frame, None);
+        assert!(!descriptor.gts_permit);
+        assert_eq!(descriptor.link_quality, 0);
+    }
+}
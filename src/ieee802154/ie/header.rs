@@ -0,0 +1,417 @@
+use super::{
+    rit::{Rit, RIT_SIZE},
+    time_correction::{TimeCorrection, TIME_CORRECTION_SIZE},
+    HeaderTermination, IE_DESCRIPTOR_SIZE,
+};
+
+mod offset {
+    pub(crate) const LENGTH: usize = 0;
+    pub(crate) const ELEMENT_ID: usize = 7;
+    pub(crate) const TYPE: usize = 15;
+}
+
+mod mask {
+    use super::offset;
+
+    pub(crate) const LENGTH: u16 = 0x7F << offset::LENGTH;
+    pub(crate) const ELEMENT_ID: u16 = 0xFF << offset::ELEMENT_ID;
+    pub(crate) const TYPE: u16 = 0x1 << offset::TYPE;
+}
+
+/// Element IDs of header IEs.
+/// Table 7-7
+pub mod element_id {
+    /// Receiver Initiated Transmission
+    pub const RIT: u8 = 0x1B;
+    /// ACK/NACK Time Correction
+    pub const TIME_CORRECTION: u8 = 0x1E;
+    /// Header Termination 1, followed by payload IEs
+    pub const HEADER_TERMINATION_1: u8 = 0x7E;
+    /// Header Termination 2, followed by an unformatted payload
+    pub const HEADER_TERMINATION_2: u8 = 0x7F;
+}
+
+/// Header information element.
+/// Chapter 7.4.2
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderIe<'a> {
+    pub element_id: u8,
+    pub content: &'a [u8],
+}
+
+impl<'a> HeaderIe<'a> {
+    /// Length of the content, as found in the IE descriptor.
+    pub const fn length(&self) -> usize {
+        self.content.len()
+    }
+
+    pub const fn is_termination(&self) -> bool {
+        matches!(
+            self.element_id,
+            element_id::HEADER_TERMINATION_1 | element_id::HEADER_TERMINATION_2
+        )
+    }
+
+    /// Writes the IE descriptor followed by the content into `buf`, and
+    /// returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the content does
+    /// not fit in the 7-bit length field, returns `InvalidIe`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let length = self.length();
+
+        if length > (mask::LENGTH >> offset::LENGTH).into() {
+            return Err(crate::composer::Error::InvalidIe {
+                id: self.element_id.into(),
+            });
+        }
+
+        let len = IE_DESCRIPTOR_SIZE + length;
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        let descriptor: u16 = ((length as u16) << offset::LENGTH)
+            | (((self.element_id as u16) << offset::ELEMENT_ID) & mask::ELEMENT_ID);
+
+        buf[..IE_DESCRIPTOR_SIZE].copy_from_slice(&descriptor.to_le_bytes());
+        buf[IE_DESCRIPTOR_SIZE..].copy_from_slice(self.content);
+
+        Ok(len)
+    }
+
+    /// Interprets the IE according to its element ID. Elements not modelled
+    /// by the crate are returned as [`HeaderElement::Unknown`].
+    ///
+    /// # Error
+    ///
+    /// If the content of a modelled element is malformed, returns
+    /// `InvalidHeader`.
+    pub fn element(&self) -> Result<HeaderElement<'a>, crate::parser::Error> {
+        match self.element_id {
+            element_id::RIT => Rit::from_bytes(self.content).map(HeaderElement::Rit),
+            element_id::TIME_CORRECTION => {
+                TimeCorrection::from_bytes(self.content).map(HeaderElement::TimeCorrection)
+            }
+            id => Ok(HeaderElement::Unknown {
+                id,
+                content: self.content,
+            }),
+        }
+    }
+}
+
+/// Header IE interpreted according to its element ID.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderElement<'a> {
+    Rit(Rit),
+    TimeCorrection(TimeCorrection),
+    /// Element not modelled by the crate, kept verbatim
+    Unknown {
+        id: u8,
+        content: &'a [u8],
+    },
+}
+
+impl<'a> HeaderElement<'a> {
+    /// Size in bytes of the IE, descriptor included.
+    pub fn serialized_len(&self) -> usize {
+        IE_DESCRIPTOR_SIZE
+            + match self {
+                HeaderElement::Rit(_) => RIT_SIZE,
+                HeaderElement::TimeCorrection(_) => TIME_CORRECTION_SIZE,
+                HeaderElement::Unknown { content, .. } => content.len(),
+            }
+    }
+
+    /// Writes the IE, descriptor included, into `buf`, and returns the
+    /// number of bytes written. Unknown elements are written back verbatim.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the content does
+    /// not fit in the length field, returns `InvalidIe`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        match self {
+            HeaderElement::Rit(rit) => rit.write_into(buf),
+            HeaderElement::TimeCorrection(correction) => correction.write_into(buf),
+            HeaderElement::Unknown { id, content } => HeaderIe {
+                element_id: *id,
+                content,
+            }
+            .write_into(buf),
+        }
+    }
+}
+
+/// Writes the header IEs one after the other into `buf`, and returns the
+/// number of bytes written. No termination is added, the composer inserts
+/// the one matching what follows the list.
+///
+/// # Errors
+///
+/// If `buf` is too small, returns `BufferTooSmall`. If the content of an IE
+/// does not fit in its length field, returns `InvalidIe`.
+pub fn write_ies(ies: &[HeaderElement], buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+    let needed = ies.iter().map(HeaderElement::serialized_len).sum();
+    let buf = buf
+        .get_mut(..needed)
+        .ok_or(crate::composer::Error::BufferTooSmall { needed })?;
+    let mut len = 0;
+
+    for ie in ies {
+        len += ie.write_into(&mut buf[len..])?;
+    }
+
+    Ok(len)
+}
+
+/// Lazily walks a list of header IEs, without copying their content.
+///
+/// Iteration stops after a Header Termination IE, at the end of the input or
+/// at the first malformed IE.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderIeIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    done: bool,
+    termination: HeaderTermination,
+}
+
+impl<'a> HeaderIeIterator<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        HeaderIeIterator {
+            data,
+            offset: 0,
+            done: false,
+            termination: HeaderTermination::None,
+        }
+    }
+
+    /// Raw bytes of the IE list being walked.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Number of bytes walked so far. Once the iteration is over, this is
+    /// the length of the IE list including its termination.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Termination that ended the list. Only meaningful once the iteration
+    /// is over.
+    pub const fn termination(&self) -> HeaderTermination {
+        self.termination
+    }
+}
+
+impl<'a> Iterator for HeaderIeIterator<'a> {
+    type Item = Result<HeaderIe<'a>, crate::parser::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.data.len() {
+            return None;
+        }
+
+        let descriptor: [u8; IE_DESCRIPTOR_SIZE] =
+            match self.data.get(self.offset..self.offset + IE_DESCRIPTOR_SIZE) {
+                Some(descriptor) => [descriptor[0], descriptor[1]],
+                None => {
+                    self.done = true;
+                    return Some(Err(crate::parser::Error::Truncated));
+                }
+            };
+        let descriptor = u16::from_le_bytes(descriptor);
+
+        let length: usize = ((descriptor & mask::LENGTH) >> offset::LENGTH).into();
+        let element_id: u8 = ((descriptor & mask::ELEMENT_ID) >> offset::ELEMENT_ID) as u8;
+
+        if descriptor & mask::TYPE != 0 {
+            self.done = true;
+            return Some(Err(crate::parser::Error::InvalidIe {
+                id: element_id.into(),
+            }));
+        }
+
+        let start = self.offset + IE_DESCRIPTOR_SIZE;
+        let content = match self.data.get(start..start + length) {
+            Some(content) => content,
+            None => {
+                self.done = true;
+                return Some(Err(crate::parser::Error::InvalidIe {
+                    id: element_id.into(),
+                }));
+            }
+        };
+
+        self.offset = start + length;
+
+        let ie = HeaderIe {
+            element_id,
+            content,
+        };
+
+        if ie.is_termination() {
+            self.done = true;
+            self.termination = match element_id {
+                element_id::HEADER_TERMINATION_1 => HeaderTermination::PayloadIes,
+                _ => HeaderTermination::Payload,
+            };
+            return None;
+        }
+
+        Some(Ok(ie))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ieee802154::ie::rit::Rit;
+
+    use super::{HeaderElement, HeaderIe, HeaderIeIterator, HeaderTermination};
+
+    #[test]
+    fn iterate_until_end_of_input() {
+        let input: [u8; 6] = [0x02, 0x0F, 0xAB, 0xCD, 0x00, 0x0F];
+        let mut iter = HeaderIeIterator::new(&input);
+
+        assert_eq!(
+            iter.next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1E,
+                content: &[0xAB, 0xCD],
+            }))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1E,
+                content: &[],
+            }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.offset(), 6);
+        assert_eq!(iter.termination(), HeaderTermination::None);
+    }
+
+    #[test]
+    fn iterate_stops_at_header_termination() {
+        let input: [u8; 7] = [0x01, 0x0F, 0xAB, 0x00, 0x3F, 0x01, 0x0F];
+        let mut iter = HeaderIeIterator::new(&input);
+
+        assert_eq!(
+            iter.next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1E,
+                content: &[0xAB],
+            }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.offset(), 5);
+        assert_eq!(iter.termination(), HeaderTermination::PayloadIes);
+    }
+
+    #[test]
+    fn length_past_end_of_input_is_an_error() {
+        let input: [u8; 3] = [0x05, 0x0F, 0xAB];
+        let mut iter = HeaderIeIterator::new(&input);
+
+        assert_eq!(
+            iter.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0x1E }))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn truncated_descriptor_is_an_error() {
+        let mut iter = HeaderIeIterator::new(&[0x00]);
+
+        assert_eq!(iter.next(), Some(Err(crate::parser::Error::Truncated)));
+    }
+
+    #[test]
+    fn payload_ie_descriptor_is_an_error() {
+        let mut iter = HeaderIeIterator::new(&[0x00, 0x80]);
+
+        assert_eq!(
+            iter.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0x00 }))
+        );
+    }
+
+    #[test]
+    fn write_into_round_trip() {
+        let ie = HeaderIe {
+            element_id: 0x1E,
+            content: &[0xAB, 0xCD],
+        };
+        let mut buf = [0u8; 4];
+
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 4);
+        assert_eq!(buf, [0x02, 0x0F, 0xAB, 0xCD]);
+        assert_eq!(HeaderIeIterator::new(&buf).next(), Some(Ok(ie)));
+        assert_eq!(
+            ie.write_into(&mut buf[..3]),
+            Err(crate::composer::Error::BufferTooSmall { needed: 4 })
+        );
+    }
+
+    #[test]
+    fn iterate_stops_at_header_termination_2() {
+        let input: [u8; 4] = [0x80, 0x3F, 0x01, 0x0F];
+        let mut iter = HeaderIeIterator::new(&input);
+
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.offset(), 2);
+        assert_eq!(iter.termination(), HeaderTermination::Payload);
+    }
+
+    #[test]
+    fn unknown_elements_survive_round_trip() {
+        let input: [u8; 10] = [0x82, 0x0E, 0xAB, 0xCD, 0x84, 0x0D, 0x10, 0x03, 0x02, 0x01];
+        let mut buf = [0u8; 10];
+        let mut len = 0;
+
+        for ie in HeaderIeIterator::new(&input) {
+            len += ie
+                .unwrap()
+                .element()
+                .unwrap()
+                .write_into(&mut buf[len..])
+                .unwrap();
+        }
+
+        assert_eq!(len, input.len());
+        assert_eq!(buf, input);
+    }
+
+    #[test]
+    fn element_from_id() {
+        let mut iter =
+            HeaderIeIterator::new(&[0x82, 0x0E, 0xAB, 0xCD, 0x84, 0x0D, 0x10, 0x03, 0x02, 0x01]);
+
+        assert_eq!(
+            iter.next().unwrap().unwrap().element(),
+            Ok(HeaderElement::Unknown {
+                id: 0x1D,
+                content: &[0xAB, 0xCD],
+            })
+        );
+        assert_eq!(
+            iter.next().unwrap().unwrap().element(),
+            Ok(HeaderElement::Rit(Rit {
+                time_to_first_listen: 0x10,
+                number_of_repeat_listen: 0x03,
+                repeat_listen_interval: 0x0102,
+            }))
+        );
+    }
+}
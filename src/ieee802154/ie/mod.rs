@@ -0,0 +1,138 @@
+//! Information elements.
+//! Chapter 7.4
+
+pub mod header;
+pub mod nested;
+pub mod payload;
+pub mod rit;
+pub mod time_correction;
+pub mod tsch;
+
+/// Size in bytes of an IE descriptor
+pub const IE_DESCRIPTOR_SIZE: usize = 2;
+
+/// Termination of a header IE list, telling what follows it.
+/// Chapter 7.4.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderTermination {
+    /// No termination IE, the IE list runs to the end of the frame
+    None,
+    /// Header Termination 1 IE, followed by payload IEs
+    PayloadIes,
+    /// Header Termination 2 IE, followed by an unformatted payload
+    Payload,
+}
+
+/// Termination IEs to insert when composing the IE lists of a frame.
+/// Chapter 7.4.1
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Terminations {
+    pub header: HeaderTermination,
+    /// Whether a Payload Termination IE ends the payload IE list
+    pub payload: bool,
+}
+
+impl Terminations {
+    /// Returns the termination IEs needed for a frame with the given
+    /// content.
+    ///
+    /// A termination IE is only inserted when something follows the list it
+    /// ends: Header Termination 1 before payload IEs, Header Termination 2
+    /// between header IEs and an unformatted payload, and Payload
+    /// Termination between payload IEs and an unformatted payload.
+    pub const fn new(header_ies: bool, payload_ies: bool, payload: bool) -> Self {
+        let header = match (header_ies, payload_ies, payload) {
+            (_, true, _) => HeaderTermination::PayloadIes,
+            (true, false, true) => HeaderTermination::Payload,
+            _ => HeaderTermination::None,
+        };
+
+        Terminations {
+            header,
+            payload: payload_ies && payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderTermination, Terminations};
+
+    #[test]
+    fn no_ies() {
+        assert_eq!(
+            Terminations::new(false, false, true),
+            Terminations {
+                header: HeaderTermination::None,
+                payload: false,
+            }
+        );
+        assert_eq!(
+            Terminations::new(false, false, false),
+            Terminations {
+                header: HeaderTermination::None,
+                payload: false,
+            }
+        );
+    }
+
+    #[test]
+    fn header_ies_without_payload_need_no_termination() {
+        assert_eq!(
+            Terminations::new(true, false, false),
+            Terminations {
+                header: HeaderTermination::None,
+                payload: false,
+            }
+        );
+    }
+
+    #[test]
+    fn header_ies_followed_by_payload() {
+        assert_eq!(
+            Terminations::new(true, false, true),
+            Terminations {
+                header: HeaderTermination::Payload,
+                payload: false,
+            }
+        );
+    }
+
+    #[test]
+    fn payload_ies_running_to_the_end() {
+        assert_eq!(
+            Terminations::new(true, true, false),
+            Terminations {
+                header: HeaderTermination::PayloadIes,
+                payload: false,
+            }
+        );
+        assert_eq!(
+            Terminations::new(false, true, false),
+            Terminations {
+                header: HeaderTermination::PayloadIes,
+                payload: false,
+            }
+        );
+    }
+
+    #[test]
+    fn payload_ies_followed_by_payload() {
+        assert_eq!(
+            Terminations::new(true, true, true),
+            Terminations {
+                header: HeaderTermination::PayloadIes,
+                payload: true,
+            }
+        );
+        assert_eq!(
+            Terminations::new(false, true, true),
+            Terminations {
+                header: HeaderTermination::PayloadIes,
+                payload: true,
+            }
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use super::{
+    tsch::{
+        ChannelHoppingIe, SlotframeAndLink, TschSynchronization, TschTimeslot,
+        TSCH_SYNCHRONIZATION_SIZE,
+    },
+    IE_DESCRIPTOR_SIZE,
+};
+
+mod offset {
+    pub(crate) const SHORT_LENGTH: usize = 0;
+    pub(crate) const SHORT_SUB_ID: usize = 8;
+    pub(crate) const LONG_LENGTH: usize = 0;
+    pub(crate) const LONG_SUB_ID: usize = 11;
+    pub(crate) const TYPE: usize = 15;
+}
+
+mod mask {
+    use super::offset;
+
+    pub(crate) const SHORT_LENGTH: u16 = 0xFF << offset::SHORT_LENGTH;
+    pub(crate) const SHORT_SUB_ID: u16 = 0x7F << offset::SHORT_SUB_ID;
+    pub(crate) const LONG_LENGTH: u16 = 0x7FF << offset::LONG_LENGTH;
+    pub(crate) const LONG_SUB_ID: u16 = 0xF << offset::LONG_SUB_ID;
+    pub(crate) const TYPE: u16 = 0x1 << offset::TYPE;
+}
+
+/// Sub-IDs of short nested IEs.
+/// Table 7-21
+pub mod short_id {
+    pub const TSCH_SYNCHRONIZATION: u8 = 0x1A;
+    pub const TSCH_SLOTFRAME_AND_LINK: u8 = 0x1B;
+    pub const TSCH_TIMESLOT: u8 = 0x1C;
+}
+
+/// Sub-IDs of long nested IEs.
+/// Table 7-20
+pub mod long_id {
+    pub const CHANNEL_HOPPING: u8 = 0x9;
+}
+
+/// Format of a nested IE. Short and long nested IEs have their own sub-ID
+/// space and length field size.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedIeKind {
+    /// 7-bit sub-ID, content up to 255 bytes
+    Short,
+    /// 4-bit sub-ID, content up to 2047 bytes
+    Long,
+}
+
+/// IE nested inside an MLME payload IE.
+/// Chapter 7.4.4
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NestedIe<'a> {
+    pub kind: NestedIeKind,
+    pub sub_id: u8,
+    pub content: &'a [u8],
+}
+
+impl<'a> NestedIe<'a> {
+    /// Length of the content, as found in the IE descriptor.
+    pub const fn length(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Writes the IE descriptor followed by the content into `buf`, and
+    /// returns the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the content does
+    /// not fit in the length field, returns `InvalidIe`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let len = IE_DESCRIPTOR_SIZE + self.length();
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        buf[IE_DESCRIPTOR_SIZE..].copy_from_slice(self.content);
+        write_descriptor(self.kind, self.sub_id, self.length(), buf)?;
+
+        Ok(len)
+    }
+
+    /// Interprets the IE according to its kind and sub-ID. Sub-IEs not
+    /// modelled by the crate are returned as [`NestedElement::Unknown`].
+    ///
+    /// # Error
+    ///
+    /// If the content of a modelled sub-IE is malformed, returns
+    /// `InvalidIe`.
+    pub fn element(&self) -> Result<NestedElement<'a>, crate::parser::Error> {
+        match (self.kind, self.sub_id) {
+            (NestedIeKind::Short, short_id::TSCH_SYNCHRONIZATION) => {
+                TschSynchronization::try_from(*self).map(NestedElement::TschSynchronization)
+            }
+            (NestedIeKind::Short, short_id::TSCH_SLOTFRAME_AND_LINK) => {
+                SlotframeAndLink::try_from(*self).map(NestedElement::SlotframeAndLink)
+            }
+            (NestedIeKind::Short, short_id::TSCH_TIMESLOT) => {
+                TschTimeslot::try_from(*self).map(NestedElement::TschTimeslot)
+            }
+            (NestedIeKind::Long, long_id::CHANNEL_HOPPING) => {
+                ChannelHoppingIe::try_from(*self).map(NestedElement::ChannelHopping)
+            }
+            (kind, id) => Ok(NestedElement::Unknown {
+                kind,
+                id,
+                content: self.content,
+            }),
+        }
+    }
+}
+
+/// Writes the descriptor of a nested IE of `length` bytes at the start of
+/// `buf`. A length that does not fit in the descriptor is refused with
+/// `InvalidIe`.
+fn write_descriptor(
+    kind: NestedIeKind,
+    sub_id: u8,
+    length: usize,
+    buf: &mut [u8],
+) -> Result<(), crate::composer::Error> {
+    let (max_length, descriptor) = match kind {
+        NestedIeKind::Short => (
+            mask::SHORT_LENGTH >> offset::SHORT_LENGTH,
+            ((sub_id as u16) << offset::SHORT_SUB_ID) & mask::SHORT_SUB_ID,
+        ),
+        NestedIeKind::Long => (
+            mask::LONG_LENGTH >> offset::LONG_LENGTH,
+            (((sub_id as u16) << offset::LONG_SUB_ID) & mask::LONG_SUB_ID) | mask::TYPE,
+        ),
+    };
+
+    if length > max_length.into() {
+        return Err(crate::composer::Error::InvalidIe { id: sub_id.into() });
+    }
+
+    let descriptor = descriptor | (length as u16);
+    buf[..IE_DESCRIPTOR_SIZE].copy_from_slice(&descriptor.to_le_bytes());
+
+    Ok(())
+}
+
+/// Nested IE interpreted according to its kind and sub-ID.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum NestedElement<'a> {
+    TschSynchronization(TschSynchronization),
+    SlotframeAndLink(SlotframeAndLink),
+    TschTimeslot(TschTimeslot),
+    ChannelHopping(ChannelHoppingIe<'a>),
+    /// Sub-IE not modelled by the crate, kept verbatim
+    Unknown {
+        kind: NestedIeKind,
+        id: u8,
+        content: &'a [u8],
+    },
+}
+
+impl<'a> NestedElement<'a> {
+    /// Size in bytes of the sub-IE, descriptor included.
+    pub fn serialized_len(&self) -> usize {
+        IE_DESCRIPTOR_SIZE
+            + match self {
+                NestedElement::TschSynchronization(_) => TSCH_SYNCHRONIZATION_SIZE,
+                NestedElement::SlotframeAndLink(slotframes) => slotframes.serialized_len(),
+                NestedElement::TschTimeslot(timeslot) => timeslot.serialized_len(),
+                NestedElement::ChannelHopping(hopping) => hopping.serialized_len(),
+                NestedElement::Unknown { content, .. } => content.len(),
+            }
+    }
+
+    /// Writes the IE, descriptor included, into `buf`, and returns the
+    /// number of bytes written. Unknown sub-IEs are written back verbatim.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the content
+    /// cannot be composed, returns `InvalidHeader`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let needed = self.serialized_len();
+        let buf = buf
+            .get_mut(..needed)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed })?;
+        let content = &mut buf[IE_DESCRIPTOR_SIZE..];
+
+        let (kind, sub_id, length) = match self {
+            NestedElement::TschSynchronization(sync) => {
+                let bytes = sync.bytes();
+                content.copy_from_slice(&bytes);
+
+                (
+                    NestedIeKind::Short,
+                    short_id::TSCH_SYNCHRONIZATION,
+                    bytes.len(),
+                )
+            }
+            NestedElement::SlotframeAndLink(slotframes) => (
+                NestedIeKind::Short,
+                short_id::TSCH_SLOTFRAME_AND_LINK,
+                slotframes.write_into(content)?,
+            ),
+            NestedElement::TschTimeslot(timeslot) => (
+                NestedIeKind::Short,
+                short_id::TSCH_TIMESLOT,
+                timeslot.write_into(content)?,
+            ),
+            NestedElement::ChannelHopping(hopping) => (
+                NestedIeKind::Long,
+                long_id::CHANNEL_HOPPING,
+                hopping.write_into(content)?,
+            ),
+            NestedElement::Unknown { kind, id, content } => {
+                return NestedIe {
+                    kind: *kind,
+                    sub_id: *id,
+                    content,
+                }
+                .write_into(buf)
+            }
+        };
+
+        write_descriptor(kind, sub_id, length, buf)?;
+
+        Ok(IE_DESCRIPTOR_SIZE + length)
+    }
+}
+
+/// Lazily walks the nested IEs contained in an MLME payload IE.
+///
+/// Iteration stops at the end of the input or at the first malformed IE.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedIeIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    done: bool,
+}
+
+impl<'a> NestedIeIterator<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        NestedIeIterator {
+            data,
+            offset: 0,
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for NestedIeIterator<'a> {
+    type Item = Result<NestedIe<'a>, crate::parser::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.data.len() {
+            return None;
+        }
+
+        let descriptor: [u8; IE_DESCRIPTOR_SIZE] =
+            match self.data.get(self.offset..self.offset + IE_DESCRIPTOR_SIZE) {
+                Some(descriptor) => [descriptor[0], descriptor[1]],
+                None => {
+                    self.done = true;
+                    return Some(Err(crate::parser::Error::Truncated));
+                }
+            };
+        let descriptor = u16::from_le_bytes(descriptor);
+
+        let (kind, sub_id, length) = match descriptor & mask::TYPE != 0 {
+            true => (
+                NestedIeKind::Long,
+                ((descriptor & mask::LONG_SUB_ID) >> offset::LONG_SUB_ID) as u8,
+                ((descriptor & mask::LONG_LENGTH) >> offset::LONG_LENGTH) as usize,
+            ),
+            false => (
+                NestedIeKind::Short,
+                ((descriptor & mask::SHORT_SUB_ID) >> offset::SHORT_SUB_ID) as u8,
+                ((descriptor & mask::SHORT_LENGTH) >> offset::SHORT_LENGTH) as usize,
+            ),
+        };
+
+        let start = self.offset + IE_DESCRIPTOR_SIZE;
+        let content = match self.data.get(start..start + length) {
+            Some(content) => content,
+            None => {
+                self.done = true;
+                return Some(Err(crate::parser::Error::InvalidIe { id: sub_id.into() }));
+            }
+        };
+
+        self.offset = start + length;
+
+        Some(Ok(NestedIe {
+            kind,
+            sub_id,
+            content,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NestedElement, NestedIe, NestedIeIterator, NestedIeKind};
+
+    #[test]
+    fn short_length_uses_eight_bits() {
+        let mut input = [0u8; 2 + 0xC8];
+        input[0] = 0xC8;
+        input[1] = 0x1B;
+
+        let mut ies = NestedIeIterator::new(&input);
+        let ie = ies.next().unwrap().unwrap();
+
+        assert_eq!(ie.kind, NestedIeKind::Short);
+        assert_eq!(ie.sub_id, 0x1B);
+        assert_eq!(ie.length(), 0xC8);
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn long_length_uses_eleven_bits() {
+        let mut input = [0u8; 2 + 0x401];
+        input[0] = 0x01;
+        input[1] = 0xCC;
+
+        let mut ies = NestedIeIterator::new(&input);
+        let ie = ies.next().unwrap().unwrap();
+
+        assert_eq!(ie.kind, NestedIeKind::Long);
+        assert_eq!(ie.sub_id, 0x9);
+        assert_eq!(ie.length(), 0x401);
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn mixed_short_and_long() {
+        let input: [u8; 7] = [0x01, 0x1C, 0xAA, 0x02, 0xC8, 0xBB, 0xCC];
+        let mut ies = NestedIeIterator::new(&input);
+
+        assert_eq!(
+            ies.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Short,
+                sub_id: 0x1C,
+                content: &[0xAA],
+            }))
+        );
+        assert_eq!(
+            ies.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Long,
+                sub_id: 0x9,
+                content: &[0xBB, 0xCC],
+            }))
+        );
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn length_past_end_of_input_is_an_error() {
+        let input: [u8; 3] = [0x02, 0x1A, 0x00];
+        let mut ies = NestedIeIterator::new(&input);
+
+        assert_eq!(
+            ies.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0x1A }))
+        );
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn unknown_elements_survive_round_trip() {
+        let input: [u8; 13] = [
+            0x01, 0x1C, 0x00, 0x01, 0x7F, 0xAA, 0x02, 0xF8, 0xBB, 0xCC, 0x01, 0xC8, 0x00,
+        ];
+        let mut buf = [0u8; 13];
+        let mut len = 0;
+
+        for ie in NestedIeIterator::new(&input) {
+            len += ie
+                .unwrap()
+                .element()
+                .unwrap()
+                .write_into(&mut buf[len..])
+                .unwrap();
+        }
+
+        assert_eq!(len, input.len());
+        assert_eq!(buf, input);
+    }
+
+    #[test]
+    fn element_from_sub_id() {
+        let input: [u8; 6] = [0x01, 0x7F, 0xAA, 0x02, 0xF8, 0xBB];
+        let mut ies = NestedIeIterator::new(&input);
+
+        assert_eq!(
+            ies.next().unwrap().unwrap().element(),
+            Ok(NestedElement::Unknown {
+                kind: NestedIeKind::Short,
+                id: 0x7F,
+                content: &[0xAA],
+            })
+        );
+        assert_eq!(
+            ies.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0xF }))
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use super::{
+    nested::{NestedElement, NestedIeIterator},
+    IE_DESCRIPTOR_SIZE,
+};
+
+mod offset {
+    pub(crate) const LENGTH: usize = 0;
+    pub(crate) const GROUP_ID: usize = 11;
+    pub(crate) const TYPE: usize = 15;
+}
+
+mod mask {
+    use super::offset;
+
+    pub(crate) const LENGTH: u16 = 0x7FF << offset::LENGTH;
+    pub(crate) const GROUP_ID: u16 = 0xF << offset::GROUP_ID;
+    pub(crate) const TYPE: u16 = 0x1 << offset::TYPE;
+}
+
+const ESDU_VALUE: u8 = 0x0;
+const MLME_VALUE: u8 = 0x1;
+const VENDOR_SPECIFIC_VALUE: u8 = 0x2;
+const MULTIPLEXED_VALUE: u8 = 0x3;
+const OMNI_VALUE: u8 = 0x4;
+const IETF_VALUE: u8 = 0x5;
+const TERMINATION_VALUE: u8 = 0xF;
+
+/// Group ID of a payload IE.
+/// Table 7-19
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadIeGroup {
+    Esdu,
+    /// Content is a list of nested IEs
+    Mlme,
+    VendorSpecific,
+    Multiplexed,
+    Omni,
+    Ietf,
+    Termination,
+    Reserved(u8),
+}
+
+impl PayloadIeGroup {
+    pub const fn bits(&self) -> u8 {
+        match self {
+            PayloadIeGroup::Esdu => ESDU_VALUE,
+            PayloadIeGroup::Mlme => MLME_VALUE,
+            PayloadIeGroup::VendorSpecific => VENDOR_SPECIFIC_VALUE,
+            PayloadIeGroup::Multiplexed => MULTIPLEXED_VALUE,
+            PayloadIeGroup::Omni => OMNI_VALUE,
+            PayloadIeGroup::Ietf => IETF_VALUE,
+            PayloadIeGroup::Termination => TERMINATION_VALUE,
+            PayloadIeGroup::Reserved(value) => *value,
+        }
+    }
+
+    /// Returns the group matching the 4 bits of `value`.
+    pub const fn from_byte(value: u8) -> Self {
+        match value & 0xF {
+            ESDU_VALUE => PayloadIeGroup::Esdu,
+            MLME_VALUE => PayloadIeGroup::Mlme,
+            VENDOR_SPECIFIC_VALUE => PayloadIeGroup::VendorSpecific,
+            MULTIPLEXED_VALUE => PayloadIeGroup::Multiplexed,
+            OMNI_VALUE => PayloadIeGroup::Omni,
+            IETF_VALUE => PayloadIeGroup::Ietf,
+            TERMINATION_VALUE => PayloadIeGroup::Termination,
+            value => PayloadIeGroup::Reserved(value),
+        }
+    }
+}
+
+impl From<u8> for PayloadIeGroup {
+    fn from(value: u8) -> Self {
+        PayloadIeGroup::from_byte(value)
+    }
+}
+
+/// Payload information element.
+/// Chapter 7.4.3
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadIe<'a> {
+    pub group: PayloadIeGroup,
+    pub content: &'a [u8],
+}
+
+impl<'a> PayloadIe<'a> {
+    /// Length of the content, as found in the IE descriptor.
+    pub const fn length(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Returns an iterator over the nested IEs of an MLME IE, `None` for
+    /// other groups.
+    pub fn nested(&self) -> Option<NestedIeIterator<'a>> {
+        match self.group {
+            PayloadIeGroup::Mlme => Some(NestedIeIterator::new(self.content)),
+            _ => None,
+        }
+    }
+
+    /// Writes the IE descriptor followed by the content into `buf`, and
+    /// returns the number of bytes written. Used to write back IEs of any
+    /// group verbatim.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the content does
+    /// not fit in the 11-bit length field, returns `InvalidIe`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let length = self.length();
+        let len = IE_DESCRIPTOR_SIZE + length;
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        write_descriptor(self.group, length, buf)?;
+        buf[IE_DESCRIPTOR_SIZE..].copy_from_slice(self.content);
+
+        Ok(len)
+    }
+
+    /// Writes an MLME IE holding the `nested` IEs into `buf`, and returns
+    /// the number of bytes written. The length field is computed from the
+    /// nested IEs.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If a nested IE or the
+    /// whole list does not fit in its length field, returns `InvalidIe`.
+    pub fn write_mlme_into(
+        nested: &[NestedElement],
+        buf: &mut [u8],
+    ) -> Result<usize, crate::composer::Error> {
+        let needed = IE_DESCRIPTOR_SIZE
+            + nested
+                .iter()
+                .map(NestedElement::serialized_len)
+                .sum::<usize>();
+        let buf = buf
+            .get_mut(..needed)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed })?;
+
+        let mut length = 0;
+        for ie in nested {
+            length += ie.write_into(&mut buf[IE_DESCRIPTOR_SIZE + length..])?;
+        }
+
+        write_descriptor(PayloadIeGroup::Mlme, length, buf)?;
+
+        Ok(IE_DESCRIPTOR_SIZE + length)
+    }
+}
+
+/// Writes the descriptor of a payload IE of `length` bytes at the start of
+/// `buf`.
+fn write_descriptor(
+    group: PayloadIeGroup,
+    length: usize,
+    buf: &mut [u8],
+) -> Result<(), crate::composer::Error> {
+    if length > (mask::LENGTH >> offset::LENGTH).into() {
+        return Err(crate::composer::Error::InvalidIe {
+            id: group.bits().into(),
+        });
+    }
+
+    let descriptor: u16 = ((length as u16) << offset::LENGTH)
+        | (((group.bits() as u16) << offset::GROUP_ID) & mask::GROUP_ID)
+        | mask::TYPE;
+
+    buf.get_mut(..IE_DESCRIPTOR_SIZE)
+        .ok_or(crate::composer::Error::InvalidHeader)?
+        .copy_from_slice(&descriptor.to_le_bytes());
+
+    Ok(())
+}
+
+/// Writes the payload IEs one after the other into `buf`, and returns the
+/// number of bytes written. No termination is added, the composer inserts
+/// it when a payload follows the list.
+///
+/// # Errors
+///
+/// If `buf` is too small, returns `BufferTooSmall`. If the content of an IE
+/// does not fit in its length field, returns `InvalidIe`.
+pub fn write_ies(ies: &[PayloadIe], buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+    let needed = ies.iter().map(|ie| IE_DESCRIPTOR_SIZE + ie.length()).sum();
+    let buf = buf
+        .get_mut(..needed)
+        .ok_or(crate::composer::Error::BufferTooSmall { needed })?;
+    let mut len = 0;
+
+    for ie in ies {
+        len += ie.write_into(&mut buf[len..])?;
+    }
+
+    Ok(len)
+}
+
+/// Lazily walks a list of payload IEs, without copying their content.
+///
+/// Iteration stops after a Payload Termination IE, at the end of the input
+/// or at the first malformed IE.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadIeIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    done: bool,
+    terminated: bool,
+}
+
+impl<'a> PayloadIeIterator<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        PayloadIeIterator {
+            data,
+            offset: 0,
+            done: false,
+            terminated: false,
+        }
+    }
+
+    /// Raw bytes of the IE list being walked.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Number of bytes walked so far. Once the iteration is over, this is
+    /// the length of the IE list including its termination.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether the iteration reached a Payload Termination IE, meaning an
+    /// unformatted payload follows the list.
+    pub const fn terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<'a> Iterator for PayloadIeIterator<'a> {
+    type Item = Result<PayloadIe<'a>, crate::parser::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.data.len() {
+            return None;
+        }
+
+        let descriptor: [u8; IE_DESCRIPTOR_SIZE] =
+            match self.data.get(self.offset..self.offset + IE_DESCRIPTOR_SIZE) {
+                Some(descriptor) => [descriptor[0], descriptor[1]],
+                None => {
+                    self.done = true;
+                    return Some(Err(crate::parser::Error::Truncated));
+                }
+            };
+        let descriptor = u16::from_le_bytes(descriptor);
+
+        let length: usize = ((descriptor & mask::LENGTH) >> offset::LENGTH).into();
+        let group_id: u8 = ((descriptor & mask::GROUP_ID) >> offset::GROUP_ID) as u8;
+
+        if descriptor & mask::TYPE == 0 {
+            self.done = true;
+            return Some(Err(crate::parser::Error::InvalidIe {
+                id: group_id.into(),
+            }));
+        }
+
+        let group = PayloadIeGroup::from_byte(group_id);
+
+        let start = self.offset + IE_DESCRIPTOR_SIZE;
+        let content = match self.data.get(start..start + length) {
+            Some(content) => content,
+            None => {
+                self.done = true;
+                return Some(Err(crate::parser::Error::InvalidIe {
+                    id: group_id.into(),
+                }));
+            }
+        };
+
+        self.offset = start + length;
+
+        if group == PayloadIeGroup::Termination {
+            self.done = true;
+            self.terminated = true;
+            return None;
+        }
+
+        Some(Ok(PayloadIe { group, content }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::ieee802154::ie::nested::{NestedElement, NestedIe, NestedIeKind};
+
+    use super::{write_ies, PayloadIe, PayloadIeGroup, PayloadIeIterator};
+
+    /// Payload IEs of the enhanced beacon sent by a 6TiSCH minimal
+    /// configuration (RFC 8180) coordinator: TSCH Synchronization, TSCH
+    /// Timeslot, Channel Hopping and Slotframe and Link nested IEs.
+    const MINIMAL_EB_PAYLOAD_IES: [u8; 28] = [
+        0x1A, 0x88, 0x06, 0x1A, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x1C, 0x00, 0x01, 0xC8,
+        0x00, 0x0A, 0x1B, 0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F,
+    ];
+
+    #[test]
+    fn group_from_byte_round_trip() {
+        for value in 0..0x10 {
+            assert_eq!(PayloadIeGroup::from_byte(value).bits(), value);
+        }
+
+        assert_eq!(PayloadIeGroup::from_byte(0x1), PayloadIeGroup::Mlme);
+        assert_eq!(PayloadIeGroup::from_byte(0xF), PayloadIeGroup::Termination);
+        assert_eq!(
+            PayloadIeGroup::from_byte(0x7),
+            PayloadIeGroup::Reserved(0x7)
+        );
+    }
+
+    #[test]
+    fn minimal_enhanced_beacon() {
+        let mut ies = PayloadIeIterator::new(&MINIMAL_EB_PAYLOAD_IES);
+
+        let mlme = ies.next().unwrap().unwrap();
+        assert_eq!(mlme.group, PayloadIeGroup::Mlme);
+        assert_eq!(mlme.length(), 26);
+        assert_eq!(ies.next(), None);
+        assert_eq!(ies.offset(), MINIMAL_EB_PAYLOAD_IES.len());
+        assert!(!ies.terminated());
+
+        let mut nested = mlme.nested().unwrap();
+
+        assert_eq!(
+            nested.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Short,
+                sub_id: 0x1A,
+                content: &[0x0E, 0x00, 0x00, 0x00, 0x00, 0x00],
+            }))
+        );
+        assert_eq!(
+            nested.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Short,
+                sub_id: 0x1C,
+                content: &[0x00],
+            }))
+        );
+        assert_eq!(
+            nested.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Long,
+                sub_id: 0x9,
+                content: &[0x00],
+            }))
+        );
+        assert_eq!(
+            nested.next(),
+            Some(Ok(NestedIe {
+                kind: NestedIeKind::Short,
+                sub_id: 0x1B,
+                content: &[0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F],
+            }))
+        );
+        assert_eq!(nested.next(), None);
+    }
+
+    #[test]
+    fn write_mlme_round_trip() {
+        let mlme = PayloadIeIterator::new(&MINIMAL_EB_PAYLOAD_IES)
+            .next()
+            .unwrap()
+            .unwrap();
+        let nested: Vec<NestedElement> = mlme
+            .nested()
+            .unwrap()
+            .map(|ie| ie.unwrap().element().unwrap())
+            .collect();
+
+        let mut buf = [0u8; 32];
+        let len = PayloadIe::write_mlme_into(&nested, &mut buf).unwrap();
+
+        assert_eq!(&buf[..len], &MINIMAL_EB_PAYLOAD_IES);
+        assert_eq!(
+            PayloadIe::write_mlme_into(&nested, &mut buf[..len - 1]),
+            Err(crate::composer::Error::BufferTooSmall { needed: len })
+        );
+    }
+
+    #[test]
+    fn write_ies_round_trip() {
+        let ies = [
+            PayloadIe {
+                group: PayloadIeGroup::Esdu,
+                content: &[0xAB, 0xCD],
+            },
+            PayloadIe {
+                group: PayloadIeGroup::Ietf,
+                content: &[],
+            },
+            PayloadIe {
+                group: PayloadIeGroup::VendorSpecific,
+                content: &[0x01, 0x02, 0x03],
+            },
+        ];
+
+        let mut buf = [0u8; 16];
+        let len = write_ies(&ies, &mut buf).unwrap();
+        assert_eq!(len, 11);
+        assert_eq!(
+            write_ies(&ies, &mut buf[..10]),
+            Err(crate::composer::Error::BufferTooSmall { needed: 11 })
+        );
+
+        let mut iter = PayloadIeIterator::new(&buf[..len]);
+        let parsed: Vec<PayloadIe> = iter.by_ref().map(Result::unwrap).collect();
+
+        assert_eq!(parsed, ies);
+        assert!(!iter.terminated());
+    }
+
+    #[test]
+    fn write_refuses_content_over_length_field() {
+        let content = [0u8; 2048];
+        let mut buf = [0u8; 2050];
+
+        let ie = PayloadIe {
+            group: PayloadIeGroup::Esdu,
+            content: &content[..2047],
+        };
+        assert_eq!(ie.write_into(&mut buf), Ok(2049));
+
+        let ie = PayloadIe {
+            group: PayloadIeGroup::Esdu,
+            content: &content,
+        };
+        assert_eq!(
+            ie.write_into(&mut buf),
+            Err(crate::composer::Error::InvalidIe { id: 0x0 })
+        );
+
+        // A short nested IE holds at most 255 bytes
+        let nested = [NestedElement::Unknown {
+            kind: NestedIeKind::Short,
+            id: 0x1A,
+            content: &content[..256],
+        }];
+        assert_eq!(
+            PayloadIe::write_mlme_into(&nested, &mut buf),
+            Err(crate::composer::Error::InvalidIe { id: 0x1A })
+        );
+
+        // Long nested IEs filling an MLME IE past its 11-bit length
+        let nested = [
+            NestedElement::Unknown {
+                kind: NestedIeKind::Long,
+                id: 0x9,
+                content: &content[..1024],
+            },
+            NestedElement::Unknown {
+                kind: NestedIeKind::Long,
+                id: 0x9,
+                content: &content[..1024],
+            },
+        ];
+        let mut buf = [0u8; 2100];
+        assert_eq!(
+            PayloadIe::write_mlme_into(&nested, &mut buf),
+            Err(crate::composer::Error::InvalidIe { id: 0x1 })
+        );
+    }
+
+    #[test]
+    fn iterate_stops_at_payload_termination() {
+        let input: [u8; 7] = [0x01, 0x80, 0xAB, 0x00, 0xF8, 0x01, 0x80];
+        let mut ies = PayloadIeIterator::new(&input);
+
+        assert_eq!(
+            ies.next(),
+            Some(Ok(PayloadIe {
+                group: PayloadIeGroup::Esdu,
+                content: &[0xAB],
+            }))
+        );
+        assert_eq!(ies.next(), None);
+        assert_eq!(ies.offset(), 5);
+        assert!(ies.terminated());
+    }
+
+    #[test]
+    fn nested_only_for_mlme() {
+        let ie = PayloadIe {
+            group: PayloadIeGroup::Ietf,
+            content: &[],
+        };
+
+        assert!(ie.nested().is_none());
+    }
+
+    #[test]
+    fn length_past_end_of_input_is_an_error() {
+        let input: [u8; 3] = [0x05, 0x88, 0xAB];
+        let mut ies = PayloadIeIterator::new(&input);
+
+        assert_eq!(
+            ies.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0x1 }))
+        );
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn header_ie_descriptor_is_an_error() {
+        let mut ies = PayloadIeIterator::new(&[0x00, 0x3F]);
+
+        assert_eq!(
+            ies.next(),
+            Some(Err(crate::parser::Error::InvalidIe { id: 0x7 }))
+        );
+    }
+
+    #[test]
+    fn reserved_group_survives_round_trip() {
+        let input: [u8; 4] = [0x02, 0xB8, 0xAB, 0xCD];
+        let ie = PayloadIeIterator::new(&input).next().unwrap().unwrap();
+
+        assert_eq!(ie.group, PayloadIeGroup::Reserved(0x7));
+
+        let mut buf = [0u8; 4];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 4);
+        assert_eq!(buf, input);
+    }
+}
@@ -0,0 +1,131 @@
+//! RIT header IE.
+//! Chapter 7.4.2.3
+
+use super::header::{element_id, HeaderIe};
+
+/// Size in bytes of the RIT IE content
+pub const RIT_SIZE: usize = 4;
+
+/// Receiver Initiated Transmission IE, advertising when a receiver listens
+/// for RIT data requests.
+/// Figure 7-29
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rit {
+    /// Delay before the first listen, in units of macRitPeriod
+    pub time_to_first_listen: u8,
+    /// Number of listen periods following the first one
+    pub number_of_repeat_listen: u8,
+    /// Interval between two listen periods, in units of macRitPeriod
+    pub repeat_listen_interval: u16,
+}
+
+impl Rit {
+    /// # Error
+    ///
+    /// If `data` is not exactly [`RIT_SIZE`] bytes long, returns
+    /// `InvalidIe`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [time_to_first_listen, number_of_repeat_listen, interval_low, interval_high] => {
+                Ok(Rit {
+                    time_to_first_listen: *time_to_first_listen,
+                    number_of_repeat_listen: *number_of_repeat_listen,
+                    repeat_listen_interval: u16::from_le_bytes([*interval_low, *interval_high]),
+                })
+            }
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: element_id::RIT as u16,
+            }),
+        }
+    }
+
+    pub const fn bytes(&self) -> [u8; RIT_SIZE] {
+        let interval = self.repeat_listen_interval.to_le_bytes();
+
+        [
+            self.time_to_first_listen,
+            self.number_of_repeat_listen,
+            interval[0],
+            interval[1],
+        ]
+    }
+
+    /// Writes the IE, descriptor included, into `buf`, and returns the
+    /// number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        HeaderIe {
+            element_id: element_id::RIT,
+            content: &self.bytes(),
+        }
+        .write_into(buf)
+    }
+}
+
+impl<'a> TryFrom<HeaderIe<'a>> for Rit {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: HeaderIe<'a>) -> Result<Self, Self::Error> {
+        match ie.element_id {
+            element_id::RIT => Rit::from_bytes(ie.content),
+            id => Err(crate::parser::Error::InvalidIe { id: id.into() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ieee802154::ie::header::{HeaderIe, HeaderIeIterator};
+
+    use super::Rit;
+
+    #[test]
+    fn round_trip_through_header_ie_iterator() {
+        let rit = Rit {
+            time_to_first_listen: 0x10,
+            number_of_repeat_listen: 0x03,
+            repeat_listen_interval: 0x0102,
+        };
+        let mut buf = [0u8; 6];
+
+        assert_eq!(rit.write_into(&mut buf).unwrap(), 6);
+        assert_eq!(buf, [0x84, 0x0D, 0x10, 0x03, 0x02, 0x01]);
+
+        let mut ies = HeaderIeIterator::new(&buf);
+        let ie = ies.next().unwrap().unwrap();
+
+        assert_eq!(Rit::try_from(ie), Ok(rit));
+        assert_eq!(ies.next(), None);
+    }
+
+    #[test]
+    fn invalid_length() {
+        let ie = HeaderIe {
+            element_id: 0x1B,
+            content: &[0x10, 0x03, 0x02],
+        };
+
+        assert_eq!(
+            Rit::try_from(ie),
+            Err(crate::parser::Error::InvalidIe { id: 0x1B })
+        );
+    }
+
+    #[test]
+    fn other_element_id() {
+        let ie = HeaderIe {
+            element_id: 0x1A,
+            content: &[0x10, 0x03, 0x02, 0x01],
+        };
+
+        assert_eq!(
+            Rit::try_from(ie),
+            Err(crate::parser::Error::InvalidIe { id: 0x1A })
+        );
+    }
+}
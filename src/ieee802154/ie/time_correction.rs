@@ -0,0 +1,153 @@
+//! ACK/NACK Time Correction header IE.
+//! Chapter 7.4.2.7
+
+use super::header::{element_id, HeaderIe};
+
+/// Size in bytes of the Time Correction IE content
+pub const TIME_CORRECTION_SIZE: usize = 2;
+
+mod offset {
+    pub(crate) const TIME_SYNC_INFO: usize = 0;
+    pub(crate) const NACK: usize = 15;
+}
+
+mod mask {
+    use super::offset;
+
+    pub(crate) const TIME_SYNC_INFO: u16 = 0xFFF << offset::TIME_SYNC_INFO;
+    pub(crate) const NACK: u16 = 0x1 << offset::NACK;
+}
+
+/// Smallest time correction the 12-bit field can carry, in microseconds
+pub const MIN_TIME_CORRECTION: i16 = -2048;
+/// Largest time correction the 12-bit field can carry, in microseconds
+pub const MAX_TIME_CORRECTION: i16 = 2047;
+
+/// Time Correction IE, carried by enhanced acknowledgments to tell the
+/// sender how far off its transmission was.
+/// Figure 7-33
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeCorrection {
+    /// Time correction in microseconds, between [`MIN_TIME_CORRECTION`] and
+    /// [`MAX_TIME_CORRECTION`]
+    pub correction_us: i16,
+    /// Whether the frame is negatively acknowledged
+    pub nack: bool,
+}
+
+impl TimeCorrection {
+    /// # Error
+    ///
+    /// If `data` is not exactly [`TIME_CORRECTION_SIZE`] bytes long, returns
+    /// `InvalidIe`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data {
+            [low, high] => {
+                let bits = u16::from_le_bytes([*low, *high]);
+                // Sign extends the 12-bit two's complement value
+                let correction_us =
+                    (((bits & mask::TIME_SYNC_INFO) >> offset::TIME_SYNC_INFO) << 4) as i16 >> 4;
+
+                Ok(TimeCorrection {
+                    correction_us,
+                    nack: bits & mask::NACK != 0,
+                })
+            }
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: element_id::TIME_CORRECTION as u16,
+            }),
+        }
+    }
+
+    /// Returns the IE content, the correction being truncated to 12 bits.
+    pub const fn bytes(&self) -> [u8; TIME_CORRECTION_SIZE] {
+        ((((self.correction_us as u16) << offset::TIME_SYNC_INFO) & mask::TIME_SYNC_INFO)
+            | (((self.nack as u16) << offset::NACK) & mask::NACK))
+            .to_le_bytes()
+    }
+
+    /// Writes the IE, descriptor included, into `buf`, and returns the
+    /// number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the correction
+    /// does not fit in 12 bits, returns `InvalidIe`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        if !(MIN_TIME_CORRECTION..=MAX_TIME_CORRECTION).contains(&self.correction_us) {
+            return Err(crate::composer::Error::InvalidIe {
+                id: element_id::TIME_CORRECTION.into(),
+            });
+        }
+
+        HeaderIe {
+            element_id: element_id::TIME_CORRECTION,
+            content: &self.bytes(),
+        }
+        .write_into(buf)
+    }
+}
+
+impl<'a> TryFrom<HeaderIe<'a>> for TimeCorrection {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: HeaderIe<'a>) -> Result<Self, Self::Error> {
+        match ie.element_id {
+            element_id::TIME_CORRECTION => TimeCorrection::from_bytes(ie.content),
+            id => Err(crate::parser::Error::InvalidIe { id: id.into() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ieee802154::ie::header::HeaderIeIterator;
+
+    use super::TimeCorrection;
+
+    #[test]
+    fn round_trip_through_header_ie_iterator() {
+        let cases = [
+            (TimeCorrection::default(), [0x02, 0x0F, 0x00, 0x00]),
+            (
+                TimeCorrection {
+                    correction_us: -100,
+                    nack: false,
+                },
+                [0x02, 0x0F, 0x9C, 0x0F],
+            ),
+            (
+                TimeCorrection {
+                    correction_us: 2047,
+                    nack: true,
+                },
+                [0x02, 0x0F, 0xFF, 0x87],
+            ),
+        ];
+
+        for (correction, bytes) in cases {
+            let mut buf = [0u8; 4];
+
+            assert_eq!(correction.write_into(&mut buf).unwrap(), 4);
+            assert_eq!(buf, bytes);
+
+            let ie = HeaderIeIterator::new(&buf).next().unwrap().unwrap();
+            assert_eq!(TimeCorrection::try_from(ie), Ok(correction));
+        }
+    }
+
+    #[test]
+    fn out_of_range() {
+        let correction = TimeCorrection {
+            correction_us: -2049,
+            nack: false,
+        };
+
+        assert_eq!(
+            correction.write_into(&mut [0u8; 4]),
+            Err(crate::composer::Error::InvalidIe { id: 0x1E })
+        );
+        assert!(TimeCorrection::from_bytes(&[0x00]).is_err());
+    }
+}
@@ -0,0 +1,1106 @@
+//! TSCH nested IEs.
+//! Chapter 7.4.4
+
+#[cfg(feature = "ufmt")]
+use ufmt::uwrite;
+
+use super::nested::{long_id, short_id, NestedIe, NestedIeKind};
+
+/// Size in bytes of an absolute slot number
+pub const ASN_SIZE: usize = 5;
+
+/// Absolute Slot Number, the number of timeslots elapsed since the start of
+/// the network. Only the 40 least significant bits are used.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Asn(u64);
+
+impl Asn {
+    pub const MAX: u64 = 0xFF_FFFF_FFFF;
+
+    /// Returns the ASN made of the 40 least significant bits of `value`.
+    pub const fn new(value: u64) -> Self {
+        Asn(value & Self::MAX)
+    }
+
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub const fn from_bytes(data: &[u8; ASN_SIZE]) -> Self {
+        Asn(u64::from_le_bytes([
+            data[0], data[1], data[2], data[3], data[4], 0, 0, 0,
+        ]))
+    }
+
+    pub const fn bytes(&self) -> [u8; ASN_SIZE] {
+        let bytes = self.0.to_le_bytes();
+
+        [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4]]
+    }
+}
+
+impl From<Asn> for u64 {
+    fn from(value: Asn) -> Self {
+        value.value()
+    }
+}
+
+/// Size in bytes of a TSCH Synchronization IE content
+pub const TSCH_SYNCHRONIZATION_SIZE: usize = ASN_SIZE + 1;
+
+/// TSCH Synchronization IE, sent in enhanced beacons so joining devices can
+/// synchronize to the network.
+/// Chapter 7.4.4.2
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TschSynchronization {
+    pub asn: Asn,
+    pub join_metric: u8,
+}
+
+impl TschSynchronization {
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        let data: &[u8; TSCH_SYNCHRONIZATION_SIZE] = data
+            .get(..TSCH_SYNCHRONIZATION_SIZE)
+            .and_then(|data| data.try_into().ok())
+            .ok_or(crate::parser::Error::InvalidIe {
+                id: short_id::TSCH_SYNCHRONIZATION.into(),
+            })?;
+
+        Ok(TschSynchronization {
+            asn: Asn::from_bytes(&[data[0], data[1], data[2], data[3], data[4]]),
+            join_metric: data[ASN_SIZE],
+        })
+    }
+
+    pub const fn bytes(&self) -> [u8; TSCH_SYNCHRONIZATION_SIZE] {
+        let asn = self.asn.bytes();
+
+        [asn[0], asn[1], asn[2], asn[3], asn[4], self.join_metric]
+    }
+}
+
+impl<'a> TryFrom<NestedIe<'a>> for TschSynchronization {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: NestedIe<'a>) -> Result<Self, Self::Error> {
+        match (ie.kind, ie.sub_id) {
+            (NestedIeKind::Short, short_id::TSCH_SYNCHRONIZATION) => {
+                TschSynchronization::from_bytes(ie.content)
+            }
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: ie.sub_id.into(),
+            }),
+        }
+    }
+}
+
+pub mod link_option {
+    pub const TX: u8 = 0x1 << 0;
+    pub const RX: u8 = 0x1 << 1;
+    pub const SHARED: u8 = 0x1 << 2;
+    pub const TIMEKEEPING: u8 = 0x1 << 3;
+    pub const PRIORITY: u8 = 0x1 << 4;
+}
+
+/// Link options bitmap.
+/// Figure 7-62
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinkOptions {
+    pub tx: bool,
+    pub rx: bool,
+    pub shared: bool,
+    pub timekeeping: bool,
+    pub priority: bool,
+}
+
+impl LinkOptions {
+    pub const fn from_byte(value: u8) -> Self {
+        LinkOptions {
+            tx: value & link_option::TX != 0,
+            rx: value & link_option::RX != 0,
+            shared: value & link_option::SHARED != 0,
+            timekeeping: value & link_option::TIMEKEEPING != 0,
+            priority: value & link_option::PRIORITY != 0,
+        }
+    }
+
+    pub const fn bits(&self) -> u8 {
+        (self.tx as u8 * link_option::TX)
+            | (self.rx as u8 * link_option::RX)
+            | (self.shared as u8 * link_option::SHARED)
+            | (self.timekeeping as u8 * link_option::TIMEKEEPING)
+            | (self.priority as u8 * link_option::PRIORITY)
+    }
+}
+
+/// Size in bytes of a link information field
+pub const LINK_SIZE: usize = 5;
+
+/// Link information.
+/// Figure 7-61
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Link {
+    pub timeslot: u16,
+    pub channel_offset: u16,
+    pub options: LinkOptions,
+}
+
+impl Link {
+    pub const fn from_bytes(data: &[u8; LINK_SIZE]) -> Self {
+        Link {
+            timeslot: u16::from_le_bytes([data[0], data[1]]),
+            channel_offset: u16::from_le_bytes([data[2], data[3]]),
+            options: LinkOptions::from_byte(data[4]),
+        }
+    }
+
+    pub const fn bytes(&self) -> [u8; LINK_SIZE] {
+        let timeslot = self.timeslot.to_le_bytes();
+        let channel_offset = self.channel_offset.to_le_bytes();
+
+        [
+            timeslot[0],
+            timeslot[1],
+            channel_offset[0],
+            channel_offset[1],
+            self.options.bits(),
+        ]
+    }
+}
+
+/// Maximum number of slotframes held by a [`SlotframeAndLink`]
+pub const MAX_SLOTFRAMES: usize = 4;
+/// Maximum number of links held by a [`Slotframe`]
+pub const MAX_LINKS: usize = 8;
+/// Size in bytes of a slotframe descriptor, without its links
+pub const SLOTFRAME_DESCRIPTOR_SIZE: usize = 4;
+
+/// Slotframe descriptor.
+/// Figure 7-60
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slotframe {
+    pub handle: u8,
+    /// Number of timeslots in the slotframe
+    pub size: u16,
+    pub links: heapless::Vec<Link, MAX_LINKS>,
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for Slotframe {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        uwrite!(
+            f,
+            "Slotframe {{ handle: {} size: {} links: [ ",
+            self.handle,
+            self.size
+        )?;
+
+        for link in &self.links {
+            uwrite!(f, "{:?} ", link)?;
+        }
+
+        uwrite!(f, "] }}")
+    }
+}
+
+impl Slotframe {
+    pub fn serialized_len(&self) -> usize {
+        SLOTFRAME_DESCRIPTOR_SIZE + self.links.len() * LINK_SIZE
+    }
+}
+
+/// TSCH Slotframe and Link IE, advertising the schedule of the network.
+/// Chapter 7.4.4.3
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SlotframeAndLink {
+    pub slotframes: heapless::Vec<Slotframe, MAX_SLOTFRAMES>,
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for SlotframeAndLink {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        uwrite!(f, "SlotframeAndLink {{ slotframes: [ ")?;
+
+        for slotframe in &self.slotframes {
+            uwrite!(f, "{:?} ", slotframe)?;
+        }
+
+        uwrite!(f, "] }}")
+    }
+}
+
+impl SlotframeAndLink {
+    /// # Error
+    ///
+    /// Returns `InvalidIe` if the content is truncated, or if it holds
+    /// more than [`MAX_SLOTFRAMES`] slotframes or [`MAX_LINKS`] links in a
+    /// slotframe.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        const INVALID: crate::parser::Error = crate::parser::Error::InvalidIe {
+            id: short_id::TSCH_SLOTFRAME_AND_LINK as u16,
+        };
+
+        let count: usize = (*data.first().ok_or(INVALID)?).into();
+        let mut slotframes = heapless::Vec::new();
+        let mut index = 1;
+
+        for _ in 0..count {
+            let descriptor = data
+                .get(index..index + SLOTFRAME_DESCRIPTOR_SIZE)
+                .ok_or(INVALID)?;
+            index += SLOTFRAME_DESCRIPTOR_SIZE;
+
+            let mut slotframe = Slotframe {
+                handle: descriptor[0],
+                size: u16::from_le_bytes([descriptor[1], descriptor[2]]),
+                links: heapless::Vec::new(),
+            };
+
+            for _ in 0..descriptor[3] {
+                let link: &[u8; LINK_SIZE] = data
+                    .get(index..index + LINK_SIZE)
+                    .and_then(|link| link.try_into().ok())
+                    .ok_or(INVALID)?;
+                index += LINK_SIZE;
+
+                slotframe
+                    .links
+                    .push(Link::from_bytes(link))
+                    .map_err(|_| INVALID)?;
+            }
+
+            slotframes.push(slotframe).map_err(|_| INVALID)?;
+        }
+
+        Ok(SlotframeAndLink { slotframes })
+    }
+
+    pub fn serialized_len(&self) -> usize {
+        1 + self
+            .slotframes
+            .iter()
+            .map(Slotframe::serialized_len)
+            .sum::<usize>()
+    }
+
+    /// Writes the IE content into `buf`, and returns the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let len = self.serialized_len();
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        buf[0] = self.slotframes.len() as u8;
+        let mut index = 1;
+
+        for slotframe in &self.slotframes {
+            let size = slotframe.size.to_le_bytes();
+            buf[index..index + SLOTFRAME_DESCRIPTOR_SIZE].copy_from_slice(&[
+                slotframe.handle,
+                size[0],
+                size[1],
+                slotframe.links.len() as u8,
+            ]);
+            index += SLOTFRAME_DESCRIPTOR_SIZE;
+
+            for link in &slotframe.links {
+                buf[index..index + LINK_SIZE].copy_from_slice(&link.bytes());
+                index += LINK_SIZE;
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+impl<'a> TryFrom<NestedIe<'a>> for SlotframeAndLink {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: NestedIe<'a>) -> Result<Self, Self::Error> {
+        match (ie.kind, ie.sub_id) {
+            (NestedIeKind::Short, short_id::TSCH_SLOTFRAME_AND_LINK) => {
+                SlotframeAndLink::from_bytes(ie.content)
+            }
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: ie.sub_id.into(),
+            }),
+        }
+    }
+}
+
+/// Size in bytes of a Timeslot IE holding only the timeslot ID
+pub const TIMESLOT_ID_SIZE: usize = 1;
+/// Size in bytes of a Timeslot IE with 2-octet max TX and timeslot length
+pub const TIMESLOT_FULL_SIZE: usize = 25;
+/// Size in bytes of a Timeslot IE with 3-octet max TX and timeslot length
+pub const TIMESLOT_EXTENDED_SIZE: usize = 27;
+
+/// Timing values of a timeslot, in microseconds.
+/// Table 8-98
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeslotTemplate {
+    pub cca_offset: u16,
+    pub cca: u16,
+    pub tx_offset: u16,
+    pub rx_offset: u16,
+    pub rx_ack_delay: u16,
+    pub tx_ack_delay: u16,
+    pub rx_wait: u16,
+    pub ack_wait: u16,
+    pub rx_tx: u16,
+    pub max_ack: u16,
+    /// 16 bits, or 24 bits in the extended form
+    pub max_tx: u32,
+    /// 16 bits, or 24 bits in the extended form
+    pub timeslot_length: u32,
+}
+
+impl TimeslotTemplate {
+    /// Default timeslot template for the 2450 MHz band, timeslot ID 0.
+    pub const DEFAULT_2450MHZ: TimeslotTemplate = TimeslotTemplate {
+        cca_offset: 1800,
+        cca: 128,
+        tx_offset: 2120,
+        rx_offset: 1020,
+        rx_ack_delay: 800,
+        tx_ack_delay: 1000,
+        rx_wait: 2200,
+        ack_wait: 400,
+        rx_tx: 192,
+        max_ack: 2400,
+        max_tx: 4256,
+        timeslot_length: 10000,
+    };
+
+    /// Reads the template following the timeslot ID, `wide` telling whether
+    /// the last two fields are 3 octets long.
+    fn from_bytes(data: &[u8], wide: bool) -> Self {
+        let u16_at = |index: usize| u16::from_le_bytes([data[index], data[index + 1]]);
+        let (max_tx, timeslot_length) = match wide {
+            true => (
+                u32::from_le_bytes([data[20], data[21], data[22], 0]),
+                u32::from_le_bytes([data[23], data[24], data[25], 0]),
+            ),
+            false => (u16_at(20).into(), u16_at(22).into()),
+        };
+
+        TimeslotTemplate {
+            cca_offset: u16_at(0),
+            cca: u16_at(2),
+            tx_offset: u16_at(4),
+            rx_offset: u16_at(6),
+            rx_ack_delay: u16_at(8),
+            tx_ack_delay: u16_at(10),
+            rx_wait: u16_at(12),
+            ack_wait: u16_at(14),
+            rx_tx: u16_at(16),
+            max_ack: u16_at(18),
+            max_tx,
+            timeslot_length,
+        }
+    }
+
+    fn write_into(&self, buf: &mut [u8], wide: bool) {
+        let fields = [
+            self.cca_offset,
+            self.cca,
+            self.tx_offset,
+            self.rx_offset,
+            self.rx_ack_delay,
+            self.tx_ack_delay,
+            self.rx_wait,
+            self.ack_wait,
+            self.rx_tx,
+            self.max_ack,
+        ];
+
+        for (i, field) in fields.iter().enumerate() {
+            buf[2 * i..2 * i + 2].copy_from_slice(&field.to_le_bytes());
+        }
+
+        let width = match wide {
+            true => 3,
+            false => 2,
+        };
+
+        buf[20..20 + width].copy_from_slice(&self.max_tx.to_le_bytes()[..width]);
+        buf[20 + width..20 + 2 * width]
+            .copy_from_slice(&self.timeslot_length.to_le_bytes()[..width]);
+    }
+}
+
+/// TSCH Timeslot IE, describing the timing of a timeslot either by an ID
+/// referring to a known template or in full.
+/// Chapter 7.4.4.4
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TschTimeslot {
+    /// Timeslot ID only
+    Id(u8),
+    /// Timeslot ID and template, with 2-octet max TX and timeslot length
+    Full(u8, TimeslotTemplate),
+    /// Timeslot ID and template, with 3-octet max TX and timeslot length
+    Extended(u8, TimeslotTemplate),
+}
+
+impl TschTimeslot {
+    pub const fn id(&self) -> u8 {
+        match self {
+            TschTimeslot::Id(id) | TschTimeslot::Full(id, _) | TschTimeslot::Extended(id, _) => *id,
+        }
+    }
+
+    pub const fn template(&self) -> Option<&TimeslotTemplate> {
+        match self {
+            TschTimeslot::Id(_) => None,
+            TschTimeslot::Full(_, template) | TschTimeslot::Extended(_, template) => Some(template),
+        }
+    }
+
+    /// Returns the timeslot IE, the form being picked from the content
+    /// length.
+    ///
+    /// # Error
+    ///
+    /// If the length does not match any form, returns `InvalidIe`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, crate::parser::Error> {
+        match data.len() {
+            TIMESLOT_ID_SIZE => Ok(TschTimeslot::Id(data[0])),
+            TIMESLOT_FULL_SIZE => Ok(TschTimeslot::Full(
+                data[0],
+                TimeslotTemplate::from_bytes(&data[1..], false),
+            )),
+            TIMESLOT_EXTENDED_SIZE => Ok(TschTimeslot::Extended(
+                data[0],
+                TimeslotTemplate::from_bytes(&data[1..], true),
+            )),
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: short_id::TSCH_TIMESLOT as u16,
+            }),
+        }
+    }
+
+    pub const fn serialized_len(&self) -> usize {
+        match self {
+            TschTimeslot::Id(_) => TIMESLOT_ID_SIZE,
+            TschTimeslot::Full(_, _) => TIMESLOT_FULL_SIZE,
+            TschTimeslot::Extended(_, _) => TIMESLOT_EXTENDED_SIZE,
+        }
+    }
+
+    /// Writes the IE content into `buf`, and returns the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If max TX or the
+    /// timeslot length do not fit in the form used, returns `InvalidHeader`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let len = self.serialized_len();
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        buf[0] = self.id();
+
+        match self {
+            TschTimeslot::Id(_) => {}
+            TschTimeslot::Full(_, template) => {
+                if template.max_tx > u16::MAX.into() || template.timeslot_length > u16::MAX.into() {
+                    return Err(crate::composer::Error::InvalidHeader);
+                }
+
+                template.write_into(&mut buf[1..], false);
+            }
+            TschTimeslot::Extended(_, template) => {
+                if template.max_tx > 0xFF_FFFF || template.timeslot_length > 0xFF_FFFF {
+                    return Err(crate::composer::Error::InvalidHeader);
+                }
+
+                template.write_into(&mut buf[1..], true);
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+impl<'a> TryFrom<NestedIe<'a>> for TschTimeslot {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: NestedIe<'a>) -> Result<Self, Self::Error> {
+        match (ie.kind, ie.sub_id) {
+            (NestedIeKind::Short, short_id::TSCH_TIMESLOT) => TschTimeslot::from_bytes(ie.content),
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: ie.sub_id.into(),
+            }),
+        }
+    }
+}
+
+/// Size in bytes of a Channel Hopping IE holding only the sequence ID
+pub const CHANNEL_HOPPING_ID_SIZE: usize = 1;
+/// Size in bytes of the fixed part of an explicit Channel Hopping IE, that is
+/// every field but the extended bitmap and the hopping sequence
+pub const CHANNEL_HOPPING_FIXED_SIZE: usize = 12;
+
+/// Hopping sequence carried by an explicit Channel Hopping IE.
+/// Figure 7-90
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelHoppingSequence<'a> {
+    pub channel_page: u8,
+    pub number_of_channels: u16,
+    pub phy_configuration: u32,
+    /// Only present for channel pages 9 and 10, one bit per channel
+    pub extended_bitmap: &'a [u8],
+    /// Channel numbers, as 16-bit little endian values
+    pub hopping_sequence: &'a [u8],
+    pub current_hop: u16,
+}
+
+impl<'a> ChannelHoppingSequence<'a> {
+    /// Returns the channel numbers of the hopping sequence.
+    pub fn channels(&self) -> impl Iterator<Item = u16> + 'a {
+        self.hopping_sequence
+            .chunks_exact(2)
+            .map(|channel| u16::from_le_bytes([channel[0], channel[1]]))
+    }
+
+    /// Number of channels in the hopping sequence.
+    pub const fn hopping_sequence_length(&self) -> usize {
+        self.hopping_sequence.len() / 2
+    }
+
+    const fn extended_bitmap_len(channel_page: u8, number_of_channels: u16) -> usize {
+        match channel_page {
+            9 | 10 => (number_of_channels as usize).div_ceil(8),
+            _ => 0,
+        }
+    }
+}
+
+/// Channel Hopping IE, describing the hopping sequence either by an ID or
+/// explicitly.
+/// Chapter 7.4.4.31
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelHoppingIe<'a> {
+    /// Hopping sequence ID only
+    Id(u8),
+    /// Hopping sequence ID and explicit sequence
+    Explicit(u8, ChannelHoppingSequence<'a>),
+}
+
+impl<'a> ChannelHoppingIe<'a> {
+    pub const fn id(&self) -> u8 {
+        match self {
+            ChannelHoppingIe::Id(id) | ChannelHoppingIe::Explicit(id, _) => *id,
+        }
+    }
+
+    /// Returns the Channel Hopping IE, the form being picked from the
+    /// content length.
+    ///
+    /// # Error
+    ///
+    /// If the content is truncated, or its length does not match the
+    /// hopping sequence length, returns `InvalidIe`.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, crate::parser::Error> {
+        const INVALID: crate::parser::Error = crate::parser::Error::InvalidIe {
+            id: long_id::CHANNEL_HOPPING as u16,
+        };
+
+        if data.len() == CHANNEL_HOPPING_ID_SIZE {
+            return Ok(ChannelHoppingIe::Id(data[0]));
+        }
+
+        if data.len() < CHANNEL_HOPPING_FIXED_SIZE {
+            return Err(INVALID);
+        }
+
+        let id = data[0];
+        let channel_page = data[1];
+        let number_of_channels = u16::from_le_bytes([data[2], data[3]]);
+        let phy_configuration = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+
+        let bitmap_len =
+            ChannelHoppingSequence::extended_bitmap_len(channel_page, number_of_channels);
+        let (extended_bitmap, rest) = data[8..].split_at_checked(bitmap_len).ok_or(INVALID)?;
+
+        let (length, rest) = rest.split_first_chunk::<2>().ok_or(INVALID)?;
+        let length = usize::from(u16::from_le_bytes(*length));
+
+        if rest.len() != 2 * length + 2 {
+            return Err(INVALID);
+        }
+
+        let (hopping_sequence, current_hop) = rest.split_at(2 * length);
+
+        Ok(ChannelHoppingIe::Explicit(
+            id,
+            ChannelHoppingSequence {
+                channel_page,
+                number_of_channels,
+                phy_configuration,
+                extended_bitmap,
+                hopping_sequence,
+                current_hop: u16::from_le_bytes([current_hop[0], current_hop[1]]),
+            },
+        ))
+    }
+
+    pub const fn serialized_len(&self) -> usize {
+        match self {
+            ChannelHoppingIe::Id(_) => CHANNEL_HOPPING_ID_SIZE,
+            ChannelHoppingIe::Explicit(_, sequence) => {
+                CHANNEL_HOPPING_FIXED_SIZE
+                    + sequence.extended_bitmap.len()
+                    + sequence.hopping_sequence.len()
+            }
+        }
+    }
+
+    /// Writes the IE content into `buf`, and returns the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`. If the hopping
+    /// sequence is not made of 16-bit values or if the extended bitmap does
+    /// not match the channel page and number of channels, returns
+    /// `InvalidHeader`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        let len = self.serialized_len();
+        let buf = buf
+            .get_mut(..len)
+            .ok_or(crate::composer::Error::BufferTooSmall { needed: len })?;
+
+        buf[0] = self.id();
+
+        if let ChannelHoppingIe::Explicit(_, sequence) = self {
+            let bitmap_len = ChannelHoppingSequence::extended_bitmap_len(
+                sequence.channel_page,
+                sequence.number_of_channels,
+            );
+
+            if sequence.extended_bitmap.len() != bitmap_len
+                || sequence.hopping_sequence.len() % 2 != 0
+            {
+                return Err(crate::composer::Error::InvalidHeader);
+            }
+
+            let length = u16::try_from(sequence.hopping_sequence_length())
+                .map_err(|_| crate::composer::Error::InvalidHeader)?;
+
+            buf[1] = sequence.channel_page;
+            buf[2..4].copy_from_slice(&sequence.number_of_channels.to_le_bytes());
+            buf[4..8].copy_from_slice(&sequence.phy_configuration.to_le_bytes());
+
+            let sequence_start = 10 + bitmap_len;
+            buf[8..sequence_start - 2].copy_from_slice(sequence.extended_bitmap);
+            buf[sequence_start - 2..sequence_start].copy_from_slice(&length.to_le_bytes());
+            buf[sequence_start..len - 2].copy_from_slice(sequence.hopping_sequence);
+            buf[len - 2..].copy_from_slice(&sequence.current_hop.to_le_bytes());
+        }
+
+        Ok(len)
+    }
+}
+
+impl<'a> TryFrom<NestedIe<'a>> for ChannelHoppingIe<'a> {
+    type Error = crate::parser::Error;
+
+    fn try_from(ie: NestedIe<'a>) -> Result<Self, Self::Error> {
+        match (ie.kind, ie.sub_id) {
+            (NestedIeKind::Long, long_id::CHANNEL_HOPPING) => {
+                ChannelHoppingIe::from_bytes(ie.content)
+            }
+            _ => Err(crate::parser::Error::InvalidIe {
+                id: ie.sub_id.into(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ieee802154::ie::nested::{short_id, NestedIe, NestedIeKind};
+
+    use super::{
+        Asn, ChannelHoppingIe, ChannelHoppingSequence, Link, LinkOptions, Slotframe,
+        SlotframeAndLink, TimeslotTemplate, TschSynchronization, TschTimeslot, MAX_LINKS,
+    };
+
+    #[test]
+    fn asn_uses_40_bits_little_endian() {
+        let asn = Asn::from_bytes(&[0x01, 0x02, 0x03, 0x04, 0x05]);
+
+        assert_eq!(u64::from(asn), 0x05_0403_0201);
+        assert_eq!(asn.bytes(), [0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(Asn::new(0xFFFF_FF05_0403_0201).value(), 0x05_0403_0201);
+        assert_eq!(Asn::new(u64::MAX).value(), Asn::MAX);
+    }
+
+    #[test]
+    fn from_bytes_eq_bytes() {
+        let content: [u8; 6] = [0x0E, 0x00, 0x00, 0x00, 0x01, 0x04];
+
+        let sync = TschSynchronization::from_bytes(&content).unwrap();
+
+        assert_eq!(sync.asn.value(), 0x01_0000_000E);
+        assert_eq!(sync.join_metric, 0x04);
+        assert_eq!(sync.bytes(), content);
+    }
+
+    #[test]
+    fn from_nested_ie() {
+        let ie = NestedIe {
+            kind: NestedIeKind::Short,
+            sub_id: short_id::TSCH_SYNCHRONIZATION,
+            content: &[0x0E, 0x00, 0x00, 0x00, 0x00, 0x00],
+        };
+
+        assert_eq!(
+            TschSynchronization::try_from(ie).unwrap(),
+            TschSynchronization {
+                asn: Asn::new(0x0E),
+                join_metric: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn from_nested_ie_with_other_sub_id() {
+        let ie = NestedIe {
+            kind: NestedIeKind::Short,
+            sub_id: short_id::TSCH_TIMESLOT,
+            content: &[0x0E, 0x00, 0x00, 0x00, 0x00, 0x00],
+        };
+
+        assert!(matches!(
+            TschSynchronization::try_from(ie),
+            Err(crate::parser::Error::InvalidIe { id: 0x1C })
+        ));
+    }
+
+    #[test]
+    fn truncated_content() {
+        assert!(matches!(
+            TschSynchronization::from_bytes(&[0x0E, 0x00, 0x00, 0x00, 0x00]),
+            Err(crate::parser::Error::InvalidIe { id: 0x1A })
+        ));
+        assert!(matches!(
+            TschSynchronization::from_bytes(&[]),
+            Err(crate::parser::Error::InvalidIe { id: 0x1A })
+        ));
+    }
+
+    #[test]
+    fn link_options_bits() {
+        for value in 0..0x20 {
+            assert_eq!(LinkOptions::from_byte(value).bits(), value);
+        }
+
+        assert_eq!(
+            LinkOptions::from_byte(0x0F),
+            LinkOptions {
+                tx: true,
+                rx: true,
+                shared: true,
+                timekeeping: true,
+                priority: false,
+            }
+        );
+    }
+
+    #[test]
+    fn slotframe_and_link_minimal_configuration() {
+        // RFC 8180: one slotframe of 101 timeslots, one shared link in slot 0
+        let content: [u8; 10] = [0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F];
+
+        let ie = SlotframeAndLink::from_bytes(&content).unwrap();
+
+        assert_eq!(ie.slotframes.len(), 1);
+
+        let slotframe = &ie.slotframes[0];
+        assert_eq!(slotframe.handle, 0);
+        assert_eq!(slotframe.size, 101);
+        assert_eq!(slotframe.links.len(), 1);
+        assert_eq!(slotframe.links[0].timeslot, 0);
+        assert_eq!(slotframe.links[0].channel_offset, 0);
+        assert_eq!(slotframe.links[0].options, LinkOptions::from_byte(0x0F));
+
+        let mut buf = [0u8; 10];
+        assert_eq!(ie.serialized_len(), content.len());
+        assert_eq!(ie.write_into(&mut buf).unwrap(), content.len());
+        assert_eq!(buf, content);
+    }
+
+    #[test]
+    fn slotframe_and_link_multiple_slotframes() {
+        let content: [u8; 24] = [
+            0x02, 0x00, 0x65, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x05, 0x00, 0x03, 0x00,
+            0x01, 0x01, 0x07, 0x00, 0x01, 0x02, 0x00, 0x04, 0x00, 0x02,
+        ];
+
+        let ie = SlotframeAndLink::from_bytes(&content).unwrap();
+
+        assert_eq!(ie.slotframes.len(), 2);
+        assert_eq!(ie.slotframes[0].links.len(), 2);
+        assert_eq!(
+            ie.slotframes[0].links[1],
+            Link {
+                timeslot: 5,
+                channel_offset: 3,
+                options: LinkOptions {
+                    tx: true,
+                    ..LinkOptions::default()
+                },
+            }
+        );
+        assert_eq!(ie.slotframes[1].handle, 1);
+        assert_eq!(ie.slotframes[1].size, 7);
+        assert_eq!(
+            ie.slotframes[1].links[0],
+            Link {
+                timeslot: 2,
+                channel_offset: 4,
+                options: LinkOptions {
+                    rx: true,
+                    ..LinkOptions::default()
+                },
+            }
+        );
+
+        let mut buf = [0u8; 24];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), content.len());
+        assert_eq!(buf, content);
+    }
+
+    #[test]
+    fn slotframe_and_link_truncated_content() {
+        let content: [u8; 10] = [0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F];
+
+        for len in 0..content.len() {
+            assert!(matches!(
+                SlotframeAndLink::from_bytes(&content[..len]),
+                Err(crate::parser::Error::InvalidIe { id: 0x1B })
+            ));
+        }
+    }
+
+    #[test]
+    fn slotframe_and_link_too_many_links() {
+        let mut content = [0u8; 5 + (MAX_LINKS + 1) * 5];
+        content[0] = 0x01;
+        content[4] = (MAX_LINKS + 1) as u8;
+
+        assert!(matches!(
+            SlotframeAndLink::from_bytes(&content),
+            Err(crate::parser::Error::InvalidIe { id: 0x1B })
+        ));
+    }
+
+    #[test]
+    fn slotframe_and_link_write_into_small_buffer() {
+        let mut ie = SlotframeAndLink::default();
+        ie.slotframes
+            .push(Slotframe {
+                handle: 0,
+                size: 101,
+                links: heapless::Vec::new(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            ie.write_into(&mut [0u8; 4]),
+            Err(crate::composer::Error::BufferTooSmall { needed: 5 })
+        );
+    }
+
+    /// Timeslot IE carrying the default 2450 MHz template.
+    const DEFAULT_TIMESLOT: [u8; 25] = [
+        0x00, 0x08, 0x07, 0x80, 0x00, 0x48, 0x08, 0xFC, 0x03, 0x20, 0x03, 0xE8, 0x03, 0x98, 0x08,
+        0x90, 0x01, 0xC0, 0x00, 0x60, 0x09, 0xA0, 0x10, 0x10, 0x27,
+    ];
+
+    #[test]
+    fn timeslot_id_only() {
+        let ie = TschTimeslot::from_bytes(&[0x02]).unwrap();
+
+        assert_eq!(ie, TschTimeslot::Id(2));
+        assert_eq!(ie.template(), None);
+
+        let mut buf = [0u8; 1];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 1);
+        assert_eq!(buf, [0x02]);
+    }
+
+    #[test]
+    fn timeslot_full_template() {
+        let ie = TschTimeslot::from_bytes(&DEFAULT_TIMESLOT).unwrap();
+
+        assert_eq!(ie, TschTimeslot::Full(0, TimeslotTemplate::DEFAULT_2450MHZ));
+
+        let mut buf = [0u8; 25];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 25);
+        assert_eq!(buf, DEFAULT_TIMESLOT);
+    }
+
+    #[test]
+    fn timeslot_extended_template() {
+        let template = TimeslotTemplate {
+            max_tx: 0x01_0203,
+            timeslot_length: 0x04_0506,
+            ..TimeslotTemplate::DEFAULT_2450MHZ
+        };
+        let ie = TschTimeslot::Extended(1, template);
+
+        let mut buf = [0u8; 27];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 27);
+        assert_eq!(buf[1..21], DEFAULT_TIMESLOT[1..21]);
+        assert_eq!(buf[0], 1);
+        assert_eq!(buf[21..], [0x03, 0x02, 0x01, 0x06, 0x05, 0x04]);
+        assert_eq!(TschTimeslot::from_bytes(&buf).unwrap(), ie);
+    }
+
+    #[test]
+    fn timeslot_full_template_with_wide_values() {
+        let template = TimeslotTemplate {
+            timeslot_length: 0x1_0000,
+            ..TimeslotTemplate::DEFAULT_2450MHZ
+        };
+
+        assert!(matches!(
+            TschTimeslot::Full(0, template).write_into(&mut [0u8; 25]),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn timeslot_invalid_length() {
+        assert!(matches!(
+            TschTimeslot::from_bytes(&[]),
+            Err(crate::parser::Error::InvalidIe { id: 0x1C })
+        ));
+        assert!(matches!(
+            TschTimeslot::from_bytes(&DEFAULT_TIMESLOT[..24]),
+            Err(crate::parser::Error::InvalidIe { id: 0x1C })
+        ));
+    }
+
+    #[test]
+    fn channel_hopping_id_only() {
+        let ie = NestedIe {
+            kind: NestedIeKind::Long,
+            sub_id: 0x9,
+            content: &[0x00],
+        };
+
+        assert_eq!(ChannelHoppingIe::try_from(ie), Ok(ChannelHoppingIe::Id(0)));
+
+        let mut buf = [0xFFu8; 1];
+        assert_eq!(ChannelHoppingIe::Id(0).write_into(&mut buf).unwrap(), 1);
+        assert_eq!(buf, [0x00]);
+    }
+
+    #[test]
+    fn channel_hopping_explicit() {
+        let input: [u8; 18] = [
+            0x01, 0x00, 0x10, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x03, 0x00, 0x0F, 0x00, 0x14, 0x00,
+            0x19, 0x00, 0x01, 0x00,
+        ];
+
+        let ie = ChannelHoppingIe::from_bytes(&input).unwrap();
+        let ChannelHoppingIe::Explicit(id, sequence) = ie else {
+            panic!("expected an explicit channel hopping IE");
+        };
+
+        assert_eq!(id, 1);
+        assert_eq!(sequence.channel_page, 0);
+        assert_eq!(sequence.number_of_channels, 16);
+        assert_eq!(sequence.phy_configuration, 0xFFFF);
+        assert!(sequence.extended_bitmap.is_empty());
+        assert_eq!(sequence.hopping_sequence_length(), 3);
+        assert!(sequence.channels().eq([15, 20, 25]));
+        assert_eq!(sequence.current_hop, 1);
+
+        let mut buf = [0u8; 18];
+        assert_eq!(ie.serialized_len(), 18);
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 18);
+        assert_eq!(buf, input);
+    }
+
+    #[test]
+    fn channel_hopping_extended_bitmap() {
+        let input: [u8; 16] = [
+            0x02, 0x09, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x0F, 0x01, 0x00, 0x0B, 0x00,
+            0x00, 0x00,
+        ];
+
+        let ie = ChannelHoppingIe::from_bytes(&input).unwrap();
+        let ChannelHoppingIe::Explicit(_, sequence) = ie else {
+            panic!("expected an explicit channel hopping IE");
+        };
+
+        assert_eq!(sequence.extended_bitmap, &[0xFF, 0x0F]);
+        assert!(sequence.channels().eq([11]));
+
+        let mut buf = [0u8; 16];
+        assert_eq!(ie.write_into(&mut buf).unwrap(), 16);
+        assert_eq!(buf, input);
+    }
+
+    #[test]
+    fn channel_hopping_length_mismatch() {
+        let input: [u8; 14] = [
+            0x01, 0x00, 0x10, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x02, 0x00, 0x0F, 0x00, 0x01, 0x00,
+        ];
+
+        assert!(matches!(
+            ChannelHoppingIe::from_bytes(&input),
+            Err(crate::parser::Error::InvalidIe { id: 0x9 })
+        ));
+        assert!(matches!(
+            ChannelHoppingIe::from_bytes(&input[..5]),
+            Err(crate::parser::Error::InvalidIe { id: 0x9 })
+        ));
+    }
+
+    #[test]
+    fn channel_hopping_odd_sequence() {
+        let ie = ChannelHoppingIe::Explicit(
+            0,
+            ChannelHoppingSequence {
+                channel_page: 0,
+                number_of_channels: 16,
+                phy_configuration: 0,
+                extended_bitmap: &[],
+                hopping_sequence: &[0x0F],
+                current_hop: 0,
+            },
+        );
+
+        assert!(matches!(
+            ie.write_into(&mut [0u8; 13]),
+            Err(crate::composer::Error::InvalidHeader)
+        ));
+    }
+}
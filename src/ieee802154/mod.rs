@@ -1,8 +1,92 @@
 pub mod address;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 pub mod composer;
 pub mod control_field;
+pub mod fcs;
+pub mod filter;
 pub mod frame;
+pub mod ie;
 pub mod parser;
+pub mod phy;
+pub mod security;
 pub mod security_header;
+#[cfg(test)]
+mod security_vectors;
 
 pub struct Ieee802154 {}
+
+/// aMaxPhyPacketSize, the maximum PSDU size in bytes.
+/// Chapter 11.3
+pub const MAX_PHY_PACKET_SIZE: usize = 127;
+/// aMaxPhyPacketSize of the SUN PHYs, in bytes.
+/// Chapter 11.3
+pub const MAX_SUN_PHY_PACKET_SIZE: usize = 2047;
+
+/// Maximum frame size accepted when parsing and composing.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PsduLimit {
+    /// [`MAX_PHY_PACKET_SIZE`]
+    #[default]
+    Standard,
+    /// [`MAX_SUN_PHY_PACKET_SIZE`], for 802.15.4g SUN PHYs
+    Sun,
+}
+
+impl PsduLimit {
+    pub const fn max(&self) -> usize {
+        match self {
+            PsduLimit::Standard => MAX_PHY_PACKET_SIZE,
+            PsduLimit::Sun => MAX_SUN_PHY_PACKET_SIZE,
+        }
+    }
+
+    /// # Error
+    ///
+    /// If `len` is over the limit, returns `FrameTooLong`.
+    pub const fn check_parse(&self, len: usize) -> Result<(), crate::parser::Error> {
+        match len > self.max() {
+            true => Err(crate::parser::Error::FrameTooLong {
+                len,
+                max: self.max(),
+            }),
+            false => Ok(()),
+        }
+    }
+
+    /// # Error
+    ///
+    /// If `len` is over the limit, returns `FrameTooLong`.
+    pub const fn check_compose(&self, len: usize) -> Result<(), crate::composer::Error> {
+        match len > self.max() {
+            true => Err(crate::composer::Error::FrameTooLong {
+                len,
+                max: self.max(),
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PsduLimit;
+
+    #[test]
+    fn check_compose_at_limits() {
+        assert!(PsduLimit::Standard.check_compose(127).is_ok());
+        assert!(matches!(
+            PsduLimit::Standard.check_compose(128),
+            Err(crate::composer::Error::FrameTooLong { len: 128, max: 127 })
+        ));
+        assert!(PsduLimit::Sun.check_compose(2047).is_ok());
+        assert!(matches!(
+            PsduLimit::Sun.check_compose(2048),
+            Err(crate::composer::Error::FrameTooLong {
+                len: 2048,
+                max: 2047
+            })
+        ));
+    }
+}
@@ -1,11 +1,2422 @@
-use crate::parser::Parser;
+use byte::{ctx::Bytes, BytesExt, TryRead, LE};
 
-use super::{frame::Frame, Ieee802154};
+use crate::parser::{Error, ParseError, ParseMode, Parser, Section};
+
+use super::{
+    address::{wire, AddressField, AddressKind, AddressingMode, PanId},
+    control_field::{
+        mask, offset, FrameKind, FrameVersion, MultipurposeControlField, StandardControlField,
+        STANDARD_CONTROL_FIELD_SIZE,
+    },
+    fcs::FcsPolicy,
+    frame::{
+        ack::AckFrame,
+        beacon::{
+            gts::Gts, pending_address::PendingAddress, BeaconFrame, BeaconHeader, BeaconPayload,
+            SuperFrame,
+        },
+        command::{CommandId, MacCommandFrame, COMMAND_ID_SIZE},
+        data::DataFrame,
+        enh_ack::EnhAckFrame,
+        enh_beacon::EnhBeaconFrame,
+        multipurpose::{self, MultipurposeFrame},
+        Frame, MacHeader, ReservedFrame,
+    },
+    ie::{
+        header::{element_id, HeaderIeIterator},
+        payload::PayloadIeIterator,
+        time_correction::TimeCorrection,
+        HeaderTermination, IE_DESCRIPTOR_SIZE,
+    },
+    security_header::{AuxiliarySecurityHeader, KeyIdentifierMode, Mic},
+    Ieee802154, PsduLimit, MAX_PHY_PACKET_SIZE,
+};
+
+/// Options controlling how frames are parsed.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParseContext {
+    pub mode: ParseMode,
+    /// Longer inputs are rejected
+    pub limit: PsduLimit,
+    /// The private payload of secured frames is already decrypted, so that
+    /// payload IEs are decoded whatever the security level
+    pub decrypted: bool,
+}
+
+/// Header fields of a frame checked by [`Ieee802154::validate`].
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSummary {
+    pub frame_kind: FrameKind,
+    /// `None` if the sequence number is suppressed
+    pub seq_no: Option<u8>,
+    /// Destination PAN ID, also set for a PAN ID field without address
+    pub dst_pan: Option<PanId>,
+    pub dst_addr: Option<AddressKind>,
+    /// Size in bytes of the MAC header, header IEs included
+    pub header_len: usize,
+}
+
+/// Cursor over the input keeping track of the section being decoded, so that
+/// every error can be reported with its location.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    section: Section,
+    mode: ParseMode,
+    /// MIC of a secured frame, split off the end of `bytes`
+    mic: &'a [u8],
+    /// Whether the private payload is already decrypted
+    decrypted: bool,
+    /// Set once the auxiliary security header is read, if the private
+    /// payload is still encrypted
+    encrypted: bool,
+}
+
+impl<'a> Reader<'a> {
+    /// # Error
+    ///
+    /// If `bytes` is over the length limit of `context`, returns
+    /// `FrameTooLong`.
+    const fn new(bytes: &'a [u8], context: ParseContext) -> Result<Self, ParseError> {
+        match context.limit.check_parse(bytes.len()) {
+            Ok(()) => Ok(Reader {
+                bytes,
+                offset: 0,
+                section: Section::ControlField,
+                mode: context.mode,
+                mic: &[],
+                decrypted: context.decrypted,
+                encrypted: false,
+            }),
+            Err(kind) => Err(ParseError::new(kind, 0, Section::ControlField)),
+        }
+    }
+
+    fn enter(&mut self, section: Section) {
+        self.section = section;
+    }
+
+    const fn error(&self, kind: Error) -> ParseError {
+        ParseError::new(kind, self.offset, self.section)
+    }
+
+    /// Error reported when the input ends before the current section does.
+    const fn truncated(&self) -> ParseError {
+        self.error(Error::Truncated)
+    }
+
+    fn read<T: TryRead<'a, C>, C: Default>(&mut self) -> Result<T, ParseError> {
+        self.bytes
+            .read(&mut self.offset)
+            .map_err(|_| self.truncated())
+    }
+
+    fn read_le<T: TryRead<'a, byte::ctx::Endian>>(&mut self) -> Result<T, ParseError> {
+        self.bytes
+            .read_with(&mut self.offset, LE)
+            .map_err(|_| self.truncated())
+    }
+
+    /// Runs `f` at the current offset, which it moves past the field it
+    /// reads.
+    fn read_field<T>(
+        &mut self,
+        f: impl FnOnce(&'a [u8], &mut usize) -> Result<T, Error>,
+    ) -> Result<T, ParseError> {
+        let bytes = self.bytes;
+
+        f(bytes, &mut self.offset).map_err(|kind| self.error(kind))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        self.bytes
+            .read_with(&mut self.offset, Bytes::Len(len))
+            .map_err(|_| self.truncated())
+    }
+
+    /// Checks that the frame ends with the input. Only enforced in strict
+    /// mode, for frames whose end is known from their content.
+    const fn finish(&self) -> Result<(), ParseError> {
+        match (self.mode, self.remaining()) {
+            (ParseMode::Strict, count) if count != 0 => {
+                Err(self.error(Error::TrailingBytes { count }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    const fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.bytes[self.offset..];
+        self.offset = self.bytes.len();
+        rest
+    }
+
+    /// Splits the last `len` bytes of the input off as the MIC, so that the
+    /// fields following the auxiliary security header end before it.
+    ///
+    /// # Error
+    ///
+    /// If fewer than `len` bytes remain, returns `Truncated`.
+    fn split_mic(&mut self, len: usize) -> Result<(), ParseError> {
+        match self.bytes.len().checked_sub(len) {
+            Some(end) if end >= self.offset => {
+                (self.bytes, self.mic) = self.bytes.split_at(end);
+                Ok(())
+            }
+            _ => Err(ParseError::new(
+                Error::Truncated,
+                self.bytes.len(),
+                Section::Payload,
+            )),
+        }
+    }
+
+    /// Number of bytes decoded so far, the MIC included.
+    const fn consumed(&self) -> usize {
+        self.offset + self.mic.len()
+    }
+
+    /// Runs `f` on the remaining input. Errors are reported at the current
+    /// offset since `f` does not know where its slice starts.
+    fn with_rest<T>(&self, f: impl FnOnce(&'a [u8]) -> Result<T, Error>) -> Result<T, ParseError> {
+        f(&self.bytes[self.offset..]).map_err(|kind| self.error(kind))
+    }
+}
+
+/// Decoded addressing fields of a MAC header.
+struct Addressing {
+    dst: AddressField,
+    src: Option<AddressKind>,
+}
+
+/// Returns whether the destination and source PAN ID fields are present.
+/// Chapter 7.2.2.6
+pub(crate) fn pan_id_presence(
+    version: &FrameVersion,
+    dst_mode: AddressingMode,
+    src_mode: AddressingMode,
+    compression: bool,
+) -> Result<(bool, bool), Error> {
+    let dst = dst_mode != AddressingMode::None;
+    let src = src_mode != AddressingMode::None;
+
+    match version {
+        FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006 => {
+            if compression && !(dst && src) {
+                return Err(Error::InvalidPanIdCompression);
+            }
+
+            Ok((dst, src && !compression))
+        }
+        // Table 7-2, also used for reserved versions in lenient mode
+        FrameVersion::Ieee802154 | FrameVersion::Reserved(_) => match (dst, src, compression) {
+            (false, false, compression) => Ok((compression, false)),
+            (true, false, compression) => Ok((!compression, false)),
+            (false, true, compression) => Ok((false, !compression)),
+            (true, true, _)
+                if dst_mode == AddressingMode::Long && src_mode == AddressingMode::Long =>
+            {
+                Ok((!compression, false))
+            }
+            (true, true, compression) => Ok((true, !compression)),
+        },
+    }
+}
+
+fn read_address(
+    reader: &mut Reader,
+    mode: AddressingMode,
+    pan: PanId,
+) -> Result<Option<AddressKind>, ParseError> {
+    match mode {
+        AddressingMode::None => Ok(None),
+        AddressingMode::Short => Ok(Some(AddressKind::Short(
+            reader.read_field(|bytes, offset| wire::read_short(bytes, offset, pan))?,
+        ))),
+        AddressingMode::Long => Ok(Some(AddressKind::Long(
+            reader.read_field(|bytes, offset| wire::read_long(bytes, offset, pan))?,
+        ))),
+    }
+}
+
+/// Reads the destination address, keeping its PAN ID if it has no address
+/// field.
+fn read_dst_address(
+    reader: &mut Reader,
+    mode: AddressingMode,
+    pan: Option<PanId>,
+) -> Result<AddressField, ParseError> {
+    let address = read_address(reader, mode, pan.unwrap_or(PanId::BROADCAST))?;
+
+    Ok(match (address, pan) {
+        (Some(address), _) => AddressField::Present(address),
+        (None, Some(pan)) => AddressField::PanOnly(pan),
+        (None, None) => AddressField::Absent,
+    })
+}
+
+/// Reads the addressing fields following the sequence number.
+///
+/// When a PAN ID field is elided, the address takes the PAN ID of the other
+/// address, or the broadcast PAN ID if there is none.
+fn read_addressing(
+    reader: &mut Reader,
+    control: &StandardControlField,
+) -> Result<Addressing, ParseError> {
+    reader.enter(Section::Addressing);
+
+    // In lenient mode, reserved addressing modes are read as no address
+    let [dst_mode, src_mode] =
+        [control.dst_addressing_mode(), control.src_addressing_mode()].map(|mode| {
+            match (mode, reader.mode) {
+                (Ok(mode), _) => Ok(mode),
+                (Err(kind), ParseMode::Strict) => Err(reader.error(kind)),
+                (Err(_), ParseMode::Lenient) => Ok(AddressingMode::None),
+            }
+        });
+    let (dst_mode, src_mode) = (dst_mode?, src_mode?);
+
+    let (dst_pan_present, src_pan_present) = pan_id_presence(
+        &control.version,
+        dst_mode,
+        src_mode,
+        control.pan_id_compression,
+    )
+    .map_err(|kind| reader.error(kind))?;
+
+    let dst_pan: Option<PanId> = match dst_pan_present {
+        true => Some(reader.read_field(wire::read_pan)?),
+        false => None,
+    };
+    let dst = read_dst_address(reader, dst_mode, dst_pan)?;
+
+    let src_pan: Option<PanId> = match src_pan_present {
+        true => Some(reader.read_field(wire::read_pan)?),
+        false => dst_pan,
+    };
+    let src = read_address(reader, src_mode, src_pan.unwrap_or(PanId::BROADCAST))?;
+
+    Ok(Addressing { dst, src })
+}
+
+fn read_security_header(
+    reader: &mut Reader,
+    security_enabled: bool,
+    version: &FrameVersion,
+) -> Result<Option<AuxiliarySecurityHeader>, ParseError> {
+    reader.enter(Section::SecurityHeader);
+
+    if !security_enabled {
+        return Ok(None);
+    }
+
+    let start = reader.offset;
+    let mode = reader.mode;
+    let (aux, len) =
+        reader.with_rest(|data| AuxiliarySecurityHeader::from_bytes_with_mode(data, mode))?;
+    reader.take(len)?;
+    let security_control = aux.security_control_bits();
+
+    if reader.mode == ParseMode::Strict {
+        let kind = match &aux.security_level {
+            // ENC without MIC is only defined by IEEE 802.15.4-2006
+            Some(level) if level.mic == Mic::None && *version != FrameVersion::Ieee802154_2006 => {
+                Some(Error::InvalidSecurityLevel(level.bits()))
+            }
+            None if aux.key_identifier_mode != KeyIdentifierMode::Implicit => {
+                Some(Error::InvalidKeyIdMode((security_control >> 3) & 0x3))
+            }
+            // Chapter 9.4.2.3, the bit is reserved in earlier versions
+            _ if aux.frame_counter_suppressed()
+                && matches!(
+                    version,
+                    FrameVersion::Ieee802154_2003 | FrameVersion::Ieee802154_2006
+                ) =>
+            {
+                Some(Error::InvalidFrameCounterSuppression)
+            }
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            return Err(ParseError::new(kind, start, reader.section));
+        }
+    }
+
+    // Chapter 9.3.1, the MIC ends the MAC payload
+    reader.split_mic(
+        aux.security_level
+            .as_ref()
+            .map_or(0, |level| level.mic.size()),
+    )?;
+    reader.encrypted = !reader.decrypted
+        && aux
+            .security_level
+            .as_ref()
+            .is_some_and(|level| level.encrypted);
+
+    Ok(Some(aux))
+}
+
+/// Walks the header IE list once to check it and find where it ends, and
+/// returns an iterator over it along with its termination.
+fn read_header_ies<'a>(
+    reader: &mut Reader<'a>,
+    ie_present: bool,
+) -> Result<(HeaderIeIterator<'a>, HeaderTermination), ParseError> {
+    reader.enter(Section::InformationElements);
+
+    if !ie_present {
+        return Ok((HeaderIeIterator::new(&[]), HeaderTermination::None));
+    }
+
+    let mut ies = HeaderIeIterator::new(&reader.bytes[reader.offset..]);
+
+    for ie in ies.by_ref() {
+        if let Err(kind) = ie {
+            return Err(ParseError::new(
+                kind,
+                reader.offset + ies.offset(),
+                reader.section,
+            ));
+        }
+    }
+
+    // Chapter 7.2.2.10, the IE Present field announces at least one IE
+    if reader.mode == ParseMode::Strict && ies.offset() == 0 {
+        return Err(ParseError::new(
+            Error::InvalidHeader,
+            0,
+            Section::ControlField,
+        ));
+    }
+
+    Ok((
+        HeaderIeIterator::new(reader.take(ies.offset())?),
+        ies.termination(),
+    ))
+}
+
+/// Walks the payload IE list following a Header Termination 1 IE, and
+/// returns an iterator over it.
+///
+/// Without a Payload Termination IE, the list runs to the end of the frame
+/// and there is no unformatted payload. Encrypted payload IEs are part of
+/// the private payload, they are left in the payload until the frame is
+/// unsecured.
+/// Chapter 9.3.5
+fn read_payload_ies<'a>(
+    reader: &mut Reader<'a>,
+    termination: HeaderTermination,
+) -> Result<PayloadIeIterator<'a>, ParseError> {
+    reader.enter(Section::InformationElements);
+
+    if termination != HeaderTermination::PayloadIes || reader.encrypted {
+        return Ok(PayloadIeIterator::new(&[]));
+    }
+
+    let mut ies = PayloadIeIterator::new(&reader.bytes[reader.offset..]);
+
+    for ie in ies.by_ref() {
+        if let Err(kind) = ie {
+            return Err(ParseError::new(
+                kind,
+                reader.offset + ies.offset(),
+                reader.section,
+            ));
+        }
+    }
+
+    Ok(PayloadIeIterator::new(reader.take(ies.offset())?))
+}
+
+/// Reads the frame control field.
+fn read_control(reader: &mut Reader) -> Result<StandardControlField, ParseError> {
+    let raw: [u8; STANDARD_CONTROL_FIELD_SIZE] = reader
+        .take(STANDARD_CONTROL_FIELD_SIZE)?
+        .try_into()
+        .map_err(|_| reader.truncated())?;
+    let control = StandardControlField::from_bytes_with_mode(&raw, reader.mode)
+        .map_err(|kind| ParseError::new(kind, 0, Section::ControlField))?;
+
+    Ok(control)
+}
+
+/// Reads the MAC header, and returns it along with the termination of the
+/// header IEs.
+fn read_header<'a>(
+    reader: &mut Reader<'a>,
+) -> Result<(MacHeader<'a>, HeaderTermination), ParseError> {
+    let control = read_control(reader)?;
+
+    read_header_fields(reader, control)
+}
+
+/// Reads the MAC header fields following the frame control field.
+fn read_header_fields<'a>(
+    reader: &mut Reader<'a>,
+    control: StandardControlField,
+) -> Result<(MacHeader<'a>, HeaderTermination), ParseError> {
+    reader.enter(Section::SequenceNumber);
+    let seq_no: Option<u8> = match control.seq_no_present {
+        true => Some(reader.read()?),
+        false => None,
+    };
+
+    let addressing = read_addressing(reader, &control)?;
+
+    // Chapter 7.2.2.3 and 7.2.2.4
+    let invalid = (control.ack_required
+        && (control.frame_kind == FrameKind::Acknowledgment || addressing.dst.is_broadcast()))
+        || (control.frame_pending && control.frame_kind == FrameKind::MacCommand);
+
+    if reader.mode == ParseMode::Strict && invalid {
+        return Err(ParseError::new(
+            Error::InvalidHeader,
+            0,
+            Section::ControlField,
+        ));
+    }
+
+    let aux = read_security_header(reader, control.security_enabled, &control.version)?;
+    let (header_ies, termination) = read_header_ies(reader, control.ie_present)?;
+
+    Ok((
+        MacHeader {
+            control: control.into(),
+            seq_no,
+            dst_addr: addressing.dst,
+            src_addr: addressing.src,
+            aux,
+            header_ies,
+        },
+        termination,
+    ))
+}
+
+fn parse_beacon<'a>(
+    reader: &mut Reader<'a>,
+    header: MacHeader<'a>,
+) -> Result<Frame<'a>, ParseError> {
+    let Some(pan) = header.src_addr.as_ref().map(AddressKind::pan_id) else {
+        return Err(reader.error(Error::InvalidHeader));
+    };
+
+    reader.enter(Section::SuperFrame);
+    let super_frame_error = reader.error(Error::InvalidSuperFrameSpec);
+    let super_frame: SuperFrame = reader.read_le()?;
+
+    reader.enter(Section::Gts);
+    let (gts, gts_len) = reader.with_rest(|data| Gts::from_bytes(pan, data))?;
+    reader.take(gts_len)?;
+
+    if reader.mode == ParseMode::Strict {
+        super_frame.validate(&gts).map_err(|kind| ParseError {
+            kind,
+            ..super_frame_error
+        })?;
+    }
+
+    reader.enter(Section::PendingAddress);
+    let pending_address = reader.with_rest(|data| PendingAddress::from_bytes(pan, data))?;
+    reader.take(pending_address.serialized_len())?;
+
+    reader.enter(Section::Payload);
+    let data = reader.rest();
+
+    Ok(Frame::Beacon(BeaconFrame {
+        header: BeaconHeader {
+            control: header.control,
+            seq_no: header.seq_no,
+            src_addr: header.src_addr,
+            dst_addr: header.dst_addr,
+            aux: header.aux,
+            header_ies: header.header_ies,
+        },
+        payload: BeaconPayload {
+            super_frame,
+            gts,
+            pending_address,
+            data,
+        },
+        mic: reader.mic,
+    }))
+}
+
+/// Decodes an enhanced beacon, whose content is carried by IEs.
+fn parse_enh_beacon<'a>(
+    reader: &mut Reader<'a>,
+    header: MacHeader<'a>,
+    termination: HeaderTermination,
+) -> Result<Frame<'a>, ParseError> {
+    let payload_ies = read_payload_ies(reader, termination)?;
+
+    reader.enter(Section::Payload);
+    let payload = reader.rest();
+
+    Ok(Frame::EnhBeacon(EnhBeaconFrame {
+        header,
+        payload_ies,
+        payload,
+        mic: reader.mic,
+    }))
+}
+
+fn parse_data<'a>(
+    reader: &mut Reader<'a>,
+    header: MacHeader<'a>,
+    termination: HeaderTermination,
+) -> Result<Frame<'a>, ParseError> {
+    let payload_ies = read_payload_ies(reader, termination)?;
+
+    reader.enter(Section::Payload);
+    let payload = reader.rest();
+
+    Ok(Frame::Data(DataFrame {
+        header,
+        payload_ies,
+        payload,
+        mic: reader.mic,
+    }))
+}
+
+/// Decodes a MAC command, whose identifier follows the payload IEs.
+///
+/// # Error
+///
+/// If the payload IEs are encrypted, the command identifier following them
+/// is as well and `EncryptedCommand` is returned. In strict mode, commands
+/// of fixed size whose content is cut short are `Truncated`.
+fn parse_command<'a>(
+    reader: &mut Reader<'a>,
+    header: MacHeader<'a>,
+    termination: HeaderTermination,
+) -> Result<Frame<'a>, ParseError> {
+    let payload_ies = read_payload_ies(reader, termination)?;
+
+    reader.enter(Section::Payload);
+
+    if reader.encrypted && termination == HeaderTermination::PayloadIes {
+        return Err(reader.error(Error::EncryptedCommand));
+    }
+
+    let mode = reader.mode;
+    let command_id = reader.with_rest(|data| {
+        CommandId::from_byte_with_mode(*data.first().ok_or(Error::Truncated)?, mode)
+    })?;
+    reader.take(COMMAND_ID_SIZE)?;
+
+    // The end of commands of fixed size is known from their identifier
+    let content = match command_id.content_size() {
+        Some(size) if reader.remaining() >= size => {
+            let content = reader.take(size)?;
+            reader.finish()?;
+            content
+        }
+        Some(_) if mode == ParseMode::Strict => return Err(reader.truncated()),
+        _ => reader.rest(),
+    };
+
+    Ok(Frame::MacCommand(MacCommandFrame {
+        header,
+        payload_ies,
+        command_id,
+        content,
+        mic: reader.mic,
+    }))
+}
+
+/// Decodes an immediate acknowledgment, made of the frame control field and
+/// sequence number only.
+fn parse_ack<'a>(
+    reader: &mut Reader<'a>,
+    header: MacHeader<'a>,
+    version: FrameVersion,
+) -> Result<Frame<'a>, ParseError> {
+    let seq_no = match (
+        header.seq_no,
+        &header.dst_addr,
+        &header.src_addr,
+        &header.aux,
+    ) {
+        (Some(seq_no), AddressField::Absent, None, None) => seq_no,
+        _ => {
+            return Err(ParseError::new(
+                Error::InvalidHeader,
+                0,
+                Section::ControlField,
+            ))
+        }
+    };
+
+    reader.enter(Section::Payload);
+    reader.finish()?;
+
+    Ok(Frame::Acknowledgment(AckFrame {
+        version,
+        frame_pending: header.control.frame_pending(),
+        seq_no,
+    }))
+}
+
+/// Decodes an enhanced acknowledgment. A Time Correction IE leading the
+/// header IEs is taken out of them.
+fn parse_enh_ack<'a>(
+    reader: &mut Reader<'a>,
+    mut header: MacHeader<'a>,
+    termination: HeaderTermination,
+) -> Result<Frame<'a>, ParseError> {
+    let ies = header.header_ies.as_bytes();
+    let time_correction = match header.header_ies.clone().next() {
+        Some(Ok(ie)) if ie.element_id == element_id::TIME_CORRECTION => {
+            let start = reader.offset - ies.len();
+            let correction = TimeCorrection::try_from(ie)
+                .map_err(|kind| ParseError::new(kind, start, Section::InformationElements))?;
+
+            header.header_ies = HeaderIeIterator::new(&ies[IE_DESCRIPTOR_SIZE + ie.length()..]);
+            Some(correction)
+        }
+        _ => None,
+    };
+
+    let payload_ies = read_payload_ies(reader, termination)?;
+
+    reader.enter(Section::Payload);
+    let payload = reader.rest();
+
+    Ok(Frame::EnhAcknowledgment(EnhAckFrame {
+        header,
+        time_correction,
+        payload_ies,
+        payload,
+        mic: reader.mic,
+    }))
+}
+
+/// Reads the header of a multipurpose frame, whose frame control field has
+/// its own layout, and returns the frame with its payload IEs and payload
+/// left empty, along with the termination of the header IEs.
+/// Chapter 7.3.5
+fn read_multipurpose_header<'a>(
+    reader: &mut Reader<'a>,
+) -> Result<(MultipurposeFrame<'a>, HeaderTermination), ParseError> {
+    let control = reader.with_rest(MultipurposeControlField::from_bytes)?;
+    reader.take(control.size())?;
+
+    reader.enter(Section::SequenceNumber);
+    let seq_no: Option<u8> = match control.seq_no_present {
+        true => Some(reader.read()?),
+        false => None,
+    };
+
+    reader.enter(Section::Addressing);
+    let dst_mode = control.dst_addressing_mode;
+    let src_mode = control.src_addressing_mode;
+
+    let (dst_pan_present, src_pan_present) = multipurpose::pan_id_presence(
+        control.pan_id_present,
+        dst_mode != AddressingMode::None,
+        src_mode != AddressingMode::None,
+    );
+
+    let dst_pan: Option<PanId> = match dst_pan_present {
+        true => Some(reader.read_field(wire::read_pan)?),
+        false => None,
+    };
+    let dst_addr = read_dst_address(reader, dst_mode, dst_pan)?;
+
+    let src_pan: PanId = match src_pan_present {
+        true => reader.read_field(wire::read_pan)?,
+        false => PanId::BROADCAST,
+    };
+    let src_addr = read_address(reader, src_mode, src_pan)?;
+
+    // Chapter 7.2.2.4, broadcast frames are not acknowledged
+    if reader.mode == ParseMode::Strict && control.ack_required && dst_addr.is_broadcast() {
+        return Err(ParseError::new(
+            Error::InvalidHeader,
+            0,
+            Section::ControlField,
+        ));
+    }
+
+    let aux = read_security_header(reader, control.security_enabled, &control.version)?;
+    let (header_ies, termination) = read_header_ies(reader, control.ie_present)?;
+
+    let frame = MultipurposeFrame {
+        control,
+        seq_no,
+        dst_addr,
+        src_addr,
+        aux,
+        header_ies,
+        payload_ies: PayloadIeIterator::new(&[]),
+        payload: &[],
+        mic: reader.mic,
+    };
+
+    Ok((frame, termination))
+}
+
+/// Parses a multipurpose frame.
+/// Chapter 7.3.5
+fn parse_multipurpose<'a>(reader: &mut Reader<'a>) -> Result<Frame<'a>, ParseError> {
+    let (frame, termination) = read_multipurpose_header(reader)?;
+    let payload_ies = read_payload_ies(reader, termination)?;
+
+    reader.enter(Section::Payload);
+
+    Ok(Frame::MultiPurpose(MultipurposeFrame {
+        payload_ies,
+        payload: reader.rest(),
+        ..frame
+    }))
+}
+
+/// How the rest of a frame is decoded, as told by its frame kind.
+enum FrameStart {
+    /// Multipurpose frame, whose frame control field is left unread
+    MultiPurpose,
+    /// Frame of a reserved kind, only produced in lenient mode
+    Reserved(StandardControlField),
+    /// Frame whose MAC header starts with this frame control field
+    Standard(StandardControlField),
+}
+
+/// Reads the frame control field, unless the frame is a multipurpose one.
+///
+/// # Error
+///
+/// Returns `InvalidFrameKind` for the frame kinds that cannot be decoded.
+fn read_frame_start(reader: &mut Reader) -> Result<FrameStart, ParseError> {
+    let kind = reader.bytes.first().map(|&first| {
+        FrameKind::from_byte(((first as u16 & mask::FRAME_KIND) >> offset::FRAME_KIND) as u8)
+    });
+
+    if let Some(Ok(FrameKind::MultiPurpose)) = kind {
+        return Ok(FrameStart::MultiPurpose);
+    }
+
+    let control = read_control(reader)?;
+
+    match control.frame_kind {
+        FrameKind::Reserved(_) => Ok(FrameStart::Reserved(control)),
+        kind @ (FrameKind::Frak | FrameKind::Extended) => Err(ParseError::new(
+            Error::InvalidFrameKind(kind.bits()),
+            0,
+            Section::ControlField,
+        )),
+        _ => Ok(FrameStart::Standard(control)),
+    }
+}
+
+/// Decodes a whole frame, and returns it along with the number of bytes
+/// consumed.
+fn read_frame<'a>(reader: &mut Reader<'a>) -> Result<(Frame<'a>, usize), ParseError> {
+    let control = match read_frame_start(reader)? {
+        FrameStart::MultiPurpose => {
+            let frame = parse_multipurpose(reader)?;
+
+            return Ok((frame, reader.consumed()));
+        }
+        FrameStart::Reserved(control) => {
+            return Ok((
+                Frame::Reserved(ReservedFrame {
+                    control,
+                    data: reader.rest(),
+                }),
+                reader.offset,
+            ));
+        }
+        FrameStart::Standard(control) => control,
+    };
+
+    let (kind, version) = (control.frame_kind, control.version);
+    let (header, termination) = read_header_fields(reader, control)?;
+
+    let frame = match kind {
+        FrameKind::Beacon if version == FrameVersion::Ieee802154 => {
+            parse_enh_beacon(reader, header, termination)
+        }
+        FrameKind::Beacon => parse_beacon(reader, header),
+        FrameKind::Data => parse_data(reader, header, termination),
+        FrameKind::Acknowledgment if version != FrameVersion::Ieee802154 => {
+            parse_ack(reader, header, version)
+        }
+        FrameKind::Acknowledgment => parse_enh_ack(reader, header, termination),
+        FrameKind::MacCommand => parse_command(reader, header, termination),
+        kind => Err(ParseError::new(
+            Error::InvalidFrameKind(kind.bits()),
+            0,
+            Section::ControlField,
+        )),
+    }?;
+
+    Ok((frame, reader.consumed()))
+}
+
+impl Ieee802154 {
+    /// Decodes the MAC header only, and returns it along with the offset
+    /// where the MAC payload starts. Payload IEs, if any, are part of the
+    /// MAC payload.
+    pub fn parse_header(slice: &[u8]) -> Result<(MacHeader<'_>, usize), ParseError> {
+        let mut reader = Reader::new(slice, ParseContext::default())?;
+
+        let (header, _) = read_header(&mut reader)?;
+
+        Ok((header, reader.offset))
+    }
+
+    /// Checks that `slice` holds a well formed frame, without decoding its
+    /// payload. The FCS, the frame kind, the MAC header and the IE lists are
+    /// checked the same way [`Parser::parse`] does.
+    ///
+    /// # Error
+    ///
+    /// If the FCS does not match, returns `InvalidFcs`. Otherwise, returns
+    /// the error [`Parser::parse`] would report for the frame kind, the MAC
+    /// header or the IE lists. Errors in the MAC payload past the payload
+    /// IEs, such as an unknown command identifier, are not reported.
+    pub fn validate(slice: &[u8], fcs: FcsPolicy) -> Result<FrameSummary, Error> {
+        let mut reader = Reader::new(fcs.check(slice)?, ParseContext::default())?;
+
+        let control = match read_frame_start(&mut reader)? {
+            FrameStart::MultiPurpose => {
+                let (frame, termination) = read_multipurpose_header(&mut reader)?;
+                let header_len = reader.offset;
+                read_payload_ies(&mut reader, termination)?;
+
+                return Ok(FrameSummary {
+                    frame_kind: FrameKind::MultiPurpose,
+                    seq_no: frame.seq_no,
+                    dst_pan: frame.dst_addr.pan_id(),
+                    dst_addr: frame.dst_addr.address().copied(),
+                    header_len,
+                });
+            }
+            FrameStart::Reserved(control) => {
+                return Ok(FrameSummary {
+                    frame_kind: control.frame_kind,
+                    seq_no: None,
+                    dst_pan: None,
+                    dst_addr: None,
+                    header_len: reader.offset,
+                });
+            }
+            FrameStart::Standard(control) => control,
+        };
+
+        let (header, termination) = read_header_fields(&mut reader, control)?;
+        let header_len = reader.offset;
+        read_payload_ies(&mut reader, termination)?;
+
+        Ok(FrameSummary {
+            frame_kind: header.control.frame_kind(),
+            seq_no: header.seq_no,
+            dst_pan: header.dst_addr.pan_id(),
+            dst_addr: header.dst_addr.address().copied(),
+            header_len,
+        })
+    }
+
+    /// Same as [`Parser::parse`], `mode` telling whether reserved values are
+    /// errors or kept in the parsed frame.
+    pub fn parse_with_mode(slice: &[u8], mode: ParseMode) -> Result<Frame<'_>, ParseError> {
+        Self::parse_with_context(
+            slice,
+            ParseContext {
+                mode,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Same as [`Parser::parse`], with every parsing option set by
+    /// `context`.
+    pub fn parse_with_context(
+        slice: &[u8],
+        context: ParseContext,
+    ) -> Result<Frame<'_>, ParseError> {
+        read_frame(&mut Reader::new(slice, context)?).map(|(frame, _)| frame)
+    }
+}
 
 impl<'a> Parser<'a> for Ieee802154 {
     type Value = Frame<'a>;
 
-    fn parse(_slice: &'a [u8]) -> Result<Self::Value, crate::parser::Error> {
-        todo!()
+    fn parse(slice: &'a [u8]) -> Result<Self::Value, ParseError> {
+        Self::parse_with_len(slice).map(|(frame, _)| frame)
+    }
+
+    fn parse_with_len(slice: &'a [u8]) -> Result<(Self::Value, usize), ParseError> {
+        read_frame(&mut Reader::new(slice, ParseContext::default())?)
+    }
+}
+
+/// Layout of the frames stored in a buffer walked by a [`FrameIter`].
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFraming {
+    /// Each frame is preceded by its length on one byte
+    LengthPrefixed,
+    /// Frames follow each other, each one ending where the parser stopped
+    BackToBack,
+}
+
+/// Iterator over the frames stored in a single buffer.
+///
+/// Error offsets are relative to the start of the buffer. With
+/// [`FrameFraming::LengthPrefixed`], iteration resumes at the next record
+/// after a frame fails to parse, and only stops early when a record runs past
+/// the end of the buffer. With [`FrameFraming::BackToBack`], there is no way
+/// to find the next frame once one fails, so iteration stops at the first
+/// error.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameIter<'a> {
+    data: &'a [u8],
+    offset: usize,
+    framing: FrameFraming,
+    done: bool,
+}
+
+impl<'a> FrameIter<'a> {
+    pub const fn new(data: &'a [u8], framing: FrameFraming) -> Self {
+        FrameIter {
+            data,
+            offset: 0,
+            framing,
+            done: false,
+        }
+    }
+
+    /// Number of bytes walked so far.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for FrameIter<'a> {
+    type Item = Result<Frame<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.data.len() {
+            return None;
+        }
+
+        let record = self.offset;
+
+        let (start, result) = match self.framing {
+            FrameFraming::LengthPrefixed => {
+                let start = record + 1;
+                let end = start + usize::from(self.data[record]);
+
+                let Some(frame) = self.data.get(start..end) else {
+                    self.done = true;
+                    return Some(Err(ParseError::new(
+                        Error::Truncated,
+                        record,
+                        Section::ControlField,
+                    )));
+                };
+
+                self.offset = end;
+                (start, Ieee802154::parse(frame))
+            }
+            FrameFraming::BackToBack => {
+                let result = Ieee802154::parse_with_len(&self.data[record..]);
+
+                match result {
+                    Ok((_, 0)) | Err(_) => self.done = true,
+                    Ok((_, len)) => self.offset += len,
+                }
+
+                (record, result.map(|(frame, _)| frame))
+            }
+        };
+
+        Some(
+            result
+                .map_err(|error| ParseError::new(error.kind, start + error.offset, error.section)),
+        )
+    }
+}
+
+/// Result of [`StreamParser::push`].
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum StreamStatus<'a> {
+    /// At least this many more bytes are needed to complete the frame
+    Incomplete(usize),
+    /// A complete frame, along with the number of bytes it took from the
+    /// stream, length byte included
+    Frame(Frame<'a>, usize),
+}
+
+/// Push parser for frames arriving in pieces, each frame being preceded by
+/// its length on one byte, like the PHR. At most `N` bytes are buffered.
+///
+/// After an error, the first buffered byte is dropped so that the next call
+/// looks for a frame one byte further, until the stream is in sync again.
+#[derive(Debug, Clone, Default)]
+pub struct StreamParser<const N: usize> {
+    buffer: heapless::Vec<u8, N>,
+    /// Bytes to drop from the start of the buffer on the next push
+    consumed: usize,
+}
+
+impl<const N: usize> StreamParser<N> {
+    pub const fn new() -> Self {
+        StreamParser {
+            buffer: heapless::Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    /// Number of bytes buffered, the ones of the last returned frame
+    /// included.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Appends `bytes` to the buffer, and returns the first buffered frame
+    /// if it is complete. Several frames may be buffered at once, in which
+    /// case the next ones are returned by pushing an empty slice.
+    ///
+    /// # Error
+    ///
+    /// If `bytes` does not fit in the buffer, returns `FrameTooLong` and
+    /// drops `bytes`. If the length byte is over [`MAX_PHY_PACKET_SIZE`] or
+    /// the frame fails to parse, returns the error and drops one byte.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<StreamStatus<'_>, Error> {
+        self.discard();
+
+        let buffered = self.buffer.len();
+        self.buffer
+            .extend_from_slice(bytes)
+            .map_err(|_| Error::FrameTooLong {
+                len: buffered + bytes.len(),
+                max: N,
+            })?;
+
+        let Some(&len) = self.buffer.first() else {
+            return Ok(StreamStatus::Incomplete(1));
+        };
+        let end = 1 + usize::from(len);
+
+        if usize::from(len) > MAX_PHY_PACKET_SIZE || end > N {
+            self.consumed = 1;
+            return Err(Error::FrameTooLong {
+                len: usize::from(len),
+                max: MAX_PHY_PACKET_SIZE.min(N.saturating_sub(1)),
+            });
+        }
+
+        if self.buffer.len() < end {
+            return Ok(StreamStatus::Incomplete(end - self.buffer.len()));
+        }
+
+        match Ieee802154::parse(&self.buffer[1..end]) {
+            Ok(frame) => {
+                self.consumed = end;
+                Ok(StreamStatus::Frame(frame, end))
+            }
+            Err(error) => {
+                self.consumed = 1;
+                Err(error.kind)
+            }
+        }
+    }
+
+    /// Drops every buffered byte.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.consumed = 0;
+    }
+
+    fn discard(&mut self) {
+        let len = self.buffer.len() - self.consumed;
+
+        self.buffer.copy_within(self.consumed.., 0);
+        self.buffer.truncate(len);
+        self.consumed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            address::{AddressField, AddressKind, LongAddress, PanId, ShortAddress},
+            control_field::{FrameKind, FrameVersion},
+            fcs::FcsPolicy,
+            frame::Frame,
+            ie::{
+                header::HeaderIe,
+                nested::NestedElement,
+                payload::{PayloadIe, PayloadIeGroup},
+                tsch::{ChannelHoppingIe, TschTimeslot},
+            },
+            security_header::{KeyIdentifierMode, Mic},
+            Ieee802154, PsduLimit,
+        },
+        parser::{Error, ParseError, ParseMode, Parser, Section},
+    };
+
+    use super::{FrameFraming, FrameIter, FrameSummary, ParseContext, StreamParser, StreamStatus};
+
+    #[test]
+    fn parse_beacon_without_gts() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x00, 0x42,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Beacon(beacon) = frame else {
+            panic!("expected a beacon frame");
+        };
+
+        assert_eq!(beacon.header.seq_no, Some(0x2A));
+        assert_eq!(beacon.header.dst_addr, AddressField::Absent);
+        assert_eq!(
+            beacon.header.src_addr,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert_eq!(beacon.payload.super_frame.beacon_order, 0xF);
+        assert_eq!(beacon.payload.gts.descriptors.len(), 0);
+        assert!(beacon.payload.pending_address.short_addresses.is_empty());
+        assert_eq!(beacon.payload.data, &[0x42]);
+    }
+
+    #[test]
+    fn parse_enh_beacon_6tisch_minimal() {
+        let input: [u8; 45] = [
+            0x40, 0xEA, 0x01, 0xCD, 0xAB, 0xFF, 0xFF, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x00, 0x3F, 0x1A, 0x88, 0x06, 0x1A, 0x0E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A,
+            0x1B, 0x01, 0x00, 0x65, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0F, 0x01, 0x1C, 0x00,
+            0x01, 0xC8, 0x00,
+        ];
+
+        let Frame::EnhBeacon(beacon) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected an enhanced beacon frame");
+        };
+
+        assert_eq!(beacon.header.control.frame_kind(), FrameKind::Beacon);
+        assert_eq!(
+            beacon.header.control.version(),
+            Some(FrameVersion::Ieee802154)
+        );
+        assert!(beacon.payload.is_empty());
+
+        let mut ies = beacon.payload_ies.clone();
+        let mlme = ies.next().unwrap().unwrap();
+        assert_eq!(mlme.group, PayloadIeGroup::Mlme);
+        assert!(ies.next().is_none());
+
+        let mut nested = mlme
+            .nested()
+            .unwrap()
+            .map(|ie| ie.unwrap().element().unwrap());
+        let Some(NestedElement::TschSynchronization(sync)) = nested.next() else {
+            panic!("expected a TSCH Synchronization IE");
+        };
+        assert_eq!(sync.asn.value(), 14);
+        let Some(NestedElement::SlotframeAndLink(schedule)) = nested.next() else {
+            panic!("expected a TSCH Slotframe and Link IE");
+        };
+        assert_eq!(schedule.slotframes[0].size, 101);
+        assert_eq!(schedule.slotframes[0].links.len(), 1);
+        assert_eq!(
+            nested.next(),
+            Some(NestedElement::TschTimeslot(TschTimeslot::Id(0)))
+        );
+        assert_eq!(
+            nested.next(),
+            Some(NestedElement::ChannelHopping(ChannelHoppingIe::Id(0)))
+        );
+        assert_eq!(nested.next(), None);
+    }
+
+    #[test]
+    fn parse_mode_invalid_super_frame() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x80, 0x00, 0x42,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::InvalidSuperFrameSpec, 7, Section::SuperFrame)
+        );
+
+        let Frame::Beacon(beacon) =
+            Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a beacon frame");
+        };
+        assert!(beacon.payload.gts.permit);
+        assert_eq!(beacon.payload.data, &[0x42]);
+    }
+
+    #[test]
+    fn parse_beacon_with_pending_address() {
+        let input: [u8; 15] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x02, 0x01, 0x00, 0x02,
+            0x00,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Beacon(beacon) = frame else {
+            panic!("expected a beacon frame");
+        };
+
+        let pending = &beacon.payload.pending_address;
+        assert_eq!(
+            pending.short_addresses,
+            [
+                ShortAddress::new(PanId::new(0x1234), 0x0001),
+                ShortAddress::new(PanId::new(0x1234), 0x0002)
+            ]
+        );
+        assert!(pending.long_addresses.is_empty());
+        assert!(beacon.payload.data.is_empty());
+    }
+
+    #[test]
+    fn parse_enh_beacon_with_header_ies() {
+        let input: [u8; 14] = [
+            0x00, 0xA2, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x0F, 0xAB, 0x80, 0x3F, 0x42, 0x43,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::EnhBeacon(beacon) = frame else {
+            panic!("expected an enhanced beacon frame");
+        };
+
+        let mut ies = beacon.header.header_ies.clone();
+        assert_eq!(
+            ies.next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1E,
+                content: &[0xAB],
+            }))
+        );
+        assert_eq!(ies.next(), None);
+        assert!(beacon.payload_ies.as_bytes().is_empty());
+        assert_eq!(beacon.payload, &[0x42, 0x43]);
+    }
+
+    #[test]
+    fn parse_reports_header_ie_past_end_of_input() {
+        let input: [u8; 10] = [0x00, 0xA2, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x05, 0x0F, 0x04];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(
+                Error::InvalidIe { id: 0x1E },
+                7,
+                Section::InformationElements
+            )
+        );
+    }
+
+    #[test]
+    fn parse_data_frame() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.header.seq_no, Some(0x01));
+        assert_eq!(
+            data.header.dst_addr,
+            AddressField::Present(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xFFFF
+            )))
+        );
+        assert_eq!(
+            data.header.src_addr,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert!(data.header.aux.is_none());
+        assert_eq!(data.payload_ies.clone().next(), None);
+        assert_eq!(data.payload, &[0xDE, 0xAD, 0xBE]);
+    }
+
+    #[test]
+    fn parse_data_frame_with_payload_ies() {
+        let input: [u8; 16] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x00, 0x3F, 0x01, 0x88, 0xAA,
+            0x00, 0xF8,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.header.header_ies.clone().next(), None);
+
+        let mut ies = data.payload_ies.clone();
+        assert_eq!(
+            ies.next(),
+            Some(Ok(PayloadIe {
+                group: PayloadIeGroup::Mlme,
+                content: &[0xAA],
+            }))
+        );
+        assert_eq!(ies.next(), None);
+        assert_eq!(data.payload, &[]);
+    }
+
+    #[test]
+    fn parse_data_frame_with_unformatted_payload_after_header_termination_2() {
+        let input: [u8; 13] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x80, 0x3F, 0x01, 0x88,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.payload_ies.clone().next(), None);
+        assert_eq!(data.payload, &[0x01, 0x88]);
+    }
+
+    #[test]
+    fn parse_data_frame_with_header_ies_and_no_termination() {
+        let input: [u8; 12] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x01, 0x0F, 0xAA,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.header.header_ies.clone().count(), 1);
+        assert_eq!(data.payload_ies.clone().next(), None);
+        assert_eq!(data.payload, &[]);
+    }
+
+    #[test]
+    fn parse_data_frame_with_payload_ies_running_to_the_end() {
+        let input: [u8; 15] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x00, 0x3F, 0x01, 0x80, 0xAA,
+            0xBB,
+        ];
+
+        assert!(Ieee802154::parse(&input).is_err());
+
+        let frame = Ieee802154::parse(&input[..14]).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.payload_ies.clone().count(), 1);
+        assert_eq!(data.payload, &[]);
+    }
+
+    #[test]
+    fn parse_data_frame_with_payload_ies_and_payload() {
+        let input: [u8; 18] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x00, 0x3F, 0x01, 0x80, 0xAA,
+            0x00, 0xF8, 0xBB, 0xCC,
+        ];
+
+        let frame = Ieee802154::parse(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        let mut ies = data.payload_ies.clone();
+        assert_eq!(
+            ies.next(),
+            Some(Ok(PayloadIe {
+                group: PayloadIeGroup::Esdu,
+                content: &[0xAA],
+            }))
+        );
+        assert_eq!(ies.next(), None);
+        assert_eq!(data.payload, &[0xBB, 0xCC]);
+    }
+
+    #[test]
+    fn parse_reports_payload_ie_past_end_of_input() {
+        let input: [u8; 14] = [
+            0x41, 0xAA, 0x02, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x00, 0x3F, 0x02, 0x88, 0xAA,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(
+                Error::InvalidIe { id: 0x1 },
+                11,
+                Section::InformationElements
+            )
+        );
+    }
+
+    #[test]
+    fn parse_reports_truncated_control_field() {
+        assert_eq!(
+            Ieee802154::parse(&[0x00]).unwrap_err(),
+            ParseError::new(Error::Truncated, 0, Section::ControlField)
+        );
+    }
+
+    #[test]
+    fn parse_reports_invalid_frame_kind() {
+        assert_eq!(
+            Ieee802154::parse(&[0x04, 0x00, 0x00]).unwrap_err(),
+            ParseError::new(Error::InvalidFrameKind(0x4), 0, Section::ControlField)
+        );
+    }
+
+    #[test]
+    fn parse_reports_missing_sequence_number() {
+        assert_eq!(
+            Ieee802154::parse(&[0x00, 0x80]).unwrap_err(),
+            ParseError::new(Error::Truncated, 2, Section::SequenceNumber)
+        );
+    }
+
+    #[test]
+    fn parse_reports_truncated_address() {
+        let input: [u8; 6] = [0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::Truncated, 5, Section::Addressing)
+        );
+    }
+
+    #[test]
+    fn parse_reports_reserved_addressing_mode() {
+        assert_eq!(
+            Ieee802154::parse(&[0x00, 0x40, 0x2A]).unwrap_err(),
+            ParseError::new(Error::InvalidAddressingMode(0x1), 3, Section::Addressing)
+        );
+    }
+
+    #[test]
+    fn parse_reports_truncated_security_header() {
+        let input: [u8; 8] = [0x08, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x05];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::TruncatedSecurityHeader, 7, Section::SecurityHeader)
+        );
+    }
+
+    #[test]
+    fn parse_reports_truncated_super_frame() {
+        let input: [u8; 8] = [0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::Truncated, 7, Section::SuperFrame)
+        );
+    }
+
+    #[test]
+    fn parse_reports_missing_gts() {
+        let input: [u8; 9] = [0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::Truncated, 9, Section::Gts)
+        );
+    }
+
+    #[test]
+    fn parse_reports_truncated_pending_address() {
+        let input: [u8; 12] = [
+            0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x00, 0x01, 0x01,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::Truncated, 10, Section::PendingAddress)
+        );
+    }
+
+    #[test]
+    fn parse_header_returns_payload_offset() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+
+        let (header, offset) = Ieee802154::parse_header(&input).unwrap();
+
+        assert_eq!(header.seq_no, Some(0x01));
+        assert!(header.aux.is_none());
+        assert_eq!(offset, 9);
+    }
+
+    #[test]
+    fn parse_header_with_security_header() {
+        let input: [u8; 21] = [
+            0x49, 0x98, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ];
+
+        let (header, offset) = Ieee802154::parse_header(&input).unwrap();
+
+        let aux = header.aux.as_ref().unwrap();
+        assert_eq!(aux.security_control_bits(), 0x0D);
+        assert_eq!(aux.serialized_len(), 6);
+        assert_eq!(offset, 15);
+    }
+
+    #[test]
+    fn parse_secured_payload_and_mic() {
+        // Security level 5, followed by the encrypted payload and its 32-bit
+        // MIC
+        let input: [u8; 21] = [
+            0x49, 0x98, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ];
+
+        let (frame, len) = Ieee802154::parse_with_len(&input).unwrap();
+        let Frame::Data(data) = frame else {
+            panic!("expected a data frame");
+        };
+
+        assert_eq!(data.payload, &[0xDE, 0xAD]);
+        assert_eq!(data.mic, &[0xAA, 0xBB, 0xCC, 0xDD]);
+        assert_eq!(len, input.len());
+
+        // MIC without payload
+        let Frame::Data(data) = Ieee802154::parse(&input[..19]).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert!(data.payload.is_empty());
+        assert_eq!(data.mic, &[0xDE, 0xAD, 0xAA, 0xBB]);
+
+        // Security level 0 with an implicit key, without MIC
+        let mut input = input;
+        input[9] = 0x00;
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload, &[0x07, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD]);
+        assert!(data.mic.is_empty());
+    }
+
+    #[test]
+    fn parse_encrypted_payload_ies() {
+        // Security level 5, HT1, encrypted payload IEs and payload, MIC-32
+        let mut input: [u8; 27] = [
+            0x49, 0xAA, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x05, 0x78, 0x56, 0x34, 0x12,
+            0x00, 0x3F, 0x01, 0x80, 0xAA, 0x00, 0xF8, 0xDE, 0xAD, 0xAA, 0xBB, 0xCC, 0xDD,
+        ];
+
+        // Left in the payload until unsecured
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload_ies.as_bytes(), &[]);
+        assert_eq!(data.payload, &input[16..23]);
+
+        let context = ParseContext {
+            decrypted: true,
+            ..Default::default()
+        };
+        let Frame::Data(data) = Ieee802154::parse_with_context(&input, context).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload_ies.as_bytes(), &input[16..21]);
+        assert_eq!(data.payload, &[0xDE, 0xAD]);
+
+        // Without encryption, the payload IEs are in the clear
+        input[9] = 0x01;
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload_ies.as_bytes(), &input[16..21]);
+
+        // MAC command, whose identifier is encrypted along with the IEs
+        input[0] = 0x4B;
+        input[9] = 0x05;
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::EncryptedCommand, 16, Section::Payload)
+        );
+        input[21] = 0x24;
+        assert!(matches!(
+            Ieee802154::parse_with_context(&input, context),
+            Ok(Frame::MacCommand(_))
+        ));
+    }
+
+    #[test]
+    fn parse_truncated_mic() {
+        let input: [u8; 18] = [
+            0x49, 0x98, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x0D, 0x78, 0x56, 0x34, 0x12,
+            0x07, 0xAA, 0xBB, 0xCC,
+        ];
+        let error = ParseError::new(Error::Truncated, 18, Section::Payload);
+
+        assert_eq!(Ieee802154::parse(&input).unwrap_err(), error);
+        assert_eq!(Ieee802154::parse_header(&input).unwrap_err(), error);
+        assert_eq!(
+            Ieee802154::validate(&input, FcsPolicy::Absent).unwrap_err(),
+            Error::Truncated
+        );
+    }
+
+    #[test]
+    fn parse_header_stops_after_header_ies() {
+        let input: [u8; 16] = [
+            0x41, 0xAA, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x01, 0x0F, 0xAB, 0x00, 0x3F,
+            0x00, 0xF8,
+        ];
+
+        let (header, offset) = Ieee802154::parse_header(&input).unwrap();
+
+        assert_eq!(
+            header.header_ies.clone().next(),
+            Some(Ok(HeaderIe {
+                element_id: 0x1E,
+                content: &[0xAB],
+            }))
+        );
+        assert_eq!(offset, 14);
+    }
+
+    #[test]
+    fn parse_header_reports_truncated_header() {
+        assert_eq!(
+            Ieee802154::parse_header(&[0x41, 0x88, 0x01, 0x34]).unwrap_err(),
+            ParseError::new(Error::Truncated, 3, Section::Addressing)
+        );
+    }
+
+    #[test]
+    fn parse_with_len_consumes_whole_frame() {
+        let input: [u8; 12] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+        ];
+
+        let (frame, len) = Ieee802154::parse_with_len(&input).unwrap();
+
+        assert!(matches!(frame, Frame::Data(_)));
+        assert_eq!(len, input.len());
+        assert_eq!(
+            crate::parse_with_len::<Ieee802154>(&input).unwrap().1,
+            input.len()
+        );
+    }
+
+    #[test]
+    fn validate_data_frame_with_fcs() {
+        let input: [u8; 14] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE, 0x3E, 0x4D,
+        ];
+
+        let summary = Ieee802154::validate(&input, FcsPolicy::Crc16).unwrap();
+
+        assert_eq!(summary.frame_kind, FrameKind::Data);
+        assert_eq!(summary.seq_no, Some(0x01));
+        assert_eq!(summary.dst_pan, Some(PanId::new(0x1234)));
+        assert_eq!(
+            summary.dst_addr,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xFFFF
+            )))
+        );
+        assert_eq!(summary.header_len, 9);
+
+        assert_eq!(
+            Ieee802154::validate(&input[..12], FcsPolicy::Absent),
+            Ok(summary)
+        );
+        assert_eq!(
+            Ieee802154::validate(&input[..13], FcsPolicy::Crc16),
+            Err(Error::InvalidFcs)
+        );
+    }
+
+    #[test]
+    fn validate_reports_header_errors() {
+        let input: [u8; 10] = [0x00, 0xA2, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x05, 0x0F, 0x04];
+
+        assert_eq!(
+            Ieee802154::validate(&input, FcsPolicy::Absent),
+            Err(Error::InvalidIe { id: 0x1E })
+        );
+        assert_eq!(
+            Ieee802154::validate(&[0x41], FcsPolicy::Absent),
+            Err(Error::Truncated)
+        );
+    }
+
+    #[test]
+    fn validate_multipurpose_frames() {
+        let short: [u8; 6] = [0x25, 0x2A, 0xFF, 0xFF, 0x01, 0x02];
+
+        assert_eq!(
+            Ieee802154::validate(&short, FcsPolicy::Absent),
+            Ok(FrameSummary {
+                frame_kind: FrameKind::MultiPurpose,
+                seq_no: Some(0x2A),
+                dst_pan: Some(PanId::broadcast()),
+                dst_addr: Some(AddressKind::Short(ShortAddress::broadcast())),
+                header_len: 4,
+            })
+        );
+
+        let long: [u8; 23] = [
+            0xED, 0xC1, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+            0x01, 0x81, 0x0E, 0xAB, 0x80, 0x3F, 0x42, 0x43, 0x44,
+        ];
+
+        assert_eq!(
+            Ieee802154::validate(&long, FcsPolicy::Absent),
+            Ok(FrameSummary {
+                frame_kind: FrameKind::MultiPurpose,
+                seq_no: Some(0x2A),
+                dst_pan: Some(PanId::new(0x1234)),
+                dst_addr: Some(AddressKind::Short(ShortAddress::new(
+                    PanId::new(0x1234),
+                    0xABCD
+                ))),
+                header_len: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_reports_invalid_frame_kind() {
+        for kind in [0x06, 0x07] {
+            let input: [u8; 5] = [kind, 0x08, 0x2A, 0x01, 0x02];
+
+            assert_eq!(
+                Ieee802154::parse(&input).unwrap_err().kind,
+                Error::InvalidFrameKind(kind)
+            );
+            assert_eq!(
+                Ieee802154::validate(&input, FcsPolicy::Absent),
+                Err(Error::InvalidFrameKind(kind))
+            );
+        }
+    }
+
+    const STREAM_FRAME: [u8; 13] = [
+        0x0C, 0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0xBE,
+    ];
+
+    #[test]
+    fn stream_parser_frame_in_pieces() {
+        let mut parser = StreamParser::<32>::new();
+
+        assert!(matches!(parser.push(&[]), Ok(StreamStatus::Incomplete(1))));
+        assert!(matches!(
+            parser.push(&STREAM_FRAME[..5]),
+            Ok(StreamStatus::Incomplete(8))
+        ));
+        assert!(matches!(
+            parser.push(&STREAM_FRAME[5..12]),
+            Ok(StreamStatus::Incomplete(1))
+        ));
+
+        let Ok(StreamStatus::Frame(Frame::Data(data), 13)) = parser.push(&STREAM_FRAME[12..])
+        else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload, &[0xDE, 0xAD, 0xBE]);
+
+        assert!(matches!(parser.push(&[]), Ok(StreamStatus::Incomplete(1))));
+        assert!(parser.is_empty());
+    }
+
+    #[test]
+    fn stream_parser_several_frames_in_one_push() {
+        let mut parser = StreamParser::<32>::new();
+        let mut input = [0u8; 26];
+        input[..13].copy_from_slice(&STREAM_FRAME);
+        input[13..].copy_from_slice(&STREAM_FRAME);
+
+        assert!(matches!(
+            parser.push(&input),
+            Ok(StreamStatus::Frame(Frame::Data(_), 13))
+        ));
+        assert!(matches!(
+            parser.push(&[]),
+            Ok(StreamStatus::Frame(Frame::Data(_), 13))
+        ));
+        assert!(matches!(parser.push(&[]), Ok(StreamStatus::Incomplete(1))));
+    }
+
+    #[test]
+    fn stream_parser_resynchronizes() {
+        let mut parser = StreamParser::<32>::new();
+        let mut input = [0u8; 15];
+        input[0] = 0xFF;
+        input[1] = 0x00;
+        input[2..].copy_from_slice(&STREAM_FRAME);
+
+        assert_eq!(
+            parser.push(&input).unwrap_err(),
+            Error::FrameTooLong { len: 0xFF, max: 31 }
+        );
+        assert_eq!(parser.push(&[]).unwrap_err(), Error::Truncated);
+        assert!(matches!(
+            parser.push(&[]),
+            Ok(StreamStatus::Frame(Frame::Data(_), 13))
+        ));
+    }
+
+    #[test]
+    fn stream_parser_full_buffer() {
+        let mut parser = StreamParser::<16>::new();
+
+        assert!(matches!(
+            parser.push(&STREAM_FRAME[..10]),
+            Ok(StreamStatus::Incomplete(3))
+        ));
+        assert_eq!(
+            parser.push(&[0u8; 7]).unwrap_err(),
+            Error::FrameTooLong { len: 17, max: 16 }
+        );
+        assert_eq!(parser.len(), 10);
+
+        parser.clear();
+        assert!(parser.is_empty());
+    }
+
+    #[test]
+    fn frame_iter_resumes_after_corrupt_frame() {
+        // Data frame, data frame truncated in the addressing fields, data frame
+        let input: [u8; 29] = [
+            0x0B, 0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD, 0x04, 0x41,
+            0x88, 0x02, 0x34, 0x0B, 0x41, 0x88, 0x03, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xBE,
+            0xEF,
+        ];
+        let mut frames = FrameIter::new(&input, FrameFraming::LengthPrefixed);
+
+        let Some(Ok(Frame::Data(data))) = frames.next() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.seq_no, Some(0x01));
+        assert_eq!(data.payload, &[0xDE, 0xAD]);
+
+        assert_eq!(
+            frames.next().unwrap().unwrap_err(),
+            ParseError::new(Error::Truncated, 16, Section::Addressing)
+        );
+
+        let Some(Ok(Frame::Data(data))) = frames.next() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.seq_no, Some(0x03));
+        assert_eq!(data.payload, &[0xBE, 0xEF]);
+
+        assert!(frames.next().is_none());
+        assert_eq!(frames.offset(), input.len());
+    }
+
+    #[test]
+    fn frame_iter_stops_at_truncated_record() {
+        let input: [u8; 4] = [0x01, 0x41, 0x0B, 0x41];
+        let mut frames = FrameIter::new(&input, FrameFraming::LengthPrefixed);
+
+        assert_eq!(
+            frames.next().unwrap().unwrap_err(),
+            ParseError::new(Error::Truncated, 1, Section::ControlField)
+        );
+        assert_eq!(
+            frames.next().unwrap().unwrap_err(),
+            ParseError::new(Error::Truncated, 2, Section::ControlField)
+        );
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn frame_iter_back_to_back() {
+        let input: [u8; 11] = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD,
+        ];
+        let mut frames = FrameIter::new(&input, FrameFraming::BackToBack);
+
+        assert!(matches!(frames.next(), Some(Ok(Frame::Data(_)))));
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn parse_mode_reserved_frame_kind() {
+        let input: [u8; 4] = [0x04, 0x88, 0x01, 0x02];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidFrameKind(0x4), 0, Section::ControlField)
+        );
+
+        let Frame::Reserved(frame) =
+            Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a reserved frame");
+        };
+        assert_eq!(frame.control.frame_kind, FrameKind::Reserved(0x4));
+        assert_eq!(frame.data, &[0x01, 0x02]);
+    }
+
+    #[test]
+    fn pan_id_compression_table() {
+        let pan = PanId::new(0x1234);
+        let dst_short = AddressKind::Short(ShortAddress::new(pan, 0xABCD));
+        let dst_long = AddressKind::Long(LongAddress::new(pan, 0x0102_0304_0506_0708));
+        const LONG: [u8; 8] = [0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01];
+
+        // Rows of Table 7-2 for IEEE 802.15.4-2015 data frames: input,
+        // destination, source, and whether composing gives the input back
+        let rows: [(&[u8], AddressField, Option<AddressKind>, bool); 10] = [
+            (&[0x01, 0x20, 0x2A], AddressField::Absent, None, true),
+            (
+                &[0x41, 0x20, 0x2A, 0x34, 0x12],
+                AddressField::PanOnly(pan),
+                None,
+                true,
+            ),
+            (
+                &[0x01, 0x28, 0x2A, 0x34, 0x12, 0xCD, 0xAB],
+                dst_short.into(),
+                None,
+                true,
+            ),
+            // Elided PAN IDs are read as the broadcast one, which is then
+            // written explicitly
+            (
+                &[0x41, 0x28, 0x2A, 0xCD, 0xAB],
+                ShortAddress::new(PanId::BROADCAST, 0xABCD).into(),
+                None,
+                false,
+            ),
+            (
+                &[0x01, 0xA0, 0x2A, 0x34, 0x12, 0xCD, 0xAB],
+                AddressField::Absent,
+                Some(dst_short),
+                true,
+            ),
+            (
+                &[0x41, 0xA0, 0x2A, 0xCD, 0xAB],
+                AddressField::Absent,
+                Some(ShortAddress::new(PanId::BROADCAST, 0xABCD).into()),
+                false,
+            ),
+            (
+                &[
+                    0x01, 0xEC, 0x2A, 0x34, 0x12, LONG[0], LONG[1], LONG[2], LONG[3], LONG[4],
+                    LONG[5], LONG[6], LONG[7], LONG[0], LONG[1], LONG[2], LONG[3], LONG[4],
+                    LONG[5], LONG[6], LONG[7],
+                ],
+                dst_long.into(),
+                Some(dst_long),
+                true,
+            ),
+            (
+                &[
+                    0x41, 0xEC, 0x2A, LONG[0], LONG[1], LONG[2], LONG[3], LONG[4], LONG[5],
+                    LONG[6], LONG[7], LONG[0], LONG[1], LONG[2], LONG[3], LONG[4], LONG[5],
+                    LONG[6], LONG[7],
+                ],
+                LongAddress::new(PanId::BROADCAST, 0x0102_0304_0506_0708).into(),
+                Some(LongAddress::new(PanId::BROADCAST, 0x0102_0304_0506_0708).into()),
+                false,
+            ),
+            (
+                &[
+                    0x01, 0xA8, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x21, 0x43, 0x01, 0x00,
+                ],
+                dst_short.into(),
+                Some(ShortAddress::new(PanId::new(0x4321), 0x0001).into()),
+                true,
+            ),
+            (
+                &[0x41, 0xA8, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00],
+                dst_short.into(),
+                Some(ShortAddress::new(pan, 0x0001).into()),
+                true,
+            ),
+        ];
+
+        for (input, dst, src, round_trip) in rows {
+            let frame = Ieee802154::parse(input).unwrap();
+            let Frame::Data(data) = &frame else {
+                panic!("expected a data frame");
+            };
+
+            assert_eq!(data.header.dst_addr, dst, "{:02X?}", input);
+            assert_eq!(data.header.src_addr, src, "{:02X?}", input);
+            if round_trip {
+                assert_eq!(Ieee802154::compose(&frame).unwrap(), input);
+            }
+
+            let summary = Ieee802154::validate(input, FcsPolicy::Absent).unwrap();
+            assert_eq!(summary.dst_pan, dst.pan_id());
+            assert_eq!(summary.dst_addr.as_ref(), dst.address());
+        }
+    }
+
+    #[test]
+    fn parse_mode_empty_ie_area() {
+        // IEs announced, but the frame ends with the header
+        let input: [u8; 9] = [0x41, 0xAA, 0x2A, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidHeader, 0, Section::ControlField)
+        );
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Lenient).is_ok());
+
+        // Header Termination 2 IE before the payload
+        let input: [u8; 12] = [
+            0x41, 0xAA, 0x2A, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x80, 0x3F, 0x42,
+        ];
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Strict).is_ok());
+    }
+
+    #[test]
+    fn parse_mode_ack_and_pending_semantics() {
+        // Acknowledgment requested for a broadcast frame
+        let input: [u8; 10] = [0x61, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x42];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidHeader, 0, Section::ControlField)
+        );
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Lenient).is_ok());
+
+        let input: [u8; 10] = [0x61, 0x88, 0x01, 0x34, 0x12, 0x78, 0x56, 0xCD, 0xAB, 0x42];
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Strict).is_ok());
+
+        // Acknowledgment requested for an acknowledgment
+        let input: [u8; 3] = [0x22, 0x00, 0x2A];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidHeader, 0, Section::ControlField)
+        );
+
+        let input: [u8; 3] = [0x12, 0x00, 0x2A];
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Strict).is_ok());
+
+        // Pending data announced by a MAC command
+        let input: [u8; 10] = [0x73, 0x88, 0x01, 0x34, 0x12, 0x78, 0x56, 0xCD, 0xAB, 0x04];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidHeader, 0, Section::ControlField)
+        );
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Lenient).is_ok());
+
+        let input: [u8; 10] = [0x63, 0x88, 0x01, 0x34, 0x12, 0x78, 0x56, 0xCD, 0xAB, 0x04];
+        assert!(Ieee802154::parse_with_mode(&input, ParseMode::Strict).is_ok());
+    }
+
+    #[test]
+    fn parse_mode_reserved_frame_version() {
+        let input: [u8; 10] = [0x41, 0xB8, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x42];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidFrameVersion(0x3), 0, Section::ControlField)
+        );
+
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(
+            data.header.control.version(),
+            Some(FrameVersion::Reserved(0x3))
+        );
+        assert_eq!(
+            data.header.src_addr,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert_eq!(data.payload, &[0x42]);
+    }
+
+    #[test]
+    fn parse_mode_reserved_addressing_mode() {
+        let input: [u8; 8] = [0x01, 0x84, 0x01, 0x34, 0x12, 0xCD, 0xAB, 0x42];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidAddressingMode(0x1), 3, Section::Addressing)
+        );
+
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.dst_addr, AddressField::Absent);
+        assert_eq!(
+            data.header.src_addr,
+            Some(AddressKind::Short(ShortAddress::new(
+                PanId::new(0x1234),
+                0xABCD
+            )))
+        );
+        assert_eq!(data.payload, &[0x42]);
+    }
+
+    #[test]
+    fn parse_mode_reserved_security_level() {
+        let input: [u8; 15] = [
+            0x49, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x04, 0x01, 0x00, 0x00, 0x00,
+            0x42,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidSecurityLevel(0x4), 9, Section::SecurityHeader)
+        );
+
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        let level = data.header.aux.unwrap().security_level.unwrap();
+        assert_eq!(level.mic, Mic::None);
+        assert!(level.encrypted);
+        assert_eq!(data.payload, &[0x42]);
+    }
+
+    #[test]
+    fn parse_mode_security_control_checks() {
+        let mut input: [u8; 15] = [
+            0x49, 0x98, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x04, 0x01, 0x00, 0x00, 0x00,
+            0x42,
+        ];
+
+        // Security level 4 in an IEEE 802.15.4-2006 frame
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.aux.unwrap().security_control_bits(), 0x04);
+
+        // Reserved bits 6 and 7
+        input[9] = 0xC4;
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(
+                Error::ReservedSecurityControl(0xC0),
+                9,
+                Section::SecurityHeader
+            )
+        );
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.aux.unwrap().security_control_bits(), 0x04);
+        assert_eq!(data.payload, &[0x42]);
+
+        // Security level 4, reserved since IEEE 802.15.4-2015
+        input[1] = 0xA8;
+        input[9] = 0x04;
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidSecurityLevel(0x4), 9, Section::SecurityHeader)
+        );
+    }
+
+    #[test]
+    fn parse_mode_key_identifier_mode_without_security_level() {
+        let input: [u8; 16] = [
+            0x49, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x08, 0x01, 0x00, 0x00, 0x00,
+            0x07, 0x42,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::InvalidKeyIdMode(0x1), 9, Section::SecurityHeader)
+        );
+
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        let aux = data.header.aux.unwrap();
+        assert_eq!(aux.security_level, None);
+        assert!(matches!(
+            aux.key_identifier_mode,
+            KeyIdentifierMode::KeyIndex(_)
+        ));
+        assert_eq!(data.payload, &[0x42]);
+    }
+
+    #[test]
+    fn parse_mode_frame_counter_suppression() {
+        // IEEE 802.15.4-2006 frame, security level 5 with the Frame Counter
+        // Suppression bit set
+        let mut input: [u8; 15] = [
+            0x49, 0x98, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x25, 0x42, 0xAA, 0xBB, 0xCC,
+            0xDD,
+        ];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(
+                Error::InvalidFrameCounterSuppression,
+                9,
+                Section::SecurityHeader
+            )
+        );
+
+        let Frame::Data(data) = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected a data frame");
+        };
+        let aux = data.header.aux.unwrap();
+        assert!(aux.frame_counter_suppressed());
+        assert_eq!(aux.security_control_bits(), 0x25);
+        assert_eq!(data.payload, &[0x42]);
+        assert_eq!(data.mic, &[0xAA, 0xBB, 0xCC, 0xDD]);
+
+        // Allowed from IEEE 802.15.4-2015 on
+        input[1] = 0xA8;
+        let Frame::Data(data) = Ieee802154::parse(&input).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.header.aux.unwrap().frame_counter, None);
+    }
+
+    #[test]
+    fn parse_truncated_inputs_does_not_panic() {
+        let inputs: [&[u8]; 3] = [
+            &[
+                0x00, 0x80, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0xFF, 0xCF, 0x87, 0xF0, 0xAB, 0xCD, 0xFA,
+                0x12, 0x01, 0x00, 0x02, 0x00, 0x42,
+            ],
+            &[
+                0x41, 0xAA, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x01, 0x0F, 0xAB, 0x00, 0x3F,
+                0x02, 0x88, 0x01, 0x1C, 0x00, 0xF8, 0x42,
+            ],
+            &[
+                0x49, 0xA8, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x1F, 0x01, 0x00, 0x00, 0x00,
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x07, 0x42,
+            ],
+        ];
+
+        for input in inputs {
+            for len in 0..=input.len() {
+                let _ = Ieee802154::parse_with_mode(&input[..len], ParseMode::Strict);
+                let _ = Ieee802154::parse_with_mode(&input[..len], ParseMode::Lenient);
+                let _ = Ieee802154::parse_header(&input[..len]);
+            }
+        }
+    }
+
+    /// Data frame padded with payload bytes to `N` bytes.
+    fn data_frame_of_len<const N: usize>() -> [u8; N] {
+        let mut input = [0u8; N];
+        input[..9].copy_from_slice(&[0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB]);
+        input
+    }
+
+    #[test]
+    fn parse_standard_psdu_limit() {
+        assert!(Ieee802154::parse(&data_frame_of_len::<127>()).is_ok());
+        assert_eq!(
+            Ieee802154::parse(&data_frame_of_len::<128>()).unwrap_err(),
+            ParseError::new(
+                Error::FrameTooLong { len: 128, max: 127 },
+                0,
+                Section::ControlField
+            )
+        );
+    }
+
+    #[test]
+    fn parse_sun_psdu_limit() {
+        let context = ParseContext {
+            limit: PsduLimit::Sun,
+            ..Default::default()
+        };
+
+        assert!(Ieee802154::parse_with_context(&data_frame_of_len::<128>(), context).is_ok());
+        assert!(Ieee802154::parse_with_context(&data_frame_of_len::<2047>(), context).is_ok());
+        assert_eq!(
+            Ieee802154::parse_with_context(&data_frame_of_len::<2048>(), context).unwrap_err(),
+            ParseError::new(
+                Error::FrameTooLong {
+                    len: 2048,
+                    max: 2047
+                },
+                0,
+                Section::ControlField
+            )
+        );
+    }
+
+    #[test]
+    fn parse_imm_ack() {
+        let frame = Ieee802154::parse(&[0x12, 0x00, 0x2A]).unwrap();
+        let Frame::Acknowledgment(ack) = frame else {
+            panic!("expected an acknowledgment frame");
+        };
+
+        assert_eq!(ack.version, FrameVersion::Ieee802154_2003);
+        assert!(ack.frame_pending);
+        assert_eq!(ack.seq_no, 0x2A);
+    }
+
+    #[test]
+    fn parse_imm_ack_trailing_bytes() {
+        let input: [u8; 5] = [0x02, 0x10, 0x2A, 0xAB, 0xCD];
+
+        assert_eq!(
+            Ieee802154::parse_with_mode(&input, ParseMode::Strict).unwrap_err(),
+            ParseError::new(Error::TrailingBytes { count: 2 }, 3, Section::Payload)
+        );
+
+        let Frame::Acknowledgment(ack) =
+            Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap()
+        else {
+            panic!("expected an acknowledgment frame");
+        };
+        assert_eq!(ack.version, FrameVersion::Ieee802154_2006);
+        assert_eq!(ack.seq_no, 0x2A);
+    }
+
+    #[test]
+    fn parse_imm_ack_with_addressing() {
+        let input: [u8; 7] = [0x02, 0x08, 0x2A, 0x34, 0x12, 0xCD, 0xAB];
+
+        assert_eq!(
+            Ieee802154::parse(&input).unwrap_err(),
+            ParseError::new(Error::InvalidHeader, 0, Section::ControlField)
+        );
     }
 }
@@ -0,0 +1,126 @@
+use super::{fcs::FCS_16_SIZE, MAX_PHY_PACKET_SIZE};
+
+/// Size in bytes of the PHY header.
+/// Chapter 12.1
+pub const PHR_SIZE: usize = 1;
+
+/// Frame Length field of the PHY header, bit 7 is reserved.
+/// Chapter 12.1.3
+const FRAME_LENGTH_MASK: u8 = 0x7F;
+
+/// Writes the PPDU carrying `psdu` at the start of `out`, the PHY header
+/// followed by the PSDU, and returns the number of bytes written.
+///
+/// `psdu` is the frame as composed, without FCS. If the radio appends the
+/// FCS itself but expects it to be counted in the PHY header, set
+/// `fcs_included` so that the Frame Length accounts for the 16-bit FCS.
+/// Chapter 12.1
+///
+/// # Errors
+///
+/// If the Frame Length is over [`MAX_PHY_PACKET_SIZE`], returns
+/// `FrameTooLong`. If `out` cannot hold the PPDU, returns `BufferTooSmall`.
+pub fn wrap_ppdu(
+    psdu: &[u8],
+    fcs_included: bool,
+    out: &mut [u8],
+) -> Result<usize, crate::composer::Error> {
+    let len = match fcs_included {
+        true => psdu.len() + FCS_16_SIZE,
+        false => psdu.len(),
+    };
+
+    if len > MAX_PHY_PACKET_SIZE {
+        return Err(crate::composer::Error::FrameTooLong {
+            len,
+            max: MAX_PHY_PACKET_SIZE,
+        });
+    }
+
+    let needed = PHR_SIZE + psdu.len();
+
+    if out.len() < needed {
+        return Err(crate::composer::Error::BufferTooSmall { needed });
+    }
+
+    out[0] = len as u8;
+    out[PHR_SIZE..needed].copy_from_slice(psdu);
+
+    Ok(needed)
+}
+
+/// Returns the PSDU of the PPDU at the start of `ppdu`, as announced by its
+/// PHY header. Bytes after the PSDU, like the LQI some radios append, are
+/// ignored.
+///
+/// The PSDU includes the FCS when the radio counts it in the PHY header, it
+/// can then be checked with [`FcsPolicy::check`](super::fcs::FcsPolicy::check).
+/// Chapter 12.1
+///
+/// # Errors
+///
+/// If `ppdu` is empty or shorter than the PSDU, returns `Truncated`. If the
+/// reserved bit of the PHY header is set, returns `FrameTooLong`.
+pub fn unwrap_ppdu(ppdu: &[u8]) -> Result<&[u8], crate::parser::Error> {
+    let (&phr, rest) = ppdu.split_first().ok_or(crate::parser::Error::Truncated)?;
+
+    if phr & !FRAME_LENGTH_MASK != 0 {
+        return Err(crate::parser::Error::FrameTooLong {
+            len: usize::from(phr),
+            max: MAX_PHY_PACKET_SIZE,
+        });
+    }
+
+    rest.get(..usize::from(phr))
+        .ok_or(crate::parser::Error::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unwrap_ppdu, wrap_ppdu};
+
+    const PSDU: [u8; 5] = [0x41, 0x88, 0x01, 0xCD, 0xAB];
+
+    #[test]
+    fn wrap_round_trip() {
+        let mut out = [0u8; 8];
+
+        assert_eq!(wrap_ppdu(&PSDU, false, &mut out), Ok(6));
+        assert_eq!(out[..6], [0x05, 0x41, 0x88, 0x01, 0xCD, 0xAB]);
+        assert_eq!(unwrap_ppdu(&out), Ok(&PSDU[..]));
+    }
+
+    #[test]
+    fn wrap_counts_fcs() {
+        let mut out = [0u8; 6];
+
+        assert_eq!(wrap_ppdu(&PSDU, true, &mut out), Ok(6));
+        assert_eq!(out, [0x07, 0x41, 0x88, 0x01, 0xCD, 0xAB]);
+        assert_eq!(unwrap_ppdu(&out), Err(crate::parser::Error::Truncated));
+    }
+
+    #[test]
+    fn wrap_refuses_long_frames() {
+        let mut out = [0u8; 200];
+
+        assert!(wrap_ppdu(&[0; 127], false, &mut out).is_ok());
+        assert_eq!(
+            wrap_ppdu(&[0; 126], true, &mut out),
+            Err(crate::composer::Error::FrameTooLong { len: 128, max: 127 })
+        );
+        assert_eq!(
+            wrap_ppdu(&PSDU, false, &mut out[..5]),
+            Err(crate::composer::Error::BufferTooSmall { needed: 6 })
+        );
+    }
+
+    #[test]
+    fn unwrap_invalid_header() {
+        assert_eq!(unwrap_ppdu(&[]), Err(crate::parser::Error::Truncated));
+        assert_eq!(unwrap_ppdu(&[0x00]), Ok(&[][..]));
+        assert_eq!(
+            unwrap_ppdu(&[0x80, 0x00]),
+            Err(crate::parser::Error::FrameTooLong { len: 128, max: 127 })
+        );
+    }
+}
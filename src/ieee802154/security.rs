@@ -0,0 +1,1261 @@
+use core::ops::Range;
+
+use thiserror_no_std::Error;
+
+use crate::{composer, composer::ComposeInto, parser, parser::Parser};
+
+use super::{
+    address::{AddressKind, ShortAddress},
+    composer::payload_ies_len,
+    control_field::FrameKind,
+    frame::{
+        command::{CommandId, COMMAND_ID_SIZE},
+        Frame,
+    },
+    parser::ParseContext,
+    security_header::{
+        AuxiliarySecurityHeader, KeyIdentifierMode, KeyIndex, LongKey, SecurityLevel, ShortKey,
+    },
+    Ieee802154,
+};
+
+/// Size in bytes of the CCM* nonce
+pub const NONCE_SIZE: usize = 13;
+/// Size in bytes of the CCM* key
+pub const KEY_SIZE: usize = 16;
+
+pub type Nonce = [u8; NONCE_SIZE];
+pub type Key = [u8; KEY_SIZE];
+
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecurityError {
+    #[error("Frame without security enabled")]
+    Unsecured,
+    #[error("Frame counter suppressed, the nonce cannot be built")]
+    MissingFrameCounter,
+    #[error("MIC does not match the frame")]
+    AuthenticationFailed,
+    #[error("No key matches the frame")]
+    UnknownKey,
+    #[error("Extended address of the originator unknown")]
+    UnknownDevice,
+    #[error("Table full")]
+    TableFull,
+    #[error("Frame counter not above the last one received")]
+    Replayed,
+    #[error("Frame counter exhausted")]
+    FrameCounterExhausted,
+    #[error("Security level below the one required for the frame")]
+    ImproperSecurityLevel,
+    #[error("composer: {0}")]
+    Composer(#[from] composer::Error),
+    #[error("parser: {0}")]
+    Parser(#[from] parser::ParseError),
+}
+
+/// CCM* authenticated encryption with a 128-bit key, supplied by the user of
+/// the crate.
+///
+/// `m_data` is empty for security levels without encryption, `mic` is empty
+/// for security levels without MIC.
+/// Annex B
+pub trait AeadCcmStar {
+    /// Encrypts `m_data` in place, and writes the MIC of `a_data` and
+    /// `m_data` into `mic`.
+    fn encrypt(
+        &mut self,
+        key: &Key,
+        nonce: &Nonce,
+        a_data: &[u8],
+        m_data: &mut [u8],
+        mic: &mut [u8],
+    ) -> Result<(), SecurityError>;
+
+    /// Decrypts `m_data` in place, and checks `mic` against `a_data` and the
+    /// decrypted `m_data`.
+    ///
+    /// # Error
+    ///
+    /// If `mic` does not match, returns `AuthenticationFailed`.
+    fn decrypt(
+        &mut self,
+        key: &Key,
+        nonce: &Nonce,
+        a_data: &[u8],
+        m_data: &mut [u8],
+        mic: &[u8],
+    ) -> Result<(), SecurityError>;
+}
+
+/// Key used to secure a frame.
+/// Chapter 9.5
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyDescriptor {
+    pub key: Key,
+}
+
+/// Input of the key lookup procedure, derived from the key identifier mode
+/// of a frame.
+/// Chapter 9.2.2
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyLookup {
+    /// Key of the device, for the implicit key identifier mode
+    Implicit(AddressKind),
+    /// Key index, the key source being macDefaultKeySource
+    KeyIndex(KeyIndex),
+    Key4(ShortKey, KeyIndex),
+    Key8(LongKey, KeyIndex),
+}
+
+impl KeyLookup {
+    /// Key lookup of a received frame. In implicit mode, the key is the one
+    /// of the source device, or of `coordinator` if the frame has no source
+    /// address.
+    ///
+    /// Returns `None` if the frame is not secured, or if the device is
+    /// unknown.
+    pub fn incoming(frame: &Frame, coordinator: Option<AddressKind>) -> Option<Self> {
+        let parts = secured_parts(frame)?;
+
+        Self::of(parts.aux, parts.src_addr, coordinator)
+    }
+
+    /// Key lookup of a frame secured by `aux`, sent by `src_addr`.
+    fn of(
+        aux: &AuxiliarySecurityHeader,
+        src_addr: Option<AddressKind>,
+        coordinator: Option<AddressKind>,
+    ) -> Option<Self> {
+        Some(match &aux.key_identifier_mode {
+            KeyIdentifierMode::Implicit => KeyLookup::Implicit(src_addr.or(coordinator)?),
+            KeyIdentifierMode::KeyIndex(index) => KeyLookup::KeyIndex(*index),
+            KeyIdentifierMode::Key4(source, index) => KeyLookup::Key4(*source, *index),
+            KeyIdentifierMode::Key8(source, index) => KeyLookup::Key8(*source, *index),
+        })
+    }
+
+    /// Whether both lookups select the same key. Extended addresses are
+    /// looked up without their PAN ID.
+    /// Chapter 9.2.2
+    pub fn matches(&self, other: &KeyLookup) -> bool {
+        match (self, other) {
+            (
+                KeyLookup::Implicit(AddressKind::Long(device)),
+                KeyLookup::Implicit(AddressKind::Long(other)),
+            ) => device.value() == other.value(),
+            _ => self == other,
+        }
+    }
+}
+
+/// Key and device tables of the MAC PIB, used to unsecure frames.
+/// Chapter 9.5
+pub trait KeyStore {
+    /// Key descriptor matching `lookup`, if any.
+    fn lookup(&self, lookup: &KeyLookup) -> Option<&KeyDescriptor>;
+
+    /// Extended address of `device`, used to build the nonce of the frames
+    /// it sends. Only known for extended addresses by default.
+    fn long_addr(&self, device: &AddressKind) -> Option<u64> {
+        match device {
+            AddressKind::Short(_) => None,
+            AddressKind::Long(address) => Some(address.value()),
+        }
+    }
+}
+
+/// In-memory [`KeyStore`], holding up to `N` keys and `N` devices.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTable<const N: usize> {
+    keys: heapless::Vec<(KeyLookup, KeyDescriptor), N>,
+    /// Extended address of the devices using a short address
+    devices: heapless::Vec<(ShortAddress, u64), N>,
+}
+
+impl<const N: usize> KeyTable<N> {
+    pub const fn new() -> Self {
+        KeyTable {
+            keys: heapless::Vec::new(),
+            devices: heapless::Vec::new(),
+        }
+    }
+
+    /// # Error
+    ///
+    /// If `N` keys are already stored, returns `TableFull`.
+    pub fn add_key(
+        &mut self,
+        lookup: KeyLookup,
+        descriptor: KeyDescriptor,
+    ) -> Result<(), SecurityError> {
+        self.keys
+            .push((lookup, descriptor))
+            .map_err(|_| SecurityError::TableFull)
+    }
+
+    /// # Error
+    ///
+    /// If `N` devices are already stored, returns `TableFull`.
+    pub fn add_device(&mut self, short: ShortAddress, long_addr: u64) -> Result<(), SecurityError> {
+        self.devices
+            .push((short, long_addr))
+            .map_err(|_| SecurityError::TableFull)
+    }
+}
+
+impl<const N: usize> KeyStore for KeyTable<N> {
+    fn lookup(&self, lookup: &KeyLookup) -> Option<&KeyDescriptor> {
+        self.keys
+            .iter()
+            .find(|(key, _)| key.matches(lookup))
+            .map(|(_, descriptor)| descriptor)
+    }
+
+    fn long_addr(&self, device: &AddressKind) -> Option<u64> {
+        match device {
+            AddressKind::Short(short) => self
+                .devices
+                .iter()
+                .find(|(address, _)| address == short)
+                .map(|(_, long_addr)| *long_addr),
+            AddressKind::Long(address) => Some(address.value()),
+        }
+    }
+}
+
+/// Last frame counter received from each device, kept for replay
+/// protection.
+/// Chapter 9.5
+pub trait FrameCounterStore {
+    fn frame_counter(&self, src: &AddressKind) -> Option<u32>;
+
+    /// # Error
+    ///
+    /// If the counter of `src` cannot be stored, returns `TableFull`.
+    fn set_frame_counter(
+        &mut self,
+        src: &AddressKind,
+        frame_counter: u32,
+    ) -> Result<(), SecurityError>;
+}
+
+/// In-memory [`FrameCounterStore`], holding the counters of up to `N`
+/// devices.
+#[derive(Debug, Clone, Default)]
+pub struct FrameCounterTable<const N: usize> {
+    counters: heapless::LinearMap<AddressKind, u32, N>,
+}
+
+impl<const N: usize> FrameCounterTable<N> {
+    pub const fn new() -> Self {
+        FrameCounterTable {
+            counters: heapless::LinearMap::new(),
+        }
+    }
+}
+
+impl<const N: usize> FrameCounterStore for FrameCounterTable<N> {
+    fn frame_counter(&self, src: &AddressKind) -> Option<u32> {
+        self.counters.get(src).copied()
+    }
+
+    fn set_frame_counter(
+        &mut self,
+        src: &AddressKind,
+        frame_counter: u32,
+    ) -> Result<(), SecurityError> {
+        self.counters
+            .insert(*src, frame_counter)
+            .map(|_| ())
+            .map_err(|_| SecurityError::TableFull)
+    }
+}
+
+/// Checks that the frame counter of `frame` is above the last one received
+/// from its originator, and records it. Frames without source address are
+/// sent by `coordinator`. To keep forged frames from moving the counter
+/// forward, it is only called once the frame is unsecured.
+/// Chapter 9.2.4
+///
+/// # Error
+///
+/// If security is not enabled, returns `Unsecured`. If the frame counter is
+/// suppressed, returns `MissingFrameCounter`. If the originator is unknown,
+/// returns `UnknownDevice`. If the frame counter is 0xFFFFFFFF, returns
+/// `FrameCounterExhausted`. If it is not above the last one received,
+/// returns `Replayed`.
+pub fn check_and_update_counter(
+    frame: &Frame,
+    coordinator: Option<AddressKind>,
+    store: &mut impl FrameCounterStore,
+) -> Result<(), SecurityError> {
+    let parts = secured_parts(frame).ok_or(SecurityError::Unsecured)?;
+    let frame_counter = parts
+        .aux
+        .frame_counter
+        .ok_or(SecurityError::MissingFrameCounter)?;
+    let src = parts
+        .src_addr
+        .or(coordinator)
+        .ok_or(SecurityError::UnknownDevice)?;
+
+    if frame_counter == u32::MAX {
+        return Err(SecurityError::FrameCounterExhausted);
+    }
+
+    if store
+        .frame_counter(&src)
+        .is_some_and(|last| frame_counter <= last)
+    {
+        return Err(SecurityError::Replayed);
+    }
+
+    store.set_frame_counter(&src, frame_counter)
+}
+
+/// Minimum security level required for a frame type, and for MAC commands
+/// a command identifier.
+/// Chapter 9.5
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityLevelDescriptor {
+    pub frame_kind: FrameKind,
+    /// Command identifier of MAC command frames, `None` matching all of them
+    pub command_id: Option<CommandId>,
+    /// `None` for security level 0
+    pub minimum: Option<SecurityLevel>,
+    /// Accepts unsecured frames from exempt devices
+    pub device_override: bool,
+}
+
+/// Security levels required for incoming frames, and devices exempt from
+/// them. Frames not matching any descriptor are accepted at any level.
+/// Chapter 9.2.4
+#[derive(Debug, Clone, Default)]
+pub struct SecurityPolicy<const N: usize> {
+    levels: heapless::Vec<SecurityLevelDescriptor, N>,
+    exempt: heapless::Vec<AddressKind, N>,
+}
+
+impl<const N: usize> SecurityPolicy<N> {
+    pub const fn new() -> Self {
+        SecurityPolicy {
+            levels: heapless::Vec::new(),
+            exempt: heapless::Vec::new(),
+        }
+    }
+
+    /// # Error
+    ///
+    /// If `N` descriptors are already stored, returns `TableFull`.
+    pub fn add_level(&mut self, descriptor: SecurityLevelDescriptor) -> Result<(), SecurityError> {
+        self.levels
+            .push(descriptor)
+            .map_err(|_| SecurityError::TableFull)
+    }
+
+    /// # Error
+    ///
+    /// If `N` devices are already exempt, returns `TableFull`.
+    pub fn add_exempt(&mut self, device: AddressKind) -> Result<(), SecurityError> {
+        self.exempt
+            .push(device)
+            .map_err(|_| SecurityError::TableFull)
+    }
+
+    /// Checks the security level `frame` was `received` with, `None` for an
+    /// unsecured frame or security level 0. The first descriptor matching
+    /// the frame applies. An unsecured frame below its minimum is still
+    /// accepted if the descriptor allows it and its source is exempt.
+    /// Chapter 9.2.4
+    ///
+    /// # Error
+    ///
+    /// If the level is below the minimum, or not comparable to it, returns
+    /// `ImproperSecurityLevel`.
+    pub fn check(
+        &self,
+        frame: &Frame,
+        received: Option<&SecurityLevel>,
+    ) -> Result<(), SecurityError> {
+        let (frame_kind, command_id, src_addr) = match frame {
+            Frame::Beacon(beacon) => (FrameKind::Beacon, None, beacon.header.src_addr),
+            Frame::EnhBeacon(beacon) => (FrameKind::Beacon, None, beacon.header.src_addr),
+            Frame::Data(data) => (FrameKind::Data, None, data.header.src_addr),
+            Frame::Acknowledgment(_) => (FrameKind::Acknowledgment, None, None),
+            Frame::EnhAcknowledgment(ack) => (FrameKind::Acknowledgment, None, ack.header.src_addr),
+            Frame::MacCommand(command) => (
+                FrameKind::MacCommand,
+                Some(command.command_id),
+                command.header.src_addr,
+            ),
+            Frame::MultiPurpose(frame) => (FrameKind::MultiPurpose, None, frame.src_addr),
+            Frame::Frak => (FrameKind::Frak, None, None),
+            Frame::Extended => (FrameKind::Extended, None, None),
+            Frame::Reserved(frame) => (frame.control.frame_kind(), None, None),
+        };
+
+        let Some(descriptor) = self.levels.iter().find(|descriptor| {
+            descriptor.frame_kind == frame_kind
+                && (descriptor.command_id.is_none() || descriptor.command_id == command_id)
+        }) else {
+            return Ok(());
+        };
+
+        let passed = match (&descriptor.minimum, received) {
+            (None, _) => true,
+            (Some(_), None) => {
+                descriptor.device_override && src_addr.is_some_and(|src| self.exempt.contains(&src))
+            }
+            (Some(minimum), Some(received)) => received >= minimum,
+        };
+
+        match passed {
+            true => Ok(()),
+            false => Err(SecurityError::ImproperSecurityLevel),
+        }
+    }
+}
+
+/// CCM* nonce of a frame sent by the device of extended address
+/// `src_long_addr`.
+/// Chapter 9.3.2.2
+pub fn nonce(src_long_addr: u64, frame_counter: u32, level: &SecurityLevel) -> Nonce {
+    let mut nonce = [0u8; NONCE_SIZE];
+
+    nonce[..8].copy_from_slice(&src_long_addr.to_be_bytes());
+    nonce[8..12].copy_from_slice(&frame_counter.to_be_bytes());
+    nonce[12] = level.bits();
+
+    nonce
+}
+
+/// Fields of a secured frame used to secure or unsecure it.
+struct SecuredParts<'f> {
+    aux: &'f AuxiliarySecurityHeader,
+    src_addr: Option<AddressKind>,
+    /// Size in bytes of the private payload: the payload IEs and what
+    /// follows them, or without payload IEs the beacon payload, the command
+    /// content or the payload. The fields preceding it and the header IEs
+    /// are left in the open.
+    private_len: usize,
+}
+
+fn secured_parts<'f>(frame: &'f Frame) -> Option<SecuredParts<'f>> {
+    // The command identifier is only private when following payload IEs
+    let (aux, src_addr, payload_ies, open, private) = match frame {
+        Frame::Beacon(beacon) => (
+            beacon.header.aux.as_ref(),
+            beacon.header.src_addr,
+            &[][..],
+            0,
+            beacon.payload.data,
+        ),
+        Frame::EnhBeacon(beacon) => (
+            beacon.header.aux.as_ref(),
+            beacon.header.src_addr,
+            beacon.payload_ies.as_bytes(),
+            0,
+            beacon.payload,
+        ),
+        Frame::Data(data) => (
+            data.header.aux.as_ref(),
+            data.header.src_addr,
+            data.payload_ies.as_bytes(),
+            0,
+            data.payload,
+        ),
+        Frame::EnhAcknowledgment(ack) => (
+            ack.header.aux.as_ref(),
+            ack.header.src_addr,
+            ack.payload_ies.as_bytes(),
+            0,
+            ack.payload,
+        ),
+        Frame::MacCommand(command) => (
+            command.header.aux.as_ref(),
+            command.header.src_addr,
+            command.payload_ies.as_bytes(),
+            COMMAND_ID_SIZE,
+            command.content,
+        ),
+        Frame::MultiPurpose(frame) => (
+            frame.aux.as_ref(),
+            frame.src_addr,
+            frame.payload_ies.as_bytes(),
+            0,
+            frame.payload,
+        ),
+        _ => return None,
+    };
+
+    let payload_len = open + private.len();
+    let private_len = match payload_ies.is_empty() {
+        true => private.len(),
+        false => payload_ies_len(payload_ies, payload_len > 0) + payload_len,
+    };
+
+    aux.map(|aux| SecuredParts {
+        aux,
+        src_addr,
+        private_len,
+    })
+}
+
+/// Position in a secured frame of the data authenticated only, the data
+/// encrypted and the MIC. Only the private payload is encrypted: the payload
+/// IEs and everything following them up to the MIC, or without payload IEs
+/// the beacon payload of beacons, the command content of MAC commands, and
+/// the payload of the other frames. Without encryption, the whole frame up
+/// to the MIC is authenticated.
+/// Chapter 9.3.5, Table 9-1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecuredFrameLayout {
+    pub a_data: Range<usize>,
+    pub m_data: Range<usize>,
+    pub mic: Range<usize>,
+}
+
+impl SecuredFrameLayout {
+    /// Layout of `frame` once composed. For a parsed frame, it matches the
+    /// input as long as composing gives it back.
+    ///
+    /// # Error
+    ///
+    /// If security is not enabled, returns `Unsecured`. Otherwise, fails as
+    /// [`Ieee802154::composed_len`] would.
+    pub fn of(frame: &Frame) -> Result<Self, SecurityError> {
+        let parts = secured_parts(frame).ok_or(SecurityError::Unsecured)?;
+        let len = Ieee802154::composed_len(frame)?;
+
+        Ok(Self::new(
+            len,
+            parts.aux.security_level.as_ref(),
+            parts.private_len,
+        ))
+    }
+
+    /// Layout of a frame of `len` bytes, whose private payload is
+    /// `private_len` bytes long.
+    fn new(len: usize, level: Option<&SecurityLevel>, private_len: usize) -> Self {
+        let mic_len = level.map_or(0, |level| level.mic.size());
+        let m_len = match level.is_some_and(|level| level.encrypted) {
+            true => private_len,
+            false => 0,
+        };
+        let mic_start = len - mic_len;
+        let m_start = mic_start - m_len;
+
+        SecuredFrameLayout {
+            a_data: 0..m_start,
+            m_data: m_start..mic_start,
+            mic: mic_start..len,
+        }
+    }
+
+    /// Splits `frame` into the a-data, m-data and MIC.
+    fn split<'f>(&self, frame: &'f mut [u8]) -> (&'f [u8], &'f mut [u8], &'f mut [u8]) {
+        let (rest, mic) = frame[..self.mic.end].split_at_mut(self.mic.start);
+        let (a_data, m_data) = rest.split_at_mut(self.m_data.start);
+
+        (a_data, m_data, mic)
+    }
+}
+
+/// Composes `frame` into `buf` and secures it with `key`, `src_long_addr`
+/// being the extended address of the device sending it. The MIC of `frame`
+/// is ignored, the one computed by `aead` being written in its place.
+/// Frames of security level 0 are composed as they are. Returns the number
+/// of bytes written.
+/// Chapter 9.2.2
+///
+/// # Error
+///
+/// If security is not enabled, returns `Unsecured`. If the frame counter is
+/// suppressed, returns `MissingFrameCounter`. Otherwise, returns the errors
+/// of [`ComposeInto::compose_into`] and `aead`.
+pub fn secure_frame(
+    frame: &Frame,
+    key: &Key,
+    src_long_addr: u64,
+    aead: &mut impl AeadCcmStar,
+    buf: &mut [u8],
+) -> Result<usize, SecurityError> {
+    let parts = secured_parts(frame).ok_or(SecurityError::Unsecured)?;
+    let len = Ieee802154::compose_into(frame, buf)?;
+
+    let Some(level) = &parts.aux.security_level else {
+        return Ok(len);
+    };
+    let frame_counter = parts
+        .aux
+        .frame_counter
+        .ok_or(SecurityError::MissingFrameCounter)?;
+
+    let nonce = nonce(src_long_addr, frame_counter, level);
+    let layout = SecuredFrameLayout::new(len, Some(level), parts.private_len);
+    let (a_data, m_data, mic) = layout.split(buf);
+    aead.encrypt(key, &nonce, a_data, m_data, mic)?;
+
+    Ok(len)
+}
+
+/// Unsecures the frame at the start of `buf` in place, with the key and
+/// originator found in `store`, and returns the decrypted frame. Frames
+/// without source address are sent by `coordinator`. Frames of security
+/// level 0 are returned as they are.
+/// Chapter 9.2.4
+///
+/// # Error
+///
+/// If the frame cannot be parsed, returns the error of [`Parser::parse`].
+/// If security is not enabled, returns `Unsecured`. If the frame counter is
+/// suppressed, returns `MissingFrameCounter`. If `store` has no matching key
+/// or does not know the extended address of the originator, returns
+/// `UnknownKey` or `UnknownDevice`. Otherwise, returns the errors of
+/// `aead`.
+pub fn unsecure_frame<'b>(
+    buf: &'b mut [u8],
+    store: &impl KeyStore,
+    coordinator: Option<AddressKind>,
+    aead: &mut impl AeadCcmStar,
+) -> Result<Frame<'b>, SecurityError> {
+    let (aux, src_addr, private_len, len) = match Ieee802154::parse_with_len(buf) {
+        Ok((frame, len)) => {
+            let parts = secured_parts(&frame).ok_or(SecurityError::Unsecured)?;
+
+            (parts.aux.clone(), parts.src_addr, parts.private_len, len)
+        }
+        // The command identifier follows the encrypted payload IEs, the
+        // private payload starts with the MAC payload
+        Err(err) if err.kind == parser::Error::EncryptedCommand => {
+            let (header, offset) = Ieee802154::parse_header(buf)?;
+            let aux = header.aux.ok_or(SecurityError::Unsecured)?;
+            let mic_len = aux
+                .security_level
+                .as_ref()
+                .map_or(0, |level| level.mic.size());
+
+            (
+                aux,
+                header.src_addr,
+                buf.len() - offset - mic_len,
+                buf.len(),
+            )
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let lookup = KeyLookup::of(&aux, src_addr, coordinator);
+    let device = src_addr.or(coordinator);
+
+    if let Some(level) = aux.security_level {
+        let frame_counter = aux
+            .frame_counter
+            .ok_or(SecurityError::MissingFrameCounter)?;
+        let descriptor = lookup
+            .and_then(|lookup| store.lookup(&lookup))
+            .ok_or(SecurityError::UnknownKey)?;
+        let src_long_addr = device
+            .and_then(|device| store.long_addr(&device))
+            .ok_or(SecurityError::UnknownDevice)?;
+
+        let nonce = nonce(src_long_addr, frame_counter, &level);
+        let layout = SecuredFrameLayout::new(len, Some(&level), private_len);
+        let (a_data, m_data, mic) = layout.split(buf);
+        aead.decrypt(&descriptor.key, &nonce, a_data, m_data, mic)?;
+    }
+
+    let context = ParseContext {
+        decrypted: true,
+        ..Default::default()
+    };
+
+    Ok(Ieee802154::parse_with_context(&buf[..len], context)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        composer::Composer,
+        ieee802154::{
+            address::{AddressKind, LongAddress, PanId, ShortAddress},
+            control_field::FrameKind,
+            frame::{command::CommandId, Frame},
+            ie::payload::PayloadIeIterator,
+            parser::ParseContext,
+            security_header::{
+                AuxiliarySecurityHeader, KeyIdentifierMode, KeyIndex, LongKey, SecurityLevel,
+                ShortKey,
+            },
+            Ieee802154,
+        },
+        parser::{ParseMode, Parser},
+    };
+
+    use super::{
+        check_and_update_counter, nonce, secure_frame, unsecure_frame, AeadCcmStar,
+        FrameCounterStore, FrameCounterTable, Key, KeyDescriptor, KeyLookup, KeyStore, KeyTable,
+        Nonce, SecuredFrameLayout, SecurityError, SecurityLevelDescriptor, SecurityPolicy,
+    };
+
+    const KEY: Key = [
+        0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE,
+        0xCF,
+    ];
+    const SRC_LONG_ADDR: u64 = 0xACDE_4800_0000_0001;
+    const PAN: PanId = PanId::new(0x1234);
+
+    /// Key of index 7, and key of the device 0x0001 in PAN 0x1234
+    fn store() -> KeyTable<2> {
+        let mut store = KeyTable::new();
+        let device = ShortAddress::new(PAN, 0x0001);
+
+        store
+            .add_key(
+                KeyLookup::KeyIndex(KeyIndex::new(0x07)),
+                KeyDescriptor { key: KEY },
+            )
+            .unwrap();
+        store
+            .add_key(
+                KeyLookup::Implicit(device.into()),
+                KeyDescriptor { key: KEY },
+            )
+            .unwrap();
+        store.add_device(device, SRC_LONG_ADDR).unwrap();
+
+        store
+    }
+
+    /// Stand-in for CCM*, recording the split it is given. The m-data is
+    /// XORed with a constant, and each MIC byte is a checksum of the nonce,
+    /// a-data and m-data.
+    #[derive(Default)]
+    struct Recorder {
+        key: Key,
+        nonce: Nonce,
+        a_data: Vec<u8>,
+        m_data: Vec<u8>,
+        mic_len: usize,
+    }
+
+    fn checksum(nonce: &Nonce, a_data: &[u8], m_data: &[u8]) -> u8 {
+        nonce
+            .iter()
+            .chain(a_data)
+            .chain(m_data)
+            .fold(0, |sum, byte| sum ^ byte)
+    }
+
+    impl Recorder {
+        fn record(&mut self, key: &Key, nonce: &Nonce, a_data: &[u8], m_data: &[u8], mic: &[u8]) {
+            self.key = *key;
+            self.nonce = *nonce;
+            self.a_data = a_data.to_vec();
+            self.m_data = m_data.to_vec();
+            self.mic_len = mic.len();
+        }
+    }
+
+    impl AeadCcmStar for Recorder {
+        fn encrypt(
+            &mut self,
+            key: &Key,
+            nonce: &Nonce,
+            a_data: &[u8],
+            m_data: &mut [u8],
+            mic: &mut [u8],
+        ) -> Result<(), SecurityError> {
+            self.record(key, nonce, a_data, m_data, mic);
+            mic.fill(checksum(nonce, a_data, m_data));
+            m_data.iter_mut().for_each(|byte| *byte ^= 0x5A);
+
+            Ok(())
+        }
+
+        fn decrypt(
+            &mut self,
+            key: &Key,
+            nonce: &Nonce,
+            a_data: &[u8],
+            m_data: &mut [u8],
+            mic: &[u8],
+        ) -> Result<(), SecurityError> {
+            m_data.iter_mut().for_each(|byte| *byte ^= 0x5A);
+            self.record(key, nonce, a_data, m_data, mic);
+
+            let sum = checksum(nonce, a_data, m_data);
+            match mic.iter().all(|byte| *byte == sum) {
+                true => Ok(()),
+                false => Err(SecurityError::AuthenticationFailed),
+            }
+        }
+    }
+
+    #[test]
+    fn annex_c_data_frame_split() {
+        // Data frame of Annex C.2.1 before securing, security level 4 being
+        // reserved since IEEE 802.15.4-2015
+        let input: [u8; 30] = [
+            0x69, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x04, 0x05, 0x00, 0x00, 0x00, 0x61, 0x62,
+            0x63, 0x64,
+        ];
+        let frame = Ieee802154::parse_with_mode(&input, ParseMode::Lenient).unwrap();
+        let mut aead = Recorder::default();
+        let mut buf = [0u8; 30];
+
+        assert_eq!(
+            secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf),
+            Ok(30)
+        );
+        assert_eq!(aead.key, KEY);
+        assert_eq!(
+            aead.nonce,
+            [0xAC, 0xDE, 0x48, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x04]
+        );
+        assert_eq!(aead.a_data, &input[..26]);
+        assert_eq!(aead.m_data, &input[26..]);
+        assert_eq!(aead.mic_len, 0);
+        assert_eq!(buf[..26], input[..26]);
+        assert_eq!(buf[26..], [0x3B, 0x38, 0x39, 0x3E]);
+    }
+
+    #[test]
+    fn secure_unsecure_round_trip() {
+        // Data frame and vendor-specific MAC command of security level 5,
+        // with a key index
+        let inputs: [&[u8]; 2] = [
+            &[
+                0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x78, 0x56, 0x34, 0x12,
+                0x07, 0xDE, 0xAD, 0x00, 0x00, 0x00, 0x00,
+            ],
+            &[
+                0x6B, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x78, 0x56, 0x34, 0x12,
+                0x07, 0x24, 0xDE, 0xAD, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ];
+
+        for input in inputs {
+            let frame = Ieee802154::parse(input).unwrap();
+            let mut aead = Recorder::default();
+            let mut buf = [0u8; 32];
+
+            let len = secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf).unwrap();
+            assert_eq!(len, input.len());
+            assert_eq!(aead.nonce[8..], [0x12, 0x34, 0x56, 0x78, 0x05]);
+            // The command identifier is left in the open
+            assert_eq!(aead.a_data, &input[..len - 6]);
+            assert_eq!(aead.m_data, &[0xDE, 0xAD]);
+            assert_eq!(aead.mic_len, 4);
+            assert_eq!(buf[len - 6..len - 4], [0x84, 0xF7]);
+
+            let secured = buf;
+            let unsecured = unsecure_frame(&mut buf[..len], &store(), None, &mut aead);
+            assert_eq!(
+                Ieee802154::compose(&unsecured.unwrap()).unwrap()[..len - 4],
+                input[..len - 4]
+            );
+
+            // Tampered header
+            let mut buf = secured;
+            buf[2] ^= 0x01;
+            assert_eq!(
+                unsecure_frame(&mut buf[..len], &store(), None, &mut aead),
+                Err(SecurityError::AuthenticationFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_with_payload_ies() {
+        // Data frame and MAC command of security level 5 with an implicit
+        // key, whose payload IEs are encrypted
+        let inputs: [&[u8]; 2] = [
+            &[
+                0x49, 0xAA, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x05, 0x78, 0x56, 0x34, 0x12,
+                0x00, 0x3F, 0x01, 0x80, 0xAA, 0x00, 0xF8, 0xDE, 0xAD, 0x00, 0x00, 0x00, 0x00,
+            ],
+            &[
+                0x4B, 0xAA, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x05, 0x78, 0x56, 0x34, 0x12,
+                0x00, 0x3F, 0x01, 0x80, 0xAA, 0x00, 0xF8, 0x24, 0xDE, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ];
+        let context = ParseContext {
+            decrypted: true,
+            ..Default::default()
+        };
+
+        for input in inputs {
+            let frame = Ieee802154::parse_with_context(input, context).unwrap();
+            let mut aead = Recorder::default();
+            let mut buf = [0u8; 32];
+
+            let len = secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf).unwrap();
+            assert_eq!(len, input.len());
+            assert_eq!(aead.a_data, &input[..16]);
+            assert_eq!(aead.m_data, &input[16..23]);
+
+            let unsecured = unsecure_frame(&mut buf[..len], &store(), None, &mut aead);
+            assert_eq!(
+                Ieee802154::compose(&unsecured.unwrap()).unwrap()[..len - 4],
+                input[..len - 4]
+            );
+        }
+    }
+
+    #[test]
+    fn authentication_only() {
+        // Security level 2, the whole frame being authenticated
+        let input: [u8; 26] = [
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x02, 0x78, 0x56, 0x34, 0x12,
+            0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let mut aead = Recorder::default();
+        let mut buf = [0u8; 26];
+
+        assert_eq!(
+            secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf),
+            Ok(26)
+        );
+        assert_eq!(aead.a_data, &input[..18]);
+        assert!(aead.m_data.is_empty());
+        assert_eq!(aead.mic_len, 8);
+        assert_eq!(buf[..18], input[..18]);
+
+        let Frame::Data(data) = unsecure_frame(&mut buf, &store(), None, &mut aead).unwrap() else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(data.payload, &[0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn unsupported_frames() {
+        let mut aead = Recorder::default();
+        let mut buf = [0u8; 32];
+
+        let mut unsecured = [
+            0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD,
+        ];
+        let frame = Ieee802154::parse(&unsecured).unwrap();
+        assert_eq!(
+            secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf),
+            Err(SecurityError::Unsecured)
+        );
+        assert_eq!(
+            unsecure_frame(&mut unsecured, &store(), None, &mut aead),
+            Err(SecurityError::Unsecured)
+        );
+
+        // Frame counter suppressed in IEEE 802.15.4-2015
+        let mut suppressed = [
+            0x69, 0xA8, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x25, 0x42, 0xAA, 0xBB, 0xCC,
+            0xDD,
+        ];
+        let frame = Ieee802154::parse(&suppressed).unwrap();
+        assert_eq!(
+            secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut aead, &mut buf),
+            Err(SecurityError::MissingFrameCounter)
+        );
+        assert_eq!(
+            unsecure_frame(&mut suppressed, &store(), None, &mut aead),
+            Err(SecurityError::MissingFrameCounter)
+        );
+    }
+
+    #[test]
+    fn key_lookup() {
+        // Security level 5 in implicit mode, without source address
+        let mut input: [u8; 18] = [
+            0x29, 0x08, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x05, 0x78, 0x56, 0x34, 0x12, 0xDE, 0xAD,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let device: AddressKind = ShortAddress::new(PAN, 0x0001).into();
+        let coordinator: AddressKind = LongAddress::new(PAN, 0x0102_0304_0506_0708).into();
+
+        assert_eq!(KeyLookup::incoming(&frame, None), None);
+        assert_eq!(
+            KeyLookup::incoming(&frame, Some(device)),
+            Some(KeyLookup::Implicit(device))
+        );
+
+        let mut aead = Recorder::default();
+        let store = store();
+        assert_eq!(store.long_addr(&device), Some(SRC_LONG_ADDR));
+        assert_eq!(store.long_addr(&coordinator), Some(0x0102_0304_0506_0708));
+
+        // Extended addresses are looked up in any PAN
+        let elsewhere: AddressKind =
+            LongAddress::new(PanId::BROADCAST, 0x0102_0304_0506_0708).into();
+        assert!(KeyLookup::Implicit(coordinator).matches(&KeyLookup::Implicit(elsewhere)));
+        assert!(!KeyLookup::Implicit(device).matches(&KeyLookup::Implicit(coordinator)));
+        assert_eq!(
+            unsecure_frame(&mut input, &store, Some(coordinator), &mut aead),
+            Err(SecurityError::UnknownKey)
+        );
+
+        // The device in the key table, but not in the device table
+        let mut store = KeyTable::<1>::new();
+        store
+            .add_key(KeyLookup::Implicit(device), KeyDescriptor { key: KEY })
+            .unwrap();
+        assert_eq!(
+            store.add_key(KeyLookup::Implicit(coordinator), KeyDescriptor { key: KEY }),
+            Err(SecurityError::TableFull)
+        );
+        assert_eq!(
+            unsecure_frame(&mut input, &store, Some(device), &mut aead),
+            Err(SecurityError::UnknownDevice)
+        );
+
+        // Explicit key sources
+        let mut input: [u8; 30] = [
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x1D, 0x78, 0x56, 0x34, 0x12,
+            0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0x42, 0xDE, 0xAD, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        assert_eq!(
+            KeyLookup::incoming(&frame, None),
+            Some(KeyLookup::Key8(
+                LongKey::new([0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]),
+                KeyIndex::new(0x42)
+            ))
+        );
+
+        input[9] = 0x15;
+        let frame = Ieee802154::parse(&input).unwrap();
+        assert_eq!(
+            KeyLookup::incoming(&frame, None),
+            Some(KeyLookup::Key4(
+                ShortKey::new([0x08, 0x07, 0x06, 0x05]),
+                KeyIndex::new(0x04)
+            ))
+        );
+    }
+
+    #[test]
+    fn frame_counter_replay() {
+        // Security level 5 with a key index, from 0x0001 in PAN 0x1234
+        let mut input: [u8; 21] = [
+            0x69, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x0D, 0x05, 0x00, 0x00, 0x00,
+            0x07, 0xDE, 0xAD, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let src: AddressKind = ShortAddress::new(PAN, 0x0001).into();
+        let mut store = FrameCounterTable::<1>::new();
+        let mut check = |counter: u32, store: &mut FrameCounterTable<1>| {
+            input[10..14].copy_from_slice(&counter.to_le_bytes());
+            check_and_update_counter(&Ieee802154::parse(&input).unwrap(), None, store)
+        };
+
+        assert_eq!(check(5, &mut store), Ok(()));
+        assert_eq!(store.frame_counter(&src), Some(5));
+
+        // Replayed, then decreasing
+        assert_eq!(check(5, &mut store), Err(SecurityError::Replayed));
+        assert_eq!(check(4, &mut store), Err(SecurityError::Replayed));
+        assert_eq!(store.frame_counter(&src), Some(5));
+
+        assert_eq!(check(6, &mut store), Ok(()));
+        assert_eq!(check(0xFFFF_FFFE, &mut store), Ok(()));
+        assert_eq!(
+            check(0xFFFF_FFFF, &mut store),
+            Err(SecurityError::FrameCounterExhausted)
+        );
+        assert_eq!(store.frame_counter(&src), Some(0xFFFF_FFFE));
+
+        // A second device does not fit
+        let other: AddressKind = ShortAddress::new(PAN, 0x0002).into();
+        assert_eq!(store.frame_counter(&other), None);
+        assert_eq!(
+            store.set_frame_counter(&other, 1),
+            Err(SecurityError::TableFull)
+        );
+    }
+
+    #[test]
+    fn frame_counter_without_source() {
+        // Security level 5 in implicit mode, without source address
+        let input: [u8; 18] = [
+            0x29, 0x08, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x05, 0x78, 0x56, 0x34, 0x12, 0xDE, 0xAD,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let frame = Ieee802154::parse(&input).unwrap();
+        let coordinator: AddressKind = LongAddress::new(PAN, 0x0102_0304_0506_0708).into();
+        let mut store = FrameCounterTable::<2>::new();
+
+        assert_eq!(
+            check_and_update_counter(&frame, None, &mut store),
+            Err(SecurityError::UnknownDevice)
+        );
+        assert_eq!(
+            check_and_update_counter(&frame, Some(coordinator), &mut store),
+            Ok(())
+        );
+        assert_eq!(store.frame_counter(&coordinator), Some(0x1234_5678));
+    }
+
+    #[test]
+    fn security_policy() {
+        let level = |bits| SecurityLevel::from_bits(bits).unwrap();
+        let device: AddressKind = ShortAddress::new(PAN, 0x0001).into();
+        let mut policy = SecurityPolicy::<2>::new();
+        policy
+            .add_level(SecurityLevelDescriptor {
+                frame_kind: FrameKind::Data,
+                command_id: None,
+                minimum: level(0x05),
+                device_override: true,
+            })
+            .unwrap();
+        // Data requests may be sent unsecured
+        policy
+            .add_level(SecurityLevelDescriptor {
+                frame_kind: FrameKind::MacCommand,
+                command_id: Some(CommandId::DataRequest),
+                minimum: None,
+                device_override: false,
+            })
+            .unwrap();
+        assert_eq!(
+            policy.add_level(SecurityLevelDescriptor {
+                frame_kind: FrameKind::Beacon,
+                command_id: None,
+                minimum: level(0x01),
+                device_override: false,
+            }),
+            Err(SecurityError::TableFull)
+        );
+
+        // Unsecured data frame from 0x0001
+        let data = Ieee802154::parse(&[
+            0x41, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0xDE, 0xAD,
+        ])
+        .unwrap();
+        for (received, expected) in [
+            (0x05, Ok(())),
+            (0x07, Ok(())),
+            (0x06, Ok(())),
+            (0x01, Err(SecurityError::ImproperSecurityLevel)),
+            // ENC and MIC-32 are not comparable
+            (0x04, Err(SecurityError::ImproperSecurityLevel)),
+            (0x00, Err(SecurityError::ImproperSecurityLevel)),
+        ] {
+            assert_eq!(
+                policy.check(&data, level(received).as_ref()),
+                expected,
+                "level {received}"
+            );
+        }
+
+        // Unsecured frames are accepted from exempt devices only
+        policy.add_exempt(device).unwrap();
+        assert_eq!(policy.check(&data, None), Ok(()));
+        assert_eq!(
+            policy.check(&data, level(0x01).as_ref()),
+            Err(SecurityError::ImproperSecurityLevel)
+        );
+
+        // Data request, then association request which no descriptor
+        // covers
+        let mut input = [
+            0x43, 0x88, 0x2A, 0x34, 0x12, 0xCD, 0xAB, 0x01, 0x00, 0x04, 0x8E,
+        ];
+        assert_eq!(
+            policy.check(&Ieee802154::parse(&input[..10]).unwrap(), None),
+            Ok(())
+        );
+        input[9] = 0x01;
+        let command = Ieee802154::parse(&input).unwrap();
+        assert_eq!(policy.check(&command, None), Ok(()));
+        assert_eq!(policy.check(&command, level(0x01).as_ref()), Ok(()));
+    }
+
+    #[test]
+    fn nonce_layout() {
+        let level = crate::ieee802154::security_header::SecurityLevel::from_bits(6)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            nonce(0x0102_0304_0506_0708, 0x0A0B_0C0D, &level),
+            [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x0A, 0x0B, 0x0C, 0x0D, 0x06]
+        );
+    }
+
+    #[test]
+    fn layout_with_payload_ies() {
+        // Security level 5, HT1, a payload IE, the Payload Termination IE,
+        // the payload and a MIC-32
+        let secured = |control: [u8; 2], payload: [u8; 2]| {
+            [
+                control[0], control[1], 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0x05, 0x01, 0x00,
+                0x00, 0x00, 0x00, 0x3F, 0x01, 0x80, 0xAA, 0x00, 0xF8, payload[0], payload[1], 0xE1,
+                0xE2, 0xE3, 0xE4,
+            ]
+        };
+        let layout = SecuredFrameLayout {
+            a_data: 0..16,
+            m_data: 16..23,
+            mic: 23..27,
+        };
+        // Frames before securing, their payload IEs in the clear
+        let context = ParseContext {
+            decrypted: true,
+            ..Default::default()
+        };
+
+        // Enhanced beacon, data, enhanced acknowledgment, and MAC command
+        // whose command identifier is private as well
+        for (control, payload) in [
+            ([0x48, 0xAA], [0xBB, 0xCC]),
+            ([0x49, 0xAA], [0xBB, 0xCC]),
+            ([0x4A, 0xAA], [0xBB, 0xCC]),
+            ([0x4B, 0xAA], [0x24, 0xDD]),
+        ] {
+            let input = secured(control, payload);
+            let frame = Ieee802154::parse_with_context(&input, context).unwrap();
+
+            assert_eq!(
+                SecuredFrameLayout::of(&frame),
+                Ok(layout.clone()),
+                "{frame:?}"
+            );
+        }
+
+        // Multipurpose frame, composed with its long frame control field
+        let Frame::MultiPurpose(mut frame) =
+            Ieee802154::parse(&[0x25, 0x2A, 0xFF, 0xFF, 0xBB, 0xCC]).unwrap()
+        else {
+            panic!("expected a multipurpose frame");
+        };
+        frame.control.security_enabled = true;
+        frame.aux = Some(AuxiliarySecurityHeader {
+            security_level: SecurityLevel::from_bits(0x05).unwrap(),
+            key_identifier_mode: KeyIdentifierMode::Implicit,
+            frame_counter: Some(1),
+        });
+        frame.payload_ies = PayloadIeIterator::new(&[0x01, 0x80, 0xAA]);
+        frame.mic = &[0xE1, 0xE2, 0xE3, 0xE4];
+
+        let frame = Frame::MultiPurpose(frame);
+        let composed = Ieee802154::compose(&frame).unwrap();
+        assert_eq!(
+            SecuredFrameLayout::of(&frame),
+            Ok(SecuredFrameLayout {
+                a_data: 0..12,
+                m_data: 12..19,
+                mic: 19..23,
+            })
+        );
+        assert_eq!(&composed[12..17], &[0x01, 0x80, 0xAA, 0x00, 0xF8]);
+    }
+}
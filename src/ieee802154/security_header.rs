@@ -1,61 +1,188 @@
-use byte::{BytesExt, TryRead, LE};
+use core::cmp::Ordering;
+
+use byte::{ctx::Bytes, BytesExt, TryRead, TryWrite, LE};
+
+use crate::parser::ParseMode;
+
+/// Size in bytes of the security control field
+const SECURITY_CONTROL_SIZE: usize = 1;
+/// Size in bytes of the frame counter
+const FRAME_COUNTER_SIZE: usize = 4;
+/// Reserved bits of the security control field
+const SECURITY_CONTROL_RESERVED: u8 = 0xC0;
 
 // TODO: chapter 9.4
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct AuxiliarySecurityHeader {
     pub security_level: Option<SecurityLevel>,
     pub key_identifier_mode: KeyIdentifierMode,
+    /// Frame counter, `None` when suppressed. Suppression is only allowed
+    /// from IEEE 802.15.4-2015 on, for TSCH frames whose nonce uses the ASN
+    /// in place of the frame counter.
+    /// Chapter 9.4.2.3
     pub frame_counter: Option<u32>,
 }
 
-impl<'a> TryRead<'a> for AuxiliarySecurityHeader {
-    fn try_read(bytes: &'a [u8], _ctx: ()) -> byte::Result<(Self, usize)> {
-        let mut hdr = AuxiliarySecurityHeader::default();
+impl AuxiliarySecurityHeader {
+    /// Decodes the auxiliary security header at the start of `data`, and
+    /// returns it along with its size in bytes.
+    /// Chapter 9.4
+    ///
+    /// # Error
+    ///
+    /// If `data` ends before the header does, in the frame counter or key
+    /// identifier fields as well, returns `TruncatedSecurityHeader`. If the
+    /// reserved bits of the security control field are set, returns
+    /// `ReservedSecurityControl` with them.
+    pub fn from_bytes(data: &[u8]) -> Result<(Self, usize), crate::parser::Error> {
+        Self::from_bytes_with_mode(data, ParseMode::Strict)
+    }
+
+    /// Same as [`AuxiliarySecurityHeader::from_bytes`]. In lenient mode, the
+    /// reserved bits of the security control field are ignored.
+    pub fn from_bytes_with_mode(
+        data: &[u8],
+        mode: ParseMode,
+    ) -> Result<(Self, usize), crate::parser::Error> {
+        let truncated = |_| crate::parser::Error::TruncatedSecurityHeader;
         let offset = &mut 0;
 
-        let security_control: u8 = bytes.read(offset)?;
+        let security_control: u8 = data.read(offset).map_err(truncated)?;
+
+        let reserved = security_control & SECURITY_CONTROL_RESERVED;
+        if mode == ParseMode::Strict && reserved != 0 {
+            return Err(crate::parser::Error::ReservedSecurityControl(reserved));
+        }
 
-        let security_level: u8 = security_control & 0x7;
         let key_identifier_mode: u8 = (security_control >> 3) & 0x3;
         let frame_counter_present: bool = security_control & (1 << 5) == 0;
 
-        if security_level != 0 {
-            hdr.security_level = Some(SecurityLevel {
-                encrypted: security_level & 0x4 != 0,
-                mic: match security_level & 0x3 {
-                    0x1 => Mic::Mic32,
-                    0x2 => Mic::Mic64,
-                    0x3 => Mic::Mic128,
-                    _ => return Err(byte::Error::BadInput { err: "Invalid MIC" }),
-                },
-            });
-        }
+        let security_level = SecurityLevel::from_bits(security_control & 0x7)?;
 
-        if frame_counter_present {
-            hdr.frame_counter = Some(bytes.read_with(offset, LE)?);
-        }
+        let frame_counter = match frame_counter_present {
+            true => Some(data.read_with(offset, LE).map_err(truncated)?),
+            false => None,
+        };
 
-        hdr.key_identifier_mode = match key_identifier_mode {
-            0x1 => KeyIdentifierMode::KeyIndex(KeyIndex(bytes.read(offset)?)),
+        let key_identifier_mode = match key_identifier_mode {
+            0x1 => KeyIdentifierMode::KeyIndex(KeyIndex(data.read(offset).map_err(truncated)?)),
             0x2 => KeyIdentifierMode::Key4(
-                ShortKey(bytes.read_with(offset, LE)?),
-                KeyIndex(bytes.read(offset)?),
+                ShortKey(read_key_source(data, offset).map_err(truncated)?),
+                KeyIndex(data.read(offset).map_err(truncated)?),
             ),
             0x3 => KeyIdentifierMode::Key8(
-                LongKey(bytes.read_with(offset, LE)?),
-                KeyIndex(bytes.read(offset)?),
+                LongKey(read_key_source(data, offset).map_err(truncated)?),
+                KeyIndex(data.read(offset).map_err(truncated)?),
             ),
             _ => KeyIdentifierMode::Implicit,
         };
 
+        let hdr = AuxiliarySecurityHeader {
+            security_level,
+            key_identifier_mode,
+            frame_counter,
+        };
+
         Ok((hdr, *offset))
     }
+
+    /// Size in bytes of the header, security control field included.
+    pub fn serialized_len(&self) -> usize {
+        SECURITY_CONTROL_SIZE
+            + self.frame_counter.map_or(0, |_| FRAME_COUNTER_SIZE)
+            + self.key_identifier_mode.key_identifier_length()
+    }
+
+    /// Encodes the header at the start of `buf`, and returns its size in
+    /// bytes.
+    /// Chapter 9.4
+    ///
+    /// # Error
+    ///
+    /// If `buf` is too small, returns `BufferTooSmall`.
+    pub fn write_into(&self, buf: &mut [u8]) -> Result<usize, crate::composer::Error> {
+        self.try_write(buf, ())
+            .map_err(|_| crate::composer::Error::BufferTooSmall {
+                needed: self.serialized_len(),
+            })
+    }
+
+    /// Whether the Frame Counter Suppression bit is set, the frame counter
+    /// being absent.
+    /// Chapter 9.4.2.3
+    pub fn frame_counter_suppressed(&self) -> bool {
+        self.frame_counter.is_none()
+    }
+
+    /// Security control field, as written at the start of the header.
+    /// Chapter 9.4.2
+    pub fn security_control_bits(&self) -> u8 {
+        let security_level: u8 = self.security_level.as_ref().map_or(0, SecurityLevel::bits);
+        let key_identifier_mode: u8 = match self.key_identifier_mode {
+            KeyIdentifierMode::Implicit => 0x0,
+            KeyIdentifierMode::KeyIndex(_) => 0x1,
+            KeyIdentifierMode::Key4(_, _) => 0x2,
+            KeyIdentifierMode::Key8(_, _) => 0x3,
+        };
+
+        security_level | (key_identifier_mode << 3) | ((self.frame_counter_suppressed() as u8) << 5)
+    }
+}
+
+impl<'a> TryRead<'a, ParseMode> for AuxiliarySecurityHeader {
+    fn try_read(bytes: &'a [u8], mode: ParseMode) -> byte::Result<(Self, usize)> {
+        Self::from_bytes_with_mode(bytes, mode).map_err(|err| match err {
+            crate::parser::Error::TruncatedSecurityHeader => byte::Error::Incomplete,
+            _ => byte::Error::BadInput {
+                err: "Reserved security control bits",
+            },
+        })
+    }
+}
+
+impl TryWrite for &AuxiliarySecurityHeader {
+    fn try_write(self, bytes: &mut [u8], _ctx: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+
+        bytes.write(offset, self.security_control_bits())?;
+
+        if let Some(frame_counter) = self.frame_counter {
+            bytes.write_with(offset, frame_counter, LE)?;
+        }
+
+        match &self.key_identifier_mode {
+            KeyIdentifierMode::Implicit => (),
+            KeyIdentifierMode::KeyIndex(KeyIndex(index)) => bytes.write(offset, *index)?,
+            KeyIdentifierMode::Key4(ShortKey(source), KeyIndex(index)) => {
+                bytes.write(offset, &source[..])?;
+                bytes.write(offset, *index)?;
+            }
+            KeyIdentifierMode::Key8(LongKey(source), KeyIndex(index)) => {
+                bytes.write(offset, &source[..])?;
+                bytes.write(offset, *index)?;
+            }
+        }
+
+        Ok(*offset)
+    }
 }
 
+/// Reads a key source, whose octets are kept in transmission order.
+fn read_key_source<const N: usize>(bytes: &[u8], offset: &mut usize) -> byte::Result<[u8; N]> {
+    let mut source = [0; N];
+    source.copy_from_slice(bytes.read_with(offset, Bytes::Len(N))?);
+
+    Ok(source)
+}
+
+/// Variants are ordered by increasing MIC length.
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Mic {
+    /// No MIC, for the encryption only security level 4 which later
+    /// revisions of the standard reserve
+    None,
     Mic32,
     Mic64,
     Mic128,
@@ -64,13 +191,32 @@ pub enum Mic {
 impl Mic {
     pub fn size(&self) -> usize {
         match self {
+            Mic::None => 0,
             Mic::Mic32 => 4,
             Mic::Mic64 => 8,
             Mic::Mic128 => 16,
         }
     }
+
+    /// Low 2 bits of the security level.
+    /// Table 9-6
+    const fn bits(&self) -> u8 {
+        match self {
+            Mic::None => 0x0,
+            Mic::Mic32 => 0x1,
+            Mic::Mic64 => 0x2,
+            Mic::Mic128 => 0x3,
+        }
+    }
 }
 
+/// Security level of a secured frame, level 0 being represented by the
+/// absence of a security level.
+///
+/// A level is at least as strong as another one when it is encrypted if the
+/// other one is, and when its MIC is at least as long. Levels such as MIC-64
+/// and ENC are not comparable.
+/// Chapter 9.4.2.2
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SecurityLevel {
@@ -78,18 +224,115 @@ pub struct SecurityLevel {
     pub encrypted: bool,
 }
 
+impl SecurityLevel {
+    /// Decodes a security level from the 3 bits of the security control
+    /// field. Level 0 is decoded as `None`, level 4 (ENC, without MIC) as
+    /// defined by IEEE 802.15.4-2006.
+    /// Table 9-6
+    ///
+    /// # Error
+    ///
+    /// If `bits` is greater than 7, returns `InvalidSecurityLevel`.
+    pub const fn from_bits(bits: u8) -> Result<Option<Self>, crate::parser::Error> {
+        let mic = match bits & 0x3 {
+            0x1 => Mic::Mic32,
+            0x2 => Mic::Mic64,
+            0x3 => Mic::Mic128,
+            _ => Mic::None,
+        };
+
+        match bits {
+            0 => Ok(None),
+            1..=7 => Ok(Some(SecurityLevel {
+                mic,
+                encrypted: bits & 0x4 != 0,
+            })),
+            _ => Err(crate::parser::Error::InvalidSecurityLevel(bits)),
+        }
+    }
+
+    /// 3 bits value of the security level.
+    /// Table 9-6
+    pub const fn bits(&self) -> u8 {
+        ((self.encrypted as u8) << 2) | self.mic.bits()
+    }
+}
+
+impl PartialOrd for SecurityLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (
+            self.encrypted.cmp(&other.encrypted),
+            self.mic.cmp(&other.mic),
+        ) {
+            (encrypted, mic) if encrypted == mic => Some(encrypted),
+            (Ordering::Equal, ordering) | (ordering, Ordering::Equal) => Some(ordering),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct KeyIndex(u8);
 
+impl KeyIndex {
+    pub const fn new(index: u8) -> Self {
+        KeyIndex(index)
+    }
+
+    pub const fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ShortKey(u32);
+pub struct ShortKey(pub(crate) [u8; 4]);
+
+impl ShortKey {
+    /// Key source from its octets in transmission order.
+    pub const fn new(bytes: [u8; 4]) -> Self {
+        ShortKey(bytes)
+    }
+
+    /// Octets of the key source, in transmission order.
+    pub const fn as_bytes(&self) -> [u8; 4] {
+        self.0
+    }
+}
 
+impl From<[u8; 4]> for ShortKey {
+    fn from(bytes: [u8; 4]) -> Self {
+        ShortKey(bytes)
+    }
+}
+
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct LongKey(u64);
+pub struct LongKey(pub(crate) [u8; 8]);
+
+impl LongKey {
+    /// Key source from its octets in transmission order.
+    pub const fn new(bytes: [u8; 8]) -> Self {
+        LongKey(bytes)
+    }
+
+    /// Octets of the key source, in transmission order.
+    pub const fn as_bytes(&self) -> [u8; 8] {
+        self.0
+    }
+}
+
+impl From<[u8; 8]> for LongKey {
+    fn from(bytes: [u8; 8]) -> Self {
+        LongKey(bytes)
+    }
+}
 
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum KeyIdentifierMode {
@@ -120,8 +363,11 @@ mod tests {
     use alloc::vec::Vec;
     use byte::BytesExt;
 
-    use crate::ieee802154::security_header::{
-        KeyIdentifierMode, KeyIndex, LongKey, Mic, SecurityLevel, ShortKey,
+    use crate::{
+        ieee802154::security_header::{
+            KeyIdentifierMode, KeyIndex, LongKey, Mic, SecurityLevel, ShortKey,
+        },
+        parser::ParseMode,
     };
 
     use super::AuxiliarySecurityHeader;
@@ -150,14 +396,75 @@ mod tests {
         assert_eq!(hdr.security_level, None);
     }
 
+    #[test]
+    fn from_bytes_truncated() {
+        let input: [u8; 3] = [0x00, 0xFE, 0xDC];
+
+        assert_eq!(
+            AuxiliarySecurityHeader::from_bytes(&input).unwrap_err(),
+            crate::parser::Error::TruncatedSecurityHeader
+        );
+
+        // Key index, then key source running off the end
+        for input in [&[0x08][..], &[0x38, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]] {
+            assert_eq!(
+                AuxiliarySecurityHeader::from_bytes(input).unwrap_err(),
+                crate::parser::Error::TruncatedSecurityHeader
+            );
+        }
+        assert_eq!(
+            AuxiliarySecurityHeader::from_bytes(&[]).unwrap_err(),
+            crate::parser::Error::TruncatedSecurityHeader
+        );
+    }
+
+    #[test]
+    fn reserved_security_control_bits() {
+        let input: [u8; 6] = [0x8D, 0x78, 0x56, 0x34, 0x12, 0x07];
+
+        assert_eq!(
+            AuxiliarySecurityHeader::from_bytes(&input).unwrap_err(),
+            crate::parser::Error::ReservedSecurityControl(0x80)
+        );
+        assert!(input
+            .read_with::<AuxiliarySecurityHeader>(&mut 0, ParseMode::Strict)
+            .is_err());
+
+        let (hdr, len) =
+            AuxiliarySecurityHeader::from_bytes_with_mode(&input, ParseMode::Lenient).unwrap();
+        assert_eq!(len, 6);
+        assert_eq!(hdr.security_control_bits(), 0x0D);
+        assert_eq!(
+            input
+                .read_with::<AuxiliarySecurityHeader>(&mut 0, ParseMode::Lenient)
+                .unwrap(),
+            hdr
+        );
+    }
+
+    #[test]
+    fn from_bytes_returns_size() {
+        let input: [u8; 7] = [0x0D, 0x78, 0x56, 0x34, 0x12, 0x07, 0x42];
+
+        let (hdr, len) = AuxiliarySecurityHeader::from_bytes(&input).unwrap();
+
+        assert_eq!(len, 6);
+        assert_eq!(hdr.serialized_len(), len);
+        assert_eq!(hdr.frame_counter, Some(0x12345678));
+        assert_eq!(
+            hdr.key_identifier_mode,
+            KeyIdentifierMode::KeyIndex(KeyIndex(0x07))
+        );
+    }
+
     #[test]
     fn with_enc_mic_128_and_key8() {
-        let key: u64 = 0xABCD12345678ABCD;
+        let key: [u8; 8] = [0xCD, 0xAB, 0x78, 0x56, 0x34, 0x12, 0xCD, 0xAB];
         let key_index: u8 = 0xCD;
 
         let mut input: Vec<u8> = Vec::new();
         input.push(0x3F);
-        input.extend_from_slice(&key.to_le_bytes());
+        input.extend_from_slice(&key);
         input.push(key_index);
 
         let mut offset = 0;
@@ -179,14 +486,14 @@ mod tests {
 
     #[test]
     fn with_frame_counter_and_no_enc_mic_64_and_key4() {
-        let key: u32 = 0xDEADB33F;
+        let key: [u8; 4] = [0x3F, 0xB3, 0xAD, 0xDE];
         let key_index: u8 = 0x42;
         let frame_counter: u32 = 0x12345678;
 
         let mut input: Vec<u8> = Vec::new();
         input.push(0b00010010);
         input.extend_from_slice(&frame_counter.to_le_bytes());
-        input.extend_from_slice(&key.to_le_bytes());
+        input.extend_from_slice(&key);
         input.extend_from_slice(&key_index.to_be_bytes());
 
         let mut offset: usize = 0;
@@ -205,4 +512,217 @@ mod tests {
             KeyIdentifierMode::Key4(ShortKey(key), KeyIndex(key_index))
         );
     }
+
+    #[test]
+    fn write_round_trip() {
+        let mut levels: Vec<Option<SecurityLevel>> = Vec::new();
+        levels.push(None);
+        for mic in [Mic::None, Mic::Mic32, Mic::Mic64, Mic::Mic128] {
+            for encrypted in [false, true] {
+                // Security level 0, read back as no security level
+                if mic != Mic::None || encrypted {
+                    levels.push(Some(SecurityLevel {
+                        mic: mic.clone(),
+                        encrypted,
+                    }));
+                }
+            }
+        }
+
+        let key_identifier_modes = [
+            KeyIdentifierMode::Implicit,
+            KeyIdentifierMode::KeyIndex(KeyIndex(0x07)),
+            KeyIdentifierMode::Key4(ShortKey([0x3F, 0xB3, 0xAD, 0xDE]), KeyIndex(0x42)),
+            KeyIdentifierMode::Key8(
+                LongKey([0xCD, 0xAB, 0x78, 0x56, 0x34, 0x12, 0xCD, 0xAB]),
+                KeyIndex(0xCD),
+            ),
+        ];
+
+        for security_level in &levels {
+            for key_identifier_mode in &key_identifier_modes {
+                for frame_counter in [None, Some(0x12345678)] {
+                    let hdr = AuxiliarySecurityHeader {
+                        security_level: security_level.clone(),
+                        key_identifier_mode: key_identifier_mode.clone(),
+                        frame_counter,
+                    };
+                    let mut buf = [0u8; 14];
+
+                    let len = hdr.write_into(&mut buf).unwrap();
+                    assert_eq!(len, hdr.serialized_len());
+                    assert_eq!(
+                        AuxiliarySecurityHeader::from_bytes(&buf[..len]).unwrap(),
+                        (hdr.clone(), len)
+                    );
+                    assert!(matches!(
+                        hdr.write_into(&mut buf[..len - 1]),
+                        Err(crate::composer::Error::BufferTooSmall { needed }) if needed == len
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn key_source_byte_order() {
+        // Key source 01:02:03:04:05:06:07:08, followed by key index 0x09
+        let input: [u8; 15] = [
+            0x1B, 0x78, 0x56, 0x34, 0x12, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+            0xFF,
+        ];
+
+        let (hdr, len) = AuxiliarySecurityHeader::from_bytes(&input).unwrap();
+        assert_eq!(len, 14);
+        let KeyIdentifierMode::Key8(source, index) = &hdr.key_identifier_mode else {
+            panic!("expected an 8-octet key source");
+        };
+        assert_eq!(
+            source.as_bytes(),
+            [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+        );
+        assert_eq!(
+            *source,
+            LongKey::new([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08])
+        );
+        assert_eq!(index.value(), 0x09);
+
+        let mut buf = [0u8; 14];
+        assert_eq!(hdr.write_into(&mut buf), Ok(14));
+        assert_eq!(buf, input[..14]);
+
+        // 4-octet key source 01:02:03:04
+        let hdr = AuxiliarySecurityHeader {
+            security_level: SecurityLevel::from_bits(0x03).unwrap(),
+            key_identifier_mode: KeyIdentifierMode::Key4(
+                [0x01, 0x02, 0x03, 0x04].into(),
+                KeyIndex::new(0x09),
+            ),
+            frame_counter: None,
+        };
+        let mut buf = [0u8; 6];
+        assert_eq!(hdr.write_into(&mut buf), Ok(6));
+        assert_eq!(buf, [0x33, 0x01, 0x02, 0x03, 0x04, 0x09]);
+
+        let (hdr, _) = AuxiliarySecurityHeader::from_bytes(&buf).unwrap();
+        assert_eq!(
+            hdr.key_identifier_mode,
+            KeyIdentifierMode::Key4(ShortKey::new([0x01, 0x02, 0x03, 0x04]), KeyIndex(0x09))
+        );
+    }
+
+    #[test]
+    fn serialized_len() {
+        // (key identifier mode, length with frame counter)
+        let cases = [
+            (KeyIdentifierMode::Implicit, 5),
+            (KeyIdentifierMode::KeyIndex(KeyIndex(0x07)), 6),
+            (
+                KeyIdentifierMode::Key4(ShortKey([0x3F, 0xB3, 0xAD, 0xDE]), KeyIndex(0x42)),
+                10,
+            ),
+            (
+                KeyIdentifierMode::Key8(
+                    LongKey([0xCD, 0xAB, 0x78, 0x56, 0x34, 0x12, 0xCD, 0xAB]),
+                    KeyIndex(0xCD),
+                ),
+                14,
+            ),
+        ];
+
+        for (key_identifier_mode, len) in cases {
+            let mut hdr = AuxiliarySecurityHeader {
+                security_level: Some(SecurityLevel {
+                    mic: Mic::Mic64,
+                    encrypted: true,
+                }),
+                key_identifier_mode,
+                frame_counter: Some(0x12345678),
+            };
+            assert_eq!(hdr.serialized_len(), len);
+
+            // Frame counter suppressed
+            hdr.frame_counter = None;
+            assert_eq!(hdr.serialized_len(), len - 4);
+        }
+    }
+
+    #[test]
+    fn security_control_bits() {
+        let mut hdr = AuxiliarySecurityHeader {
+            security_level: Some(SecurityLevel {
+                mic: Mic::Mic128,
+                encrypted: true,
+            }),
+            key_identifier_mode: KeyIdentifierMode::Key8(LongKey([0; 8]), KeyIndex(0)),
+            frame_counter: None,
+        };
+        assert_eq!(hdr.security_control_bits(), 0x3F);
+
+        hdr.security_level = None;
+        hdr.key_identifier_mode = KeyIdentifierMode::Key4(ShortKey([0; 4]), KeyIndex(0));
+        hdr.frame_counter = Some(0);
+        assert_eq!(hdr.security_control_bits(), 0x10);
+    }
+
+    #[test]
+    fn security_level_bits() {
+        assert_eq!(SecurityLevel::from_bits(0), Ok(None));
+
+        for bits in 1..=7 {
+            let level = SecurityLevel::from_bits(bits).unwrap().unwrap();
+            assert_eq!(level.bits(), bits);
+            assert_eq!(level.encrypted, bits >= 4);
+        }
+
+        // ENC without MIC, only defined by IEEE 802.15.4-2006
+        assert_eq!(
+            SecurityLevel::from_bits(4),
+            Ok(Some(SecurityLevel {
+                mic: Mic::None,
+                encrypted: true
+            }))
+        );
+        assert_eq!(
+            SecurityLevel::from_bits(8),
+            Err(crate::parser::Error::InvalidSecurityLevel(8))
+        );
+    }
+
+    #[test]
+    fn security_level_ordering() {
+        let level = |bits| SecurityLevel::from_bits(bits).unwrap();
+
+        // Level 0 is the weakest
+        for bits in 1..=7 {
+            assert!(level(0) < level(bits));
+        }
+
+        assert!(level(1) < level(2));
+        assert!(level(2) < level(3));
+        assert!(level(1) < level(5));
+        assert!(level(4) < level(6));
+        assert!(level(7) > level(3));
+        assert!(level(6) >= level(6));
+
+        // Neither is as strong as the other
+        assert_eq!(level(2).partial_cmp(&level(4)), None);
+        assert_eq!(level(3).partial_cmp(&level(5)), None);
+    }
+
+    #[test]
+    fn write_security_control() {
+        let hdr = AuxiliarySecurityHeader {
+            security_level: Some(SecurityLevel {
+                mic: Mic::Mic32,
+                encrypted: true,
+            }),
+            key_identifier_mode: KeyIdentifierMode::KeyIndex(KeyIndex(0x07)),
+            frame_counter: Some(0x12345678),
+        };
+        let mut buf = [0u8; 6];
+
+        assert_eq!(hdr.write_into(&mut buf).unwrap(), 6);
+        assert_eq!(buf, [0x0D, 0x78, 0x56, 0x34, 0x12, 0x07]);
+    }
 }
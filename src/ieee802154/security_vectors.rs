@@ -0,0 +1,307 @@
+//! Secured frames of Annex C, run through the header parsing, nonce
+//! construction, a-data/m-data split and MIC placement of the crate.
+//!
+//! The CCM* outputs of the annex are replayed by [`Replay`], which checks
+//! that it is given the exact inputs of the annex.
+
+use alloc::vec::Vec;
+
+use crate::{
+    ieee802154::{
+        address::{AddressKind, LongAddress, PanId},
+        frame::{command::CommandId, Frame},
+        security::{
+            nonce, secure_frame, unsecure_frame, AeadCcmStar, Key, KeyDescriptor, KeyLookup,
+            KeyTable, Nonce, SecuredFrameLayout, SecurityError,
+        },
+        security_header::SecurityLevel,
+        Ieee802154,
+    },
+    parser::Parser,
+};
+
+const KEY: Key = [
+    0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE, 0xCF,
+];
+const SRC_LONG_ADDR: u64 = 0xACDE_4800_0000_0001;
+const FRAME_COUNTER: u32 = 5;
+const PAN: PanId = PanId::new(0x4321);
+
+struct Vector {
+    /// Security level of the frame
+    level: u8,
+    nonce: Nonce,
+    /// Secured frame, as transmitted
+    secured: &'static [u8],
+    /// Offset of the encrypted data, ended by the MIC
+    m_offset: usize,
+    /// Decrypted data, empty without encryption
+    plaintext: &'static [u8],
+    mic_len: usize,
+}
+
+impl Vector {
+    /// Frame before securing, the MIC being zeroed
+    fn unsecured(&self) -> Vec<u8> {
+        let mut frame = self.secured[..self.m_offset].to_vec();
+        frame.extend_from_slice(self.plaintext);
+        frame.resize(self.secured.len(), 0);
+        frame
+    }
+
+    fn mic_offset(&self) -> usize {
+        self.secured.len() - self.mic_len
+    }
+}
+
+/// Beacon frame of security level 2 (MIC-64)
+const BEACON: Vector = Vector {
+    level: 0x02,
+    nonce: [
+        0xAC, 0xDE, 0x48, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x02,
+    ],
+    secured: &[
+        0x08, 0xD0, 0x84, 0x21, 0x43, 0x01, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x02, 0x05,
+        0x00, 0x00, 0x00, 0x55, 0xCF, 0x00, 0x00, 0x51, 0x52, 0x53, 0x54, 0x22, 0x3B, 0xC1, 0xEC,
+        0x84, 0x1A, 0xB5, 0x53,
+    ],
+    m_offset: 26,
+    plaintext: &[],
+    mic_len: 8,
+};
+
+/// Data frame of security level 4 (ENC)
+const DATA: Vector = Vector {
+    level: 0x04,
+    nonce: [
+        0xAC, 0xDE, 0x48, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x04,
+    ],
+    secured: &[
+        0x69, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x01, 0x00,
+        0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x04, 0x05, 0x00, 0x00, 0x00, 0xD4, 0x3E, 0x02, 0x2B,
+    ],
+    m_offset: 26,
+    plaintext: &[0x61, 0x62, 0x63, 0x64],
+    mic_len: 0,
+};
+
+/// Association request of security level 6 (ENC-MIC-64), the command
+/// identifier being left in the clear
+const COMMAND: Vector = Vector {
+    level: 0x06,
+    nonce: [
+        0xAC, 0xDE, 0x48, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x06,
+    ],
+    secured: &[
+        0x2B, 0xDC, 0x84, 0x21, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0xFF, 0xFF,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x48, 0xDE, 0xAC, 0x06, 0x05, 0x00, 0x00, 0x00, 0x01, 0xD8,
+        0x4F, 0xDE, 0x52, 0x90, 0x61, 0xF9, 0xC6, 0xF1,
+    ],
+    m_offset: 29,
+    plaintext: &[0xCE],
+    mic_len: 8,
+};
+
+/// CCM* replaying the outputs of a vector.
+struct Replay<'v>(&'v Vector);
+
+impl AeadCcmStar for Replay<'_> {
+    fn encrypt(
+        &mut self,
+        key: &Key,
+        nonce: &Nonce,
+        a_data: &[u8],
+        m_data: &mut [u8],
+        mic: &mut [u8],
+    ) -> Result<(), SecurityError> {
+        let vector = self.0;
+
+        assert_eq!(key, &KEY);
+        assert_eq!(nonce, &vector.nonce);
+        assert_eq!(a_data, &vector.secured[..vector.m_offset]);
+        assert_eq!(m_data, vector.plaintext);
+        assert_eq!(mic.len(), vector.mic_len);
+
+        m_data.copy_from_slice(&vector.secured[vector.m_offset..vector.mic_offset()]);
+        mic.copy_from_slice(&vector.secured[vector.mic_offset()..]);
+
+        Ok(())
+    }
+
+    fn decrypt(
+        &mut self,
+        key: &Key,
+        nonce: &Nonce,
+        a_data: &[u8],
+        m_data: &mut [u8],
+        mic: &[u8],
+    ) -> Result<(), SecurityError> {
+        let vector = self.0;
+
+        assert_eq!(key, &KEY);
+        assert_eq!(nonce, &vector.nonce);
+        assert_eq!(a_data, &vector.secured[..vector.m_offset]);
+        assert_eq!(
+            m_data,
+            &vector.secured[vector.m_offset..vector.mic_offset()]
+        );
+
+        if mic != &vector.secured[vector.mic_offset()..] {
+            return Err(SecurityError::AuthenticationFailed);
+        }
+        m_data.copy_from_slice(vector.plaintext);
+
+        Ok(())
+    }
+}
+
+/// Key of the originator of all the vectors
+fn store() -> KeyTable<1> {
+    let mut store = KeyTable::new();
+    let device: AddressKind = LongAddress::new(PAN, SRC_LONG_ADDR).into();
+
+    store
+        .add_key(KeyLookup::Implicit(device), KeyDescriptor { key: KEY })
+        .unwrap();
+
+    store
+}
+
+#[test]
+fn security_header_and_nonce() {
+    for vector in [&BEACON, &DATA, &COMMAND] {
+        let frame = Ieee802154::parse(vector.secured).unwrap();
+        let aux = match &frame {
+            Frame::Beacon(beacon) => beacon.header.aux.as_ref().unwrap(),
+            Frame::Data(data) => data.header.aux.as_ref().unwrap(),
+            Frame::MacCommand(command) => command.header.aux.as_ref().unwrap(),
+            _ => panic!("unexpected frame kind"),
+        };
+        let level = aux.security_level.as_ref().unwrap();
+
+        assert_eq!(aux.security_control_bits(), vector.level);
+        assert_eq!(aux.serialized_len(), 5);
+        assert_eq!(aux.frame_counter, Some(FRAME_COUNTER));
+        assert_eq!(level.mic.size(), vector.mic_len);
+        assert_eq!(nonce(SRC_LONG_ADDR, FRAME_COUNTER, level), vector.nonce);
+    }
+}
+
+#[test]
+fn mic_placement() {
+    let Frame::Beacon(beacon) = Ieee802154::parse(BEACON.secured).unwrap() else {
+        panic!("expected a beacon frame");
+    };
+    assert_eq!(beacon.payload.data, &[0x51, 0x52, 0x53, 0x54]);
+    assert_eq!(beacon.mic, &BEACON.secured[26..]);
+
+    let Frame::MacCommand(command) = Ieee802154::parse(COMMAND.secured).unwrap() else {
+        panic!("expected a MAC command frame");
+    };
+    assert_eq!(command.command_id, CommandId::AssociationRequest);
+    assert_eq!(command.content, &[0xD8]);
+    assert_eq!(command.mic, &COMMAND.secured[30..]);
+
+    let Frame::Data(data) = Ieee802154::parse(DATA.secured).unwrap() else {
+        panic!("expected a data frame");
+    };
+    assert_eq!(data.payload, &[0xD4, 0x3E, 0x02, 0x2B]);
+    assert!(data.mic.is_empty());
+}
+
+#[test]
+fn secured_frame_layout() {
+    for vector in [&BEACON, &DATA, &COMMAND] {
+        let frame = Ieee802154::parse(vector.secured).unwrap();
+        let len = vector.secured.len();
+
+        assert_eq!(
+            SecuredFrameLayout::of(&frame),
+            Ok(SecuredFrameLayout {
+                a_data: 0..vector.m_offset,
+                m_data: vector.m_offset..vector.mic_offset(),
+                mic: vector.mic_offset()..len,
+            })
+        );
+    }
+
+    // Encrypted beacon, of which only the beacon payload is private
+    let Frame::Beacon(mut beacon) = Ieee802154::parse(BEACON.secured).unwrap() else {
+        panic!("expected a beacon frame");
+    };
+    beacon.header.aux.as_mut().unwrap().security_level = SecurityLevel::from_bits(0x06).unwrap();
+    assert_eq!(
+        SecuredFrameLayout::of(&Frame::Beacon(beacon)),
+        Ok(SecuredFrameLayout {
+            a_data: 0..22,
+            m_data: 22..26,
+            mic: 26..34,
+        })
+    );
+
+    // Security level 0, authenticating nothing
+    let Frame::MacCommand(mut command) = Ieee802154::parse(COMMAND.secured).unwrap() else {
+        panic!("expected a MAC command frame");
+    };
+    command.header.aux.as_mut().unwrap().security_level = None;
+    command.mic = &[];
+    assert_eq!(
+        SecuredFrameLayout::of(&Frame::MacCommand(command)),
+        Ok(SecuredFrameLayout {
+            a_data: 0..30,
+            m_data: 30..30,
+            mic: 30..30,
+        })
+    );
+
+    let unsecured = Ieee802154::parse(&[
+        0x41, 0x88, 0x01, 0x34, 0x12, 0xFF, 0xFF, 0xCD, 0xAB, 0xDE, 0xAD,
+    ])
+    .unwrap();
+    assert_eq!(
+        SecuredFrameLayout::of(&unsecured),
+        Err(SecurityError::Unsecured)
+    );
+}
+
+#[test]
+fn secure_vectors() {
+    for vector in [&BEACON, &DATA, &COMMAND] {
+        let unsecured = vector.unsecured();
+        let frame = Ieee802154::parse(&unsecured).unwrap();
+        let mut buf = [0u8; 64];
+
+        let len = secure_frame(&frame, &KEY, SRC_LONG_ADDR, &mut Replay(vector), &mut buf).unwrap();
+        assert_eq!(&buf[..len], vector.secured);
+    }
+}
+
+#[test]
+fn unsecure_vectors() {
+    let store = store();
+
+    for vector in [&BEACON, &DATA, &COMMAND] {
+        let mut buf = vector.secured.to_vec();
+        let frame = unsecure_frame(&mut buf, &store, None, &mut Replay(vector)).unwrap();
+        let expected = vector.unsecured();
+        let len = vector.mic_offset();
+
+        assert!(matches!(
+            (&frame, vector.level),
+            (Frame::Beacon(_), 0x02) | (Frame::Data(_), 0x04) | (Frame::MacCommand(_), 0x06)
+        ));
+        assert_eq!(buf[..len], expected[..len]);
+
+        if vector.mic_len == 0 {
+            continue;
+        }
+
+        // Tampered MIC
+        let mut buf = vector.secured.to_vec();
+        *buf.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            unsecure_frame(&mut buf, &store, None, &mut Replay(vector)),
+            Err(SecurityError::AuthenticationFailed)
+        );
+    }
+}
@@ -1,10 +1,13 @@
 #![no_std]
-#![feature(const_trait_impl)]
+#![cfg_attr(feature = "nightly", feature(const_trait_impl, const_cmp))]
 
 extern crate alloc;
+// The `arbitrary` crate and its derive rely on std
+#[cfg(feature = "arbitrary")]
+extern crate std;
 
 use alloc::vec::Vec;
-use composer::Composer;
+use composer::{ComposeInto, Composer};
 use error::Error;
 use parser::Parser;
 
@@ -20,6 +23,39 @@ pub fn parse<'a, P: Parser<'a>>(raw: &'a [u8]) -> Result<P::Value, Error> {
     Ok(P::parse(raw)?)
 }
 
+/// Same as [`parse`], also returning the number of bytes consumed from `raw`.
+pub fn parse_with_len<'a, P: Parser<'a>>(raw: &'a [u8]) -> Result<(P::Value, usize), Error> {
+    Ok(P::parse_with_len(raw)?)
+}
+
 pub fn compose<'a, C: Composer<'a>>(input: &'a C::Value) -> Result<Vec<u8>, Error> {
     Ok(C::compose(input)?)
 }
+
+/// Same as [`compose`], writing into `buf` instead of allocating. Returns
+/// the number of bytes written.
+pub fn compose_into<'a, C: ComposeInto<'a>>(
+    input: &'a C::Value,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    Ok(C::compose_into(input, buf)?)
+}
+
+/// Same as [`compose_into`], the frame being returned in a
+/// [`heapless::Vec`] of capacity `N`.
+///
+/// # Errors
+///
+/// If the frame does not fit in `N` bytes, returns `BufferTooSmall`.
+pub fn compose_heapless<'a, C: ComposeInto<'a>, const N: usize>(
+    input: &'a C::Value,
+) -> Result<heapless::Vec<u8, N>, composer::Error> {
+    let mut frame = heapless::Vec::new();
+    // Cannot fail, the length being the capacity
+    let _ = frame.resize_default(N);
+
+    let len = C::compose_into(input, &mut frame)?;
+    frame.truncate(len);
+
+    Ok(frame)
+}
@@ -1,16 +1,157 @@
 use thiserror_no_std::Error;
 
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     #[error("Invalid header")]
     InvalidHeader,
     #[error("Invalid payload")]
     InvalidPayload,
+    #[error("Truncated input")]
+    Truncated,
+    #[error("Invalid frame type: {0:#x}")]
+    InvalidFrameKind(u8),
+    #[error("Invalid frame version: {0:#x}")]
+    InvalidFrameVersion(u8),
+    #[error("Invalid addressing mode: {0:#x}")]
+    InvalidAddressingMode(u8),
+    #[error("Invalid PAN ID compression for the addressing modes")]
+    InvalidPanIdCompression,
+    #[error("Invalid security level: {0:#x}")]
+    InvalidSecurityLevel(u8),
+    #[error("Invalid key identifier mode: {0:#x}")]
+    InvalidKeyIdMode(u8),
+    #[error("Auxiliary security header truncated")]
+    TruncatedSecurityHeader,
+    #[error("Reserved security control bits set: {0:#x}")]
+    ReservedSecurityControl(u8),
+    #[error("Invalid command identifier: {0:#x}")]
+    InvalidCommandId(u8),
+    #[cfg(feature = "ieee802154")]
+    #[error("Invalid content for command {id:?}")]
+    InvalidCommand {
+        id: crate::ieee802154::frame::command::CommandId,
+    },
+    #[error("Command identifier encrypted along with the payload IEs")]
+    EncryptedCommand,
+    #[error("Invalid association status: {0:#x}")]
+    InvalidAssociationStatus(u8),
+    #[error("Invalid disassociation reason: {0:#x}")]
+    InvalidDisassociationReason(u8),
+    #[error("Frame counter suppressed before IEEE 802.15.4-2015")]
+    InvalidFrameCounterSuppression,
+    #[error("Invalid information element {id:#x}")]
+    InvalidIe { id: u16 },
+    #[error("Invalid GTS specification")]
+    InvalidGtsSpec,
+    #[error("Invalid superframe specification")]
+    InvalidSuperFrameSpec,
+    #[error("FCS does not match the frame")]
+    InvalidFcs,
+    #[error("Invalid input: {0}")]
+    InvalidInput(InputError),
+    #[error("Frame too long: {len} bytes, at most {max} allowed")]
+    FrameTooLong { len: usize, max: usize },
+    #[error("{count} bytes left after the end of the frame")]
+    TrailingBytes { count: usize },
+}
+
+impl From<byte::Error> for Error {
+    fn from(value: byte::Error) -> Self {
+        match value {
+            byte::Error::Incomplete | byte::Error::BadOffset(_) => Error::Truncated,
+            byte::Error::BadInput { err } => Error::InvalidInput(InputError(err)),
+        }
+    }
+}
+
+/// Reason given by a decoder rejecting its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputError(pub &'static str);
+
+impl core::fmt::Display for InputError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// `str` has no `uDebug` implementation, the reason is written quoted.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for InputError {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        f.write_char('"')?;
+        f.write_str(self.0)?;
+        f.write_char('"')
+    }
+}
+
+/// How reserved values found in the input are handled.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Reserved values are errors
+    #[default]
+    Strict,
+    /// Reserved values are kept in the parsed structures
+    Lenient,
+}
+
+/// Part of the frame that was being decoded when an error occurred.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    ControlField,
+    SequenceNumber,
+    Addressing,
+    SecurityHeader,
+    InformationElements,
+    SuperFrame,
+    Gts,
+    PendingAddress,
+    Payload,
+}
+
+/// Error returned by a [`Parser`], along with the location in the input
+/// where decoding stopped.
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("{kind} at offset {offset} ({section:?})")]
+pub struct ParseError {
+    pub kind: Error,
+    /// Offset in bytes from the start of the input.
+    pub offset: usize,
+    pub section: Section,
+}
+
+impl ParseError {
+    pub const fn new(kind: Error, offset: usize, section: Section) -> Self {
+        ParseError {
+            kind,
+            offset,
+            section,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        value.kind
+    }
 }
 
 pub trait Parser<'a> {
     type Value;
 
-    fn parse(slice: &'a [u8]) -> Result<Self::Value, Error>;
+    fn parse(slice: &'a [u8]) -> Result<Self::Value, ParseError>;
+
+    /// Same as [`Parser::parse`], also returning the number of bytes
+    /// consumed from `slice`. The default implementation assumes the whole
+    /// input is consumed.
+    fn parse_with_len(slice: &'a [u8]) -> Result<(Self::Value, usize), ParseError> {
+        Self::parse(slice).map(|value| (value, slice.len()))
+    }
 }